@@ -4,6 +4,8 @@ cd "$(dirname "$0")" || exit 1
 exec curl \
 	https://www.w3.org/2018/credentials/v1 -o w3c-2018-credentials-v1.jsonld \
 	https://www.w3.org/2018/credentials/examples/v1 -o w3c-2018-credentials-examples-v1.jsonld \
+	https://www.w3.org/ns/credentials/v2 -o w3c-ns-credentials-v2.jsonld \
+	https://www.w3.org/ns/credentials/examples/v2 -o w3c-ns-credentials-examples-v2.jsonld \
 	https://www.w3.org/ns/odrl.jsonld -o w3c-odrl.jsonld \
 	https://schema.org/docs/jsonldcontext.jsonld -o schema.org.jsonld \
 	https://w3id.org/security/v1 -o w3id-security-v1.jsonld \
@@ -15,6 +17,8 @@ exec curl \
 	https://w3c-ccg.github.io/lds-jws2020/contexts/lds-jws2020-v1.json -o lds-jws2020-v1.jsonld \
 	https://w3id.org/security/suites/jws-2020/v1 -o w3id-jws2020-v1.jsonld \
 	https://w3id.org/security/suites/ed25519-2020/v1 -o w3id-ed25519-signature-2020-v1.jsonld \
+	https://w3id.org/security/data-integrity/v1 -o w3id-data-integrity-v1.jsonld \
+	https://w3id.org/security/data-integrity/v2 -o w3id-data-integrity-v2.jsonld \
 	https://w3id.org/security/suites/blockchain-2021/v1 -o w3id-blockchain-2021-v1.jsonld \
 	https://w3id.org/citizenship/v1 -o w3c-ccg-citizenship-v1.jsonld \
 	https://w3id.org/vaccination/v1 -o w3c-ccg-vaccination-v1.jsonld \
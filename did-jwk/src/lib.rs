@@ -0,0 +1,307 @@
+use async_trait::async_trait;
+
+use ssi::did::{
+    Context, Contexts, DIDMethod, Document, Source, VerificationMethod, VerificationMethodMap,
+    DEFAULT_CONTEXT, DIDURL,
+};
+use ssi::did_resolve::{
+    DIDResolver, DocumentMetadata, ResolutionInputMetadata, ResolutionMetadata, ERROR_INVALID_DID,
+};
+use ssi::jsonld::W3ID_JWS2020_V1_CONTEXT;
+use ssi::jwk::{Params, JWK};
+
+/// did:jwk Method
+///
+/// [Specification](https://github.com/quartzjer/did-jwk/blob/main/spec.md)
+pub struct DIDJWK;
+
+/// Decode the method-specific identifier into a public JWK.
+fn decode_jwk(method_specific_id: &str) -> Option<JWK> {
+    let data = base64::decode_config(method_specific_id, base64::URL_SAFE_NO_PAD).ok()?;
+    let jwk: JWK = serde_json::from_slice(&data).ok()?;
+    // The JWK must not contain private key material.
+    if matches!(jwk.params, Params::Symmetric(_)) || jwk.params != jwk.params.to_public() {
+        return None;
+    }
+    Some(jwk)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DIDResolver for DIDJWK {
+    async fn resolve(
+        &self,
+        did: &str,
+        _input_metadata: &ResolutionInputMetadata,
+    ) -> (
+        ResolutionMetadata,
+        Option<Document>,
+        Option<DocumentMetadata>,
+    ) {
+        let jwk = match did.strip_prefix("did:jwk:").and_then(decode_jwk) {
+            Some(jwk) => jwk,
+            None => {
+                return (
+                    ResolutionMetadata::from_error(ERROR_INVALID_DID),
+                    None,
+                    None,
+                )
+            }
+        };
+        let vm_didurl = DIDURL {
+            did: did.to_string(),
+            fragment: Some("0".to_string()),
+            ..Default::default()
+        };
+        let vm_ref = || Some(vec![VerificationMethod::DIDURL(vm_didurl.clone())]);
+        // The "use" member restricts the key to either signing or encryption. Without it, the
+        // relationships are those the key type allows.
+        let (signing, encryption) = match (jwk.public_key_use.as_deref(), &jwk.params) {
+            (Some("sig"), _) => (true, false),
+            (Some("enc"), _) => (false, true),
+            (_, Params::OKP(okp)) if okp.curve == "X25519" || okp.curve == "X448" => (false, true),
+            (_, Params::OKP(_)) => (true, false),
+            _ => (true, true),
+        };
+        let doc = Document {
+            context: Contexts::Many(vec![
+                Context::URI(DEFAULT_CONTEXT.to_string()),
+                Context::URI(W3ID_JWS2020_V1_CONTEXT.to_string()),
+            ]),
+            id: did.to_string(),
+            verification_method: Some(vec![VerificationMethod::Map(VerificationMethodMap {
+                id: vm_didurl.to_string(),
+                type_: "JsonWebKey2020".to_string(),
+                controller: did.to_string(),
+                public_key_jwk: Some(jwk.clone()),
+                ..Default::default()
+            })]),
+            assertion_method: if signing { vm_ref() } else { None },
+            authentication: if signing { vm_ref() } else { None },
+            capability_invocation: if signing { vm_ref() } else { None },
+            capability_delegation: if signing { vm_ref() } else { None },
+            key_agreement: if encryption { vm_ref() } else { None },
+            ..Default::default()
+        };
+        (
+            ResolutionMetadata::default(),
+            Some(doc),
+            Some(DocumentMetadata::default()),
+        )
+    }
+}
+
+impl DIDMethod for DIDJWK {
+    fn name(&self) -> &'static str {
+        "jwk"
+    }
+
+    fn generate(&self, source: &Source) -> Option<String> {
+        let jwk = match source {
+            Source::Key(jwk) => jwk,
+            _ => return None,
+        };
+        if let Params::Symmetric(_) = jwk.params {
+            return None;
+        }
+        // Canonicalize the JSON (RFC 8785) so that a key always yields the same DID.
+        let json = serde_jcs::to_string(&jwk.to_public()).ok()?;
+        Some(format!(
+            "did:jwk:{}",
+            base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+        ))
+    }
+
+    fn to_resolver(&self) -> &dyn DIDResolver {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssi::did_resolve::{dereference, Content, DereferencingInputMetadata};
+    use ssi::jwk::{Base64urlUInt, OctetParams};
+
+    #[async_std::test]
+    async fn from_did_jwk_p256() {
+        // https://github.com/quartzjer/did-jwk/blob/main/spec.md#p-256
+        let did = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+        let (res_meta, doc, _) = DIDJWK
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        let vm_id = format!("{}#0", did);
+        assert_eq!(doc.verification_method.as_ref().unwrap().len(), 1);
+        for relationship in [
+            &doc.assertion_method,
+            &doc.authentication,
+            &doc.capability_invocation,
+            &doc.capability_delegation,
+            &doc.key_agreement,
+        ] {
+            assert_eq!(
+                relationship.as_ref().unwrap()[0].get_id(&doc.id),
+                vm_id.as_str()
+            );
+        }
+
+        let (res_meta, object, _) =
+            dereference(&DIDJWK, &vm_id, &DereferencingInputMetadata::default()).await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(ssi::did::Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        assert_eq!(vm.type_, "JsonWebKey2020");
+        assert_eq!(vm.controller, did);
+        let jwk = vm.public_key_jwk.unwrap();
+        match jwk.params {
+            Params::EC(ref params) => {
+                assert_eq!(params.curve.as_deref(), Some("P-256"));
+                assert_eq!(
+                    String::from(params.x_coordinate.as_ref().unwrap()),
+                    "acbIQiuMs3i8_uszEjJ2tpTtRM4EU3yz91PH6CdH2V0"
+                );
+            }
+            _ => panic!("Expected EC key"),
+        }
+        assert_eq!(DIDJWK.generate(&Source::Key(&jwk)).unwrap(), did);
+    }
+
+    #[async_std::test]
+    async fn from_did_jwk_x25519() {
+        // https://github.com/quartzjer/did-jwk/blob/main/spec.md#x25519
+        let did = "did:jwk:eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ1c2UiOiJlbmMiLCJ4IjoiM3A3YmZYdDl3YlRUVzJIQzdPUTFOei1EUThoYmVHZE5yZngtRkctSUswOCJ9";
+        let (res_meta, doc, _) = DIDJWK
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        // "use": "enc" restricts the key to key agreement.
+        assert_eq!(doc.assertion_method, None);
+        assert_eq!(doc.authentication, None);
+        assert_eq!(doc.capability_invocation, None);
+        assert_eq!(doc.capability_delegation, None);
+        assert_eq!(
+            doc.key_agreement.as_ref().unwrap()[0].get_id(&doc.id),
+            format!("{}#0", did)
+        );
+        let keys = doc.get_key_agreement_keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        match keys[0].params {
+            Params::OKP(ref params) => assert_eq!(params.curve, "X25519"),
+            _ => panic!("Expected OKP key"),
+        }
+    }
+
+    #[async_std::test]
+    async fn generate_resolve_roundtrip() {
+        let mut keys = vec![
+            JWK::generate_ed25519().unwrap(),
+            JWK::from(Params::OKP(OctetParams {
+                curve: "X25519".to_string(),
+                public_key: Base64urlUInt(vec![9; 32]),
+                private_key: Some(Base64urlUInt(vec![1; 32])),
+            })),
+            serde_json::from_str(include_str!("../../tests/rsa2048-2020-08-25.json")).unwrap(),
+        ];
+        #[cfg(feature = "secp256k1")]
+        keys.push(JWK::generate_secp256k1().unwrap());
+        #[cfg(feature = "secp256r1")]
+        keys.push(JWK::generate_p256().unwrap());
+        let mut sig_key = JWK::generate_ed25519().unwrap();
+        sig_key.public_key_use = Some("sig".to_string());
+        keys.push(sig_key);
+
+        for key in keys {
+            let did = DIDJWK.generate(&Source::Key(&key)).unwrap();
+            let (res_meta, doc, _) = DIDJWK
+                .resolve(&did, &ResolutionInputMetadata::default())
+                .await;
+            assert_eq!(res_meta.error, None);
+            let doc = doc.unwrap();
+            let vm = match &doc.verification_method.as_ref().unwrap()[0] {
+                VerificationMethod::Map(vm) => vm,
+                _ => unreachable!(),
+            };
+            let public_jwk = vm.public_key_jwk.as_ref().unwrap();
+            assert_eq!(public_jwk, &key.to_public());
+            let (signing, encryption) = match key.params {
+                _ if key.public_key_use.as_deref() == Some("sig") => (true, false),
+                Params::OKP(ref okp) if okp.curve == "X25519" => (false, true),
+                Params::OKP(_) => (true, false),
+                _ => (true, true),
+            };
+            assert_eq!(doc.assertion_method.is_some(), signing);
+            assert_eq!(doc.authentication.is_some(), signing);
+            assert_eq!(doc.key_agreement.is_some(), encryption);
+        }
+    }
+
+    #[async_std::test]
+    async fn reject_private_key() {
+        let key = JWK::generate_ed25519().unwrap();
+        let did = format!(
+            "did:jwk:{}",
+            base64::encode_config(serde_json::to_vec(&key).unwrap(), base64::URL_SAFE_NO_PAD)
+        );
+        let (res_meta, doc, _) = DIDJWK
+            .resolve(&did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, Some(ERROR_INVALID_DID.to_string()));
+        assert!(doc.is_none());
+
+        let oct = JWK::from(Params::Symmetric(ssi::jwk::SymmetricParams {
+            key_value: Some(Base64urlUInt(vec![0; 32])),
+        }));
+        assert_eq!(DIDJWK.generate(&Source::Key(&oct)), None);
+
+        let (res_meta, _, _) = DIDJWK
+            .resolve("did:jwk:not-a-jwk", &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, Some(ERROR_INVALID_DID.to_string()));
+    }
+
+    #[async_std::test]
+    async fn credential_prove_verify_did_jwk() {
+        use ssi::vc::{get_verification_method, Credential, Issuer, LinkedDataProofOptions, URI};
+        let vc_str = r###"{
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "id": "http://example.org/credentials/3731",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:30e07a529f32d234f6181736bd3",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###;
+        let mut vc: Credential = Credential::from_json_unsigned(vc_str).unwrap();
+
+        let key = JWK::generate_ed25519().unwrap();
+        let did = DIDJWK.generate(&Source::Key(&key)).unwrap();
+        let verification_method = get_verification_method(&did, &DIDJWK).await.unwrap();
+        let mut issue_options = LinkedDataProofOptions::default();
+        let mut context_loader = ssi::jsonld::ContextLoader::default();
+        vc.issuer = Some(Issuer::URI(URI::String(did.clone())));
+        issue_options.verification_method = Some(URI::String(verification_method));
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDJWK, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        vc.validate().unwrap();
+        let verification_result = vc.verify(None, &DIDJWK, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        // test that issuer is verified
+        vc.issuer = Some(Issuer::URI(URI::String("did:example:bad".to_string())));
+        assert!(!vc
+            .verify(None, &DIDJWK, &mut context_loader)
+            .await
+            .errors
+            .is_empty());
+    }
+}
@@ -19,10 +19,12 @@ use ssi::jwk::secp256k1_parse;
 use ssi::jwk::{Base64urlUInt, OctetParams, Params, JWK};
 
 const DID_KEY_ED25519_PREFIX: [u8; 2] = [0xed, 0x01];
+const DID_KEY_X25519_PREFIX: [u8; 2] = [0xec, 0x01];
 const DID_KEY_SECP256K1_PREFIX: [u8; 2] = [0xe7, 0x01];
 const DID_KEY_BLS12381_G2_PREFIX: [u8; 2] = [0xeb, 0x01];
 const DID_KEY_P256_PREFIX: [u8; 2] = [0x80, 0x24];
 const DID_KEY_P384_PREFIX: [u8; 2] = [0x81, 0x24];
+const DID_KEY_P521_PREFIX: [u8; 2] = [0x82, 0x24];
 const DID_KEY_RSA_PREFIX: [u8; 2] = [0x85, 0x24];
 
 #[derive(Error, Debug)]
@@ -51,6 +53,7 @@ impl DIDResolver for DIDKey {
     ) {
         let vm_type;
         let vm_type_iri;
+        let mut key_agreement_params = None;
         if !did.starts_with("did:key:") {
             return (
                 ResolutionMetadata {
@@ -112,7 +115,7 @@ impl DIDResolver for DIDKey {
             }
             vm_type = "Ed25519VerificationKey2018".to_string();
             vm_type_iri = "https://w3id.org/security#Ed25519VerificationKey2018".to_string();
-            JWK {
+            let jwk = JWK {
                 params: Params::OKP(OctetParams {
                     curve: "Ed25519".to_string(),
                     public_key: Base64urlUInt(data[2..].to_vec()),
@@ -126,7 +129,37 @@ impl DIDResolver for DIDKey {
                 x509_certificate_chain: None,
                 x509_thumbprint_sha1: None,
                 x509_thumbprint_sha256: None,
+            };
+            // https://w3c-ccg.github.io/did-method-key/#encryption-method-creation-algorithm
+            match jwk.ed25519_to_x25519() {
+                Ok(JWK {
+                    params: Params::OKP(params),
+                    ..
+                }) => key_agreement_params = Some(params),
+                _ => {
+                    return (
+                        ResolutionMetadata::from_error(ERROR_INVALID_DID),
+                        None,
+                        None,
+                    )
+                }
             }
+            jwk
+        } else if data[0] == DID_KEY_X25519_PREFIX[0] && data[1] == DID_KEY_X25519_PREFIX[1] {
+            if data.len() - 2 != 32 {
+                return (
+                    ResolutionMetadata::from_error(ERROR_INVALID_DID),
+                    None,
+                    None,
+                );
+            }
+            vm_type = "X25519KeyAgreementKey2019".to_string();
+            vm_type_iri = "https://w3id.org/security#X25519KeyAgreementKey2019".to_string();
+            JWK::from(Params::OKP(OctetParams {
+                curve: "X25519".to_string(),
+                public_key: Base64urlUInt(data[2..].to_vec()),
+                private_key: None,
+            }))
         } else if data[0] == DID_KEY_SECP256K1_PREFIX[0] && data[1] == DID_KEY_SECP256K1_PREFIX[1] {
             if data.len() - 2 != 33 {
                 return (
@@ -184,6 +217,22 @@ impl DIDResolver for DIDKey {
                 None,
                 None,
             );
+        } else if data[0] == DID_KEY_P521_PREFIX[0] && data[1] == DID_KEY_P521_PREFIX[1] {
+            #[cfg(feature = "ssi_p521")]
+            match ssi::jwk::p521_parse(&data[2..]) {
+                Ok(jwk) => {
+                    vm_type = "JsonWebKey2020".to_string();
+                    vm_type_iri = "https://w3id.org/security#JsonWebKey2020".to_string();
+                    jwk
+                }
+                Err(err) => return (ResolutionMetadata::from_error(&err.to_string()), None, None),
+            }
+            #[cfg(not(feature = "ssi_p521"))]
+            return (
+                ResolutionMetadata::from_error("did:key type P-521 not supported"),
+                None,
+                None,
+            );
         } else if data[0] == DID_KEY_RSA_PREFIX[0] && data[1] == DID_KEY_RSA_PREFIX[1] {
             match rsa_x509_pub_parse(&data[2..]) {
                 Ok(jwk) => {
@@ -224,12 +273,44 @@ impl DIDResolver for DIDKey {
                 None,
             );
         };
+        let key_agreement_only = vm_type == "X25519KeyAgreementKey2019";
         context.insert(vm_type.to_string(), Value::String(vm_type_iri));
         let vm_didurl = DIDURL {
             did: did.to_string(),
             fragment: Some(method_specific_id.to_string()),
             ..Default::default()
         };
+        let key_agreement = if key_agreement_only {
+            Some(vec![VerificationMethod::DIDURL(vm_didurl.clone())])
+        } else {
+            key_agreement_params.map(|params| {
+                context.insert(
+                    "X25519KeyAgreementKey2019".to_string(),
+                    Value::String(
+                        "https://w3id.org/security#X25519KeyAgreementKey2019".to_string(),
+                    ),
+                );
+                let fragment = multibase::encode(
+                    multibase::Base::Base58Btc,
+                    [DID_KEY_X25519_PREFIX.to_vec(), params.public_key.0.clone()].concat(),
+                );
+                vec![VerificationMethod::Map(VerificationMethodMap {
+                    id: format!("{}#{}", did, fragment),
+                    type_: "X25519KeyAgreementKey2019".to_string(),
+                    controller: did.to_string(),
+                    public_key_jwk: Some(JWK::from(Params::OKP(params))),
+                    ..Default::default()
+                })]
+            })
+        };
+        let (authentication, assertion_method) = if key_agreement_only {
+            (None, None)
+        } else {
+            (
+                Some(vec![VerificationMethod::DIDURL(vm_didurl.clone())]),
+                Some(vec![VerificationMethod::DIDURL(vm_didurl)]),
+            )
+        };
         let doc = Document {
             context: Contexts::Many(vec![
                 Context::URI(DEFAULT_CONTEXT.to_string()),
@@ -243,8 +324,9 @@ impl DIDResolver for DIDKey {
                 public_key_jwk: Some(jwk),
                 ..Default::default()
             })]),
-            authentication: Some(vec![VerificationMethod::DIDURL(vm_didurl.clone())]),
-            assertion_method: Some(vec![VerificationMethod::DIDURL(vm_didurl)]),
+            authentication,
+            assertion_method,
+            key_agreement,
             ..Default::default()
         };
         (
@@ -275,6 +357,14 @@ impl DIDMethod for DIDKey {
         let did = match jwk.params {
             Params::OKP(ref params) => {
                 match &params.curve[..] {
+                    "X25519" => {
+                        "did:key:".to_string()
+                            + &multibase::encode(
+                                multibase::Base::Base58Btc,
+                                [DID_KEY_X25519_PREFIX.to_vec(), params.public_key.0.clone()]
+                                    .concat(),
+                            )
+                    }
                     "Ed25519" => {
                         "did:key:".to_string()
                             + &multibase::encode(
@@ -352,6 +442,18 @@ impl DIDMethod for DIDKey {
                                 [DID_KEY_P384_PREFIX.to_vec(), pk_bytes].concat(),
                             )
                     }
+                    #[cfg(feature = "ssi_p521")]
+                    "P-521" => {
+                        let pk_bytes = match ssi::jwk::p521_serialize(params) {
+                            Ok(pk) => pk,
+                            Err(_err) => return None,
+                        };
+                        "did:key:".to_string()
+                            + &multibase::encode(
+                                multibase::Base::Base58Btc,
+                                [DID_KEY_P521_PREFIX.to_vec(), pk_bytes].concat(),
+                            )
+                    }
                     //_ => return Some(Err(DIDKeyError::UnsupportedCurve(params.curve.clone()))),
                     _ => return None,
                 }
@@ -393,6 +495,43 @@ mod tests {
         vm.public_key_jwk.unwrap();
     }
 
+    #[async_std::test]
+    async fn did_key_key_agreement() {
+        // https://w3c-ccg.github.io/did-method-key/#ed25519-x25519
+        let did = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp";
+        let x25519_id = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp#z6LShs9GGnqk85isEBzzshkuVWrVKsRp24GnDuHk8QWkARMW";
+        let (res_meta, doc, _doc_meta) = DIDKey
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let keys = doc.unwrap().get_key_agreement_keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key_id.as_deref(), Some(x25519_id));
+
+        let (res_meta, object, _meta) =
+            dereference(&DIDKey, x25519_id, &DereferencingInputMetadata::default()).await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        assert_eq!(vm.type_, "X25519KeyAgreementKey2019");
+
+        // An X25519 did:key has only a key agreement key.
+        let key = JWK::generate_x25519().unwrap();
+        let did = DIDKey.generate(&Source::Key(&key)).unwrap();
+        assert!(did.starts_with("did:key:z6LS"));
+        let (res_meta, doc, _doc_meta) = DIDKey
+            .resolve(&did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        assert_eq!(doc.assertion_method, None);
+        let keys = doc.get_key_agreement_keys().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].equals_public(&key));
+    }
+
     #[async_std::test]
     #[cfg(feature = "secp256k1")]
     async fn from_did_key_secp256k1() {
@@ -452,6 +591,55 @@ mod tests {
         assert_eq!(did1, did);
     }
 
+    #[cfg(feature = "ssi_p384")]
+    #[async_std::test]
+    async fn from_did_key_p384() {
+        // https://w3c-ccg.github.io/did-method-key/#p-384
+        let did = "did:key:z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9";
+        let (res_meta, _doc, _doc_meta) = DIDKey
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+
+        let vm = "did:key:z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9#z82Lm1MpAkeJcix9K8TMiLd5NMAhnwkjjCBeWHXyu3U4oT2MVJJKXkcVBgjGhnLBn2Kaau9";
+        let (res_meta, object, _meta) =
+            dereference(&DIDKey, &vm, &DereferencingInputMetadata::default()).await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        let key = vm.public_key_jwk.unwrap();
+
+        let did1 = DIDKey.generate(&Source::Key(&key)).unwrap();
+        assert_eq!(did1, did);
+    }
+
+    #[cfg(feature = "ssi_p521")]
+    #[async_std::test]
+    async fn from_did_key_p521() {
+        // https://w3c-ccg.github.io/did-method-key/#p-521
+        let did = "did:key:z2J9gaYxrKVpdoG9A4gRnmpnRCcxU6agDtFVVBVdn1JedouoZN7SzcyREXXzWgt3gGiwpoHq7K68X4m32D8HgzG8wv3sY5j7";
+        let (res_meta, _doc, _doc_meta) = DIDKey
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+
+        let vm = "did:key:z2J9gaYxrKVpdoG9A4gRnmpnRCcxU6agDtFVVBVdn1JedouoZN7SzcyREXXzWgt3gGiwpoHq7K68X4m32D8HgzG8wv3sY5j7#z2J9gaYxrKVpdoG9A4gRnmpnRCcxU6agDtFVVBVdn1JedouoZN7SzcyREXXzWgt3gGiwpoHq7K68X4m32D8HgzG8wv3sY5j7";
+        let (res_meta, object, _meta) =
+            dereference(&DIDKey, &vm, &DereferencingInputMetadata::default()).await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        let key = vm.public_key_jwk.unwrap();
+        assert_eq!(key.get_algorithm(), Some(ssi::jwk::Algorithm::ES512));
+
+        let did1 = DIDKey.generate(&Source::Key(&key)).unwrap();
+        assert_eq!(did1, did);
+    }
+
     #[async_std::test]
     async fn from_did_key_bls() {
         // https://w3c-ccg.github.io/did-method-key/#bls-12381
@@ -633,4 +821,47 @@ mod tests {
         vc.issuer = Some(Issuer::URI(URI::String("did:example:bad".to_string())));
         assert!(vc.verify(None, &DIDKey, &mut context_loader).await.errors.len() > 0);
     }
+
+    #[async_std::test]
+    #[cfg(feature = "ssi_p384")]
+    async fn credential_prove_verify_did_key_p384() {
+        use serde_json::json;
+        use ssi::vc::{get_verification_method, Credential, LinkedDataProofOptions, URI};
+        let key = JWK::generate_p384().unwrap();
+        let did = DIDKey.generate(&Source::Key(&key)).unwrap();
+        let vc: Credential = serde_json::from_value(json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": ["VerifiableCredential"],
+            "issuer": did.clone(),
+            "issuanceDate": "2021-02-18T20:17:46Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }))
+        .unwrap();
+        vc.validate_unsigned().unwrap();
+
+        let verification_method = get_verification_method(&did, &DIDKey).await.unwrap();
+        let mut context_loader = ssi::jsonld::ContextLoader::default();
+        for proof_type in [None, Some("EcdsaSecp384r1Signature2019")] {
+            let mut vc = vc.clone();
+            let mut issue_options = LinkedDataProofOptions::default();
+            issue_options.verification_method = Some(URI::String(verification_method.clone()));
+            issue_options.type_ = proof_type.map(str::to_string);
+            let proof = vc
+                .generate_proof(&key, &issue_options, &DIDKey, &mut context_loader)
+                .await
+                .unwrap();
+            println!("{}", serde_json::to_string_pretty(&proof).unwrap());
+            vc.add_proof(proof);
+            vc.validate().unwrap();
+            let verification_result = vc.verify(None, &DIDKey, &mut context_loader).await;
+            println!("{:#?}", verification_result);
+            assert!(verification_result.errors.is_empty());
+
+            // test that the credential is verified
+            vc.issuance_date = Some("2021-02-18T20:17:47Z".parse().unwrap());
+            assert!(vc.verify(None, &DIDKey, &mut context_loader).await.errors.len() > 0);
+        }
+    }
 }
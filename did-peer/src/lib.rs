@@ -0,0 +1,715 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use did_method_key::DIDKey;
+use ssi::did::{
+    Context, Contexts, DIDMethod, Document, Service, Source, VerificationMethod,
+    VerificationRelationship, DEFAULT_CONTEXT, DIDURL,
+};
+use ssi::did_resolve::{
+    DIDResolver, DocumentMetadata, ResolutionInputMetadata, ResolutionMetadata, ERROR_INVALID_DID,
+    ERROR_NOT_FOUND,
+};
+use ssi::hash::sha256;
+use ssi::jwk::JWK;
+
+const MULTICODEC_JSON_PREFIX: [u8; 2] = [0x80, 0x04];
+const MULTIHASH_SHA2_256_PREFIX: [u8; 2] = [0x12, 0x20];
+
+/// Abbreviations of service property names in numalgo 2 DIDs
+const SERVICE_KEY_ABBREVIATIONS: [(&str, &str); 4] = [
+    ("type", "t"),
+    ("serviceEndpoint", "s"),
+    ("routingKeys", "r"),
+    ("accept", "a"),
+];
+
+/// Abbreviations of service types in numalgo 2 DIDs
+const SERVICE_TYPE_ABBREVIATIONS: [(&str, &str); 1] = [("DIDCommMessaging", "dm")];
+
+#[derive(Error, Debug)]
+pub enum DIDPeerError {
+    #[error("Unsupported key type")]
+    UnsupportedKeyType,
+    #[error("Unsupported verification relationship: {0:?}")]
+    UnsupportedVerificationRelationship(VerificationRelationship),
+    #[error("Input document must be a JSON object without an id")]
+    InvalidInputDocument,
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    SSI(#[from] ssi::error::Error),
+}
+
+/// did:peer Method
+///
+/// [Specification](https://identity.foundation/peer-did-method-spec/)
+///
+/// [`DIDMethod::generate`] creates numalgo 0 DIDs. Numalgo 2 and numalgo 4 DIDs are created with
+/// [`DIDPeer::generate_numalgo2`] and [`DIDPeer::generate_numalgo4`].
+pub struct DIDPeer;
+
+impl DIDPeer {
+    /// Generate a numalgo 2 DID from public keys, each with a verification relationship, and
+    /// services.
+    ///
+    /// A service id may be left empty, in which case `#service`, `#service-1`, etc. are assigned
+    /// on resolution, in order of appearance.
+    pub fn generate_numalgo2(
+        keys: &[(VerificationRelationship, &JWK)],
+        services: &[Service],
+    ) -> Result<String, DIDPeerError> {
+        let mut did = "did:peer:2".to_string();
+        for (relationship, jwk) in keys {
+            let code = match relationship {
+                VerificationRelationship::AssertionMethod => 'A',
+                VerificationRelationship::KeyAgreement => 'E',
+                VerificationRelationship::Authentication => 'V',
+                VerificationRelationship::CapabilityInvocation => 'I',
+                VerificationRelationship::CapabilityDelegation => 'D',
+                _ => {
+                    return Err(DIDPeerError::UnsupportedVerificationRelationship(
+                        relationship.clone(),
+                    ))
+                }
+            };
+            did.push('.');
+            did.push(code);
+            did.push_str(&encode_key(jwk)?);
+        }
+        for (i, service) in services.iter().enumerate() {
+            let mut value = serde_json::to_value(service)?;
+            if service.id.is_empty() || service.id == default_service_id(i) {
+                if let Value::Object(ref mut object) = value {
+                    object.remove("id");
+                }
+            }
+            rewrite_service(&mut value, false);
+            did.push_str(".S");
+            did.push_str(&base64::encode_config(
+                serde_json::to_vec(&value)?,
+                base64::URL_SAFE_NO_PAD,
+            ));
+        }
+        Ok(did)
+    }
+
+    /// Generate a long-form numalgo 4 DID from an input document.
+    ///
+    /// The input document is a DID document without an `id`; it should use relative DID URLs to
+    /// refer to its own verification methods and services.
+    pub fn generate_numalgo4(document: &Value) -> Result<String, DIDPeerError> {
+        match document {
+            Value::Object(object) if !object.contains_key("id") => {}
+            _ => return Err(DIDPeerError::InvalidInputDocument),
+        }
+        let encoded = multibase::encode(
+            multibase::Base::Base58Btc,
+            [
+                MULTICODEC_JSON_PREFIX.to_vec(),
+                serde_json::to_vec(document)?,
+            ]
+            .concat(),
+        );
+        Ok(format!(
+            "did:peer:4{}:{}",
+            hash_numalgo4(&encoded)?,
+            encoded
+        ))
+    }
+
+    /// Get the short form of a long-form numalgo 4 DID.
+    pub fn numalgo4_short_form(did: &str) -> Option<&str> {
+        let (hash, _encoded) = did.strip_prefix("did:peer:4")?.split_once(':')?;
+        Some(&did[.."did:peer:4".len() + hash.len()])
+    }
+}
+
+/// Encode a public key as a multibase multicodec value, as in did:key.
+fn encode_key(jwk: &JWK) -> Result<String, DIDPeerError> {
+    DIDKey
+        .generate(&Source::Key(jwk))
+        .and_then(|did| did.strip_prefix("did:key:").map(str::to_string))
+        .ok_or(DIDPeerError::UnsupportedKeyType)
+}
+
+fn hash_numalgo4(encoded_document: &str) -> Result<String, ssi::error::Error> {
+    let digest = sha256(encoded_document.as_bytes())?;
+    Ok(multibase::encode(
+        multibase::Base::Base58Btc,
+        [MULTIHASH_SHA2_256_PREFIX.to_vec(), digest.to_vec()].concat(),
+    ))
+}
+
+fn default_service_id(index: usize) -> String {
+    match index {
+        0 => "#service".to_string(),
+        _ => format!("#service-{}", index),
+    }
+}
+
+fn swap_abbreviation(
+    table: &[(&'static str, &'static str)],
+    s: &str,
+    expand: bool,
+) -> Option<&'static str> {
+    table.iter().find_map(|&(long, short)| {
+        let (from, to) = if expand { (short, long) } else { (long, short) };
+        if from == s {
+            Some(to)
+        } else {
+            None
+        }
+    })
+}
+
+/// Abbreviate service property names and types, or expand them if `expand` is set.
+fn rewrite_service(value: &mut Value, expand: bool) {
+    match value {
+        Value::Object(object) => {
+            let type_key = if expand { "type" } else { "t" };
+            for (key, mut v) in std::mem::take(object) {
+                let key = swap_abbreviation(&SERVICE_KEY_ABBREVIATIONS, &key, expand)
+                    .map(str::to_string)
+                    .unwrap_or(key);
+                if key == type_key {
+                    let types = match v {
+                        Value::Array(ref mut types) => types.iter_mut().collect(),
+                        ref mut type_ => vec![type_],
+                    };
+                    for type_ in types {
+                        if let Value::String(ref mut type_) = type_ {
+                            if let Some(t) =
+                                swap_abbreviation(&SERVICE_TYPE_ABBREVIATIONS, type_, expand)
+                            {
+                                *type_ = t.to_string();
+                            }
+                        }
+                    }
+                } else {
+                    rewrite_service(&mut v, expand);
+                }
+                object.insert(key, v);
+            }
+        }
+        Value::Array(values) => {
+            for v in values {
+                rewrite_service(v, expand);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace a DID (and DID URLs based on it) with another DID in a JSON value.
+fn replace_did(value: &mut Value, from: &str, to: &str) {
+    match value {
+        Value::String(s) => {
+            if let Some(rest) = s.strip_prefix(from) {
+                *s = format!("{}{}", to, rest);
+            }
+        }
+        Value::Array(values) => {
+            for v in values {
+                replace_did(v, from, to);
+            }
+        }
+        Value::Object(object) => {
+            for v in object.values_mut() {
+                replace_did(v, from, to);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Numalgo 0: the DID document is that of the did:key with the same multibase value.
+async fn resolve_numalgo0(did: &str, key: &str) -> Result<Document, ResolutionMetadata> {
+    let did_key = format!("did:key:{}", key);
+    let (res_meta, doc, _) = DIDKey
+        .resolve(&did_key, &ResolutionInputMetadata::default())
+        .await;
+    let doc = match doc {
+        Some(doc) if res_meta.error.is_none() => doc,
+        _ => return Err(ResolutionMetadata::from_error(ERROR_INVALID_DID)),
+    };
+    let mut value =
+        serde_json::to_value(doc).map_err(|_| ResolutionMetadata::from_error(ERROR_INVALID_DID))?;
+    replace_did(&mut value, &did_key, did);
+    serde_json::from_value(value).map_err(|_| ResolutionMetadata::from_error(ERROR_INVALID_DID))
+}
+
+/// Numalgo 2: each element of the DID is a key with a purpose code, or an abbreviated service.
+async fn resolve_numalgo2(did: &str, elements: &str) -> Result<Document, ResolutionMetadata> {
+    let elements = match elements.strip_prefix('.') {
+        Some(elements) => elements,
+        None => return Err(ResolutionMetadata::from_error(ERROR_INVALID_DID)),
+    };
+    let mut doc = Document::new(did);
+    let mut context = BTreeMap::new();
+    let mut verification_methods = Vec::new();
+    let mut services = Vec::new();
+    for element in elements.split('.') {
+        let mut chars = element.chars();
+        let code = chars.next();
+        let value = chars.as_str();
+        if code == Some('S') {
+            let service = decode_service(did, value, services.len())
+                .ok_or_else(|| ResolutionMetadata::from_error(ERROR_INVALID_DID))?;
+            services.push(service);
+            continue;
+        }
+        // Reuse the did:key verification method for the key.
+        let (res_meta, key_doc, _) = DIDKey
+            .resolve(
+                &format!("did:key:{}", value),
+                &ResolutionInputMetadata::default(),
+            )
+            .await;
+        let mut vm = match key_doc
+            .as_ref()
+            .and_then(|key_doc| key_doc.verification_method.as_ref())
+            .and_then(|vms| vms.first())
+        {
+            Some(VerificationMethod::Map(vm)) if res_meta.error.is_none() => vm.clone(),
+            _ => return Err(ResolutionMetadata::from_error(ERROR_INVALID_DID)),
+        };
+        if let Some(Contexts::Many(contexts)) = key_doc.map(|key_doc| key_doc.context) {
+            for key_context in contexts {
+                if let Context::Object(object) = key_context {
+                    context.extend(object);
+                }
+            }
+        }
+        let vm_didurl = DIDURL {
+            did: did.to_string(),
+            fragment: Some(format!("key-{}", verification_methods.len() + 1)),
+            ..Default::default()
+        };
+        vm.id = vm_didurl.to_string();
+        vm.controller = did.to_string();
+        let relationship = match code {
+            Some('A') => &mut doc.assertion_method,
+            Some('E') => &mut doc.key_agreement,
+            Some('V') => &mut doc.authentication,
+            Some('I') => &mut doc.capability_invocation,
+            Some('D') => &mut doc.capability_delegation,
+            _ => return Err(ResolutionMetadata::from_error(ERROR_INVALID_DID)),
+        };
+        relationship
+            .get_or_insert_with(Vec::new)
+            .push(VerificationMethod::DIDURL(vm_didurl));
+        verification_methods.push(VerificationMethod::Map(vm));
+    }
+    if !context.is_empty() {
+        doc.context = Contexts::Many(vec![
+            Context::URI(DEFAULT_CONTEXT.to_string()),
+            Context::Object(context),
+        ]);
+    }
+    if !verification_methods.is_empty() {
+        doc.verification_method = Some(verification_methods);
+    }
+    if !services.is_empty() {
+        doc.service = Some(services);
+    }
+    Ok(doc)
+}
+
+fn decode_service(did: &str, encoded: &str, index: usize) -> Option<Service> {
+    let data =
+        base64::decode_config(encoded.trim_end_matches('='), base64::URL_SAFE_NO_PAD).ok()?;
+    let mut value: Value = serde_json::from_slice(&data).ok()?;
+    rewrite_service(&mut value, true);
+    let object = value.as_object_mut()?;
+    let id = match object.get("id") {
+        Some(Value::String(id)) if id.starts_with('#') => format!("{}{}", did, id),
+        Some(Value::String(id)) => id.to_string(),
+        None => format!("{}{}", did, default_service_id(index)),
+        Some(_) => return None,
+    };
+    object.insert("id".to_string(), Value::String(id));
+    serde_json::from_value(value).ok()
+}
+
+/// Numalgo 4: the long form contains a hash of the encoded input document, and the document.
+fn resolve_numalgo4(did: &str, rest: &str) -> Result<Document, ResolutionMetadata> {
+    let (hash, encoded) = match rest.split_once(':') {
+        Some(parts) => parts,
+        // The short form can only be resolved from a previously seen long form.
+        None => return Err(ResolutionMetadata::from_error(ERROR_NOT_FOUND)),
+    };
+    let invalid = || ResolutionMetadata::from_error(ERROR_INVALID_DID);
+    if hash_numalgo4(encoded).map_err(|_| invalid())? != hash {
+        return Err(invalid());
+    }
+    let (_base, data) = multibase::decode(encoded).map_err(|_| invalid())?;
+    let json = data
+        .strip_prefix(&MULTICODEC_JSON_PREFIX[..])
+        .ok_or_else(invalid)?;
+    let mut value: Value = serde_json::from_slice(json).map_err(|_| invalid())?;
+    let object = match value.as_object_mut() {
+        Some(object) if !object.contains_key("id") => object,
+        _ => return Err(invalid()),
+    };
+    object.insert("id".to_string(), Value::String(did.to_string()));
+    object
+        .entry("@context")
+        .or_insert_with(|| Value::String(DEFAULT_CONTEXT.to_string()));
+    let short_form = Value::String(did[..did.len() - encoded.len() - 1].to_string());
+    match object
+        .entry("alsoKnownAs")
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(also_known_as) => also_known_as.push(short_form),
+        _ => return Err(invalid()),
+    }
+    // Verification methods without a controller are controlled by the DID.
+    for property in [
+        "verificationMethod",
+        "authentication",
+        "assertionMethod",
+        "keyAgreement",
+        "capabilityInvocation",
+        "capabilityDelegation",
+    ] {
+        for vm in object
+            .get_mut(property)
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+        {
+            if let Value::Object(vm) = vm {
+                vm.entry("controller")
+                    .or_insert_with(|| Value::String(did.to_string()));
+            }
+        }
+    }
+    serde_json::from_value(value).map_err(|_| invalid())
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DIDResolver for DIDPeer {
+    async fn resolve(
+        &self,
+        did: &str,
+        _input_metadata: &ResolutionInputMetadata,
+    ) -> (
+        ResolutionMetadata,
+        Option<Document>,
+        Option<DocumentMetadata>,
+    ) {
+        let result = if let Some(key) = did.strip_prefix("did:peer:0") {
+            resolve_numalgo0(did, key).await
+        } else if let Some(elements) = did.strip_prefix("did:peer:2") {
+            resolve_numalgo2(did, elements).await
+        } else if let Some(rest) = did.strip_prefix("did:peer:4") {
+            resolve_numalgo4(did, rest)
+        } else {
+            Err(ResolutionMetadata::from_error(ERROR_INVALID_DID))
+        };
+        match result {
+            Ok(doc) => (
+                ResolutionMetadata::default(),
+                Some(doc),
+                Some(DocumentMetadata::default()),
+            ),
+            Err(res_meta) => (res_meta, None, None),
+        }
+    }
+}
+
+impl DIDMethod for DIDPeer {
+    fn name(&self) -> &'static str {
+        "peer"
+    }
+
+    fn generate(&self, source: &Source) -> Option<String> {
+        let jwk = match source {
+            Source::Key(jwk) => jwk,
+            _ => return None,
+        };
+        Some(format!("did:peer:0{}", encode_key(jwk).ok()?))
+    }
+
+    fn to_resolver(&self) -> &dyn DIDResolver {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use ssi::did::{Resource, ServiceEndpoint};
+    use ssi::did_resolve::{dereference, Content, DereferencingInputMetadata};
+    use ssi::one_or_many::OneOrMany;
+
+    #[async_std::test]
+    async fn from_did_peer_numalgo0() {
+        let did = "did:peer:0z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+        let (res_meta, doc, _) = DIDPeer
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        assert_eq!(doc.id, did);
+        let vm_id = format!("{}#z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH", did);
+        assert_eq!(
+            doc.get_verification_method_ids(VerificationRelationship::Authentication)
+                .unwrap(),
+            vec![vm_id.clone()]
+        );
+        let key_agreement_ids = doc
+            .get_verification_method_ids(VerificationRelationship::KeyAgreement)
+            .unwrap();
+        assert!(key_agreement_ids[0].starts_with(&format!("{}#z6LS", did)));
+
+        let (res_meta, object, _) =
+            dereference(&DIDPeer, &vm_id, &DereferencingInputMetadata::default()).await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        assert_eq!(vm.controller, did);
+        let jwk = vm.public_key_jwk.unwrap();
+        assert_eq!(DIDPeer.generate(&Source::Key(&jwk)).unwrap(), did);
+    }
+
+    #[async_std::test]
+    async fn from_did_peer_numalgo2() {
+        let did = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+        let (res_meta, doc, _) = DIDPeer
+            .resolve(did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        assert_eq!(doc.verification_method.as_ref().unwrap().len(), 3);
+        assert_eq!(
+            doc.get_verification_method_ids(VerificationRelationship::KeyAgreement)
+                .unwrap(),
+            vec![format!("{}#key-1", did)]
+        );
+        assert_eq!(
+            doc.get_verification_method_ids(VerificationRelationship::Authentication)
+                .unwrap(),
+            vec![format!("{}#key-2", did), format!("{}#key-3", did)]
+        );
+        assert_eq!(doc.assertion_method, None);
+        let keys = doc.get_key_agreement_keys().unwrap();
+        assert_eq!(keys.len(), 1);
+
+        let services = doc.service.as_ref().unwrap();
+        assert_eq!(services.len(), 1);
+        let service = &services[0];
+        assert_eq!(service.id, format!("{}#service", did));
+        assert_eq!(
+            service.type_,
+            OneOrMany::One("DIDCommMessaging".to_string())
+        );
+        assert_eq!(
+            service.service_endpoint,
+            Some(OneOrMany::One(ServiceEndpoint::URI(
+                "https://example.com/endpoint".to_string()
+            )))
+        );
+        let properties = service.property_set.as_ref().unwrap();
+        assert_eq!(
+            properties["routingKeys"],
+            json!(["did:example:somemediator#somekey"])
+        );
+        assert_eq!(
+            properties["accept"],
+            json!(["didcomm/v2", "didcomm/aip2;env=rfc587"])
+        );
+        assert_eq!(doc.select_service("service"), Some(service));
+    }
+
+    #[async_std::test]
+    async fn numalgo2_roundtrip() {
+        let key = JWK::generate_ed25519().unwrap();
+        let agreement_key = key.ed25519_to_x25519().unwrap();
+        let services = vec![
+            Service {
+                id: String::new(),
+                type_: OneOrMany::One("DIDCommMessaging".to_string()),
+                service_endpoint: Some(OneOrMany::One(ServiceEndpoint::Map(json!({
+                    "uri": "https://example.com/didcomm",
+                    "accept": ["didcomm/v2"],
+                    "routingKeys": [],
+                })))),
+                property_set: None,
+            },
+            Service {
+                id: "#linked-domain".to_string(),
+                type_: OneOrMany::One("LinkedDomains".to_string()),
+                service_endpoint: Some(OneOrMany::One(ServiceEndpoint::URI(
+                    "https://example.com/".to_string(),
+                ))),
+                property_set: None,
+            },
+        ];
+        let did = DIDPeer::generate_numalgo2(
+            &[
+                (VerificationRelationship::AssertionMethod, &key),
+                (VerificationRelationship::Authentication, &key),
+                (VerificationRelationship::KeyAgreement, &agreement_key),
+            ],
+            &services,
+        )
+        .unwrap();
+        // The DIDComm service type and properties are abbreviated.
+        let service_element = did.rsplitn(3, ".S").collect::<Vec<_>>()[1];
+        let service_json = base64::decode_config(service_element, base64::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&service_json).unwrap(),
+            json!({
+                "t": "dm",
+                "s": {
+                    "uri": "https://example.com/didcomm",
+                    "a": ["didcomm/v2"],
+                    "r": [],
+                },
+            })
+        );
+
+        let (res_meta, doc, _) = DIDPeer
+            .resolve(&did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        assert_eq!(
+            doc.get_verification_method_ids(VerificationRelationship::AssertionMethod)
+                .unwrap(),
+            vec![format!("{}#key-1", did)]
+        );
+        assert!(doc.get_key_agreement_keys().unwrap()[0].equals_public(&agreement_key));
+        let resolved_services = doc.service.unwrap();
+        assert_eq!(resolved_services[0].id, format!("{}#service", did));
+        assert_eq!(
+            resolved_services[0].service_endpoint,
+            services[0].service_endpoint
+        );
+        assert_eq!(resolved_services[1].id, format!("{}#linked-domain", did));
+
+        assert!(DIDPeer::generate_numalgo2(
+            &[(VerificationRelationship::ContractAgreement, &key)],
+            &[]
+        )
+        .is_err());
+    }
+
+    #[async_std::test]
+    async fn numalgo4_roundtrip() {
+        let key = JWK::generate_ed25519().unwrap();
+        let input_document = json!({
+            "@context": [
+                "https://www.w3.org/ns/did/v1",
+                "https://w3id.org/security/suites/jws-2020/v1"
+            ],
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "publicKeyJwk": key.to_public(),
+            }],
+            "authentication": ["#key-1"],
+            "assertionMethod": ["#key-1"],
+        });
+        let did = DIDPeer::generate_numalgo4(&input_document).unwrap();
+        let short_form = DIDPeer::numalgo4_short_form(&did).unwrap();
+        assert!(did.starts_with(&format!("{}:z", short_form)));
+        assert!(DIDPeer::generate_numalgo4(&json!({"id": "did:example:foo"})).is_err());
+
+        let (res_meta, doc, _) = DIDPeer
+            .resolve(&did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, None);
+        let doc = doc.unwrap();
+        assert_eq!(doc.id, did);
+        assert_eq!(doc.also_known_as, Some(vec![short_form.to_string()]));
+        assert_eq!(
+            doc.get_verification_method_ids(VerificationRelationship::Authentication)
+                .unwrap(),
+            vec![format!("{}#key-1", did)]
+        );
+        let (res_meta, object, _) = dereference(
+            &DIDPeer,
+            &format!("{}#key-1", did),
+            &DereferencingInputMetadata::default(),
+        )
+        .await;
+        assert_eq!(res_meta.error, None);
+        let vm = match object {
+            Content::Object(Resource::VerificationMethod(vm)) => vm,
+            _ => unreachable!(),
+        };
+        assert_eq!(vm.controller, did);
+        assert_eq!(vm.public_key_jwk, Some(key.to_public()));
+
+        // The short form cannot be resolved on its own.
+        let (res_meta, doc, _) = DIDPeer
+            .resolve(short_form, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, Some(ERROR_NOT_FOUND.to_string()));
+        assert!(doc.is_none());
+
+        // The hash must match the encoded document.
+        let other_did = DIDPeer::generate_numalgo4(&json!({})).unwrap();
+        let (_, other_encoded) = other_did["did:peer:4".len()..].split_once(':').unwrap();
+        let tampered_did = format!("{}:{}", short_form, other_encoded);
+        let (res_meta, _, _) = DIDPeer
+            .resolve(&tampered_did, &ResolutionInputMetadata::default())
+            .await;
+        assert_eq!(res_meta.error, Some(ERROR_INVALID_DID.to_string()));
+    }
+
+    #[async_std::test]
+    async fn credential_prove_verify_did_peer() {
+        use ssi::vc::{get_verification_method, Credential, Issuer, LinkedDataProofOptions, URI};
+        let vc_str = r###"{
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "id": "http://example.org/credentials/3731",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:30e07a529f32d234f6181736bd3",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###;
+        let key = JWK::generate_ed25519().unwrap();
+        let dids = vec![
+            DIDPeer.generate(&Source::Key(&key)).unwrap(),
+            DIDPeer::generate_numalgo2(&[(VerificationRelationship::AssertionMethod, &key)], &[])
+                .unwrap(),
+        ];
+        for did in dids {
+            let mut vc: Credential = Credential::from_json_unsigned(vc_str).unwrap();
+            let verification_method = get_verification_method(&did, &DIDPeer).await.unwrap();
+            let mut issue_options = LinkedDataProofOptions::default();
+            let mut context_loader = ssi::jsonld::ContextLoader::default();
+            vc.issuer = Some(Issuer::URI(URI::String(did.clone())));
+            issue_options.verification_method = Some(URI::String(verification_method));
+            let proof = vc
+                .generate_proof(&key, &issue_options, &DIDPeer, &mut context_loader)
+                .await
+                .unwrap();
+            vc.add_proof(proof);
+            vc.validate().unwrap();
+            let verification_result = vc.verify(None, &DIDPeer, &mut context_loader).await;
+            println!("{:#?}", verification_result);
+            assert!(verification_result.errors.is_empty());
+
+            // test that issuer is verified
+            vc.issuer = Some(Issuer::URI(URI::String("did:example:bad".to_string())));
+            assert!(!vc
+                .verify(None, &DIDPeer, &mut context_loader)
+                .await
+                .errors
+                .is_empty());
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// To generate test vector:
+// cargo run --example issue-bitstring-status-list > tests/bitstringStatusList.json
+
+#[async_std::main]
+async fn main() {
+    let key_str = include_str!("../tests/rsa2048-2020-08-25.json");
+    use ssi::one_or_many::OneOrMany;
+    use ssi::vc::{Credential, Issuer, URI};
+    use std::convert::TryFrom;
+    let key: ssi::jwk::JWK = serde_json::from_str(key_str).unwrap();
+    let resolver = &ssi::did::example::DIDExample;
+    use ssi::revocation::{
+        BitstringStatusList, BitstringStatusListCredential, BitstringStatusListSubject,
+    };
+    // Statuses of two bits, for suspension and messages.
+    let purposes = OneOrMany::Many(vec!["suspension".to_string(), "message".to_string()]);
+    let mut sl = BitstringStatusList::new(purposes, 131072, 2).unwrap();
+    sl.set_status(1, 2, 1).unwrap();
+    sl.set_status(2, 2, 2).unwrap();
+    let sl_vc = BitstringStatusListCredential {
+        issuer: Issuer::URI(URI::String("did:example:12345".to_string())),
+        id: URI::String("https://example.com/credentials/status/4".to_string()),
+        credential_subject: BitstringStatusListSubject::BitstringStatusList(sl),
+        more_properties: serde_json::Value::Null,
+    };
+    let mut vc = Credential::try_from(sl_vc).unwrap();
+    vc.valid_from = Some(ssi::vc::VCDateTime::from(ssi::ldp::now_ms()));
+    let mut proof_options = ssi::vc::LinkedDataProofOptions::default();
+    let verification_method = "did:example:12345#key1".to_string();
+    proof_options.verification_method = Some(ssi::vc::URI::String(verification_method));
+    let mut context_loader = ssi::jsonld::ContextLoader::default();
+    let proof = vc
+        .generate_proof(&key, &proof_options, resolver, &mut context_loader)
+        .await
+        .unwrap();
+    vc.add_proof(proof);
+    let result = vc.verify(None, resolver, &mut context_loader).await;
+    if !result.errors.is_empty() {
+        panic!("verify failed: {:#?}", result);
+    }
+    let stdout_writer = std::io::BufWriter::new(std::io::stdout());
+    serde_json::to_writer_pretty(stdout_writer, &vc).unwrap();
+}
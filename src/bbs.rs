@@ -17,6 +17,8 @@ use serde::{
     ser::SerializeTuple,
     Deserialize, Deserializer, Serialize, Serializer,
 };
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
 use std::fmt::Formatter;
 use thiserror::Error;
 use zeroize::Zeroize;
@@ -289,7 +291,7 @@ fn bls_generate_keypair<G: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes
         None => {
             passed_seed = false;
             let mut rng = thread_rng();
-            let mut seed_data = vec![0u8, 32];
+            let mut seed_data = vec![0u8; 32];
             rng.fill_bytes(seed_data.as_mut_slice());
             seed_data
         }
@@ -307,7 +309,7 @@ fn bls_generate_keypair<G: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes
                 data.extend_from_slice(seed.as_slice());
             } else {
                 let mut rng = thread_rng();
-                let mut blinding_factor = vec![0u8, 32];
+                let mut blinding_factor = vec![0u8; 32];
                 rng.fill_bytes(blinding_factor.as_mut_slice());
                 data.extend_from_slice(blinding_factor.as_slice());
             }
@@ -344,3 +346,211 @@ fn gen_sk(msg: &[u8]) -> Fr {
     );
     Fr::from_okm(&result)
 }
+
+impl<G: CurveProjective<Engine = Bls12, Scalar = Fr> + SerDes> BlsPublicKey<G> {
+    /// Encode the public key as a compressed curve point.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Serializing into a Vec does not fail.
+        self.0.serialize(&mut bytes, true).unwrap();
+        bytes
+    }
+}
+
+impl BlsSecretKey {
+    /// Encode the secret key as a big-endian scalar.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        // The scalar fits in the 32-byte buffer.
+        self.0.serialize(&mut bytes.as_mut(), true).unwrap();
+        bytes
+    }
+}
+
+/// Error resulting from BBS+ signing, verification, or proof derivation
+///
+/// Returned by [sign], [verify], [create_proof], [verify_proof], and the linked data proofs in
+/// [crate::bbs_ldp]
+#[derive(Error, Debug)]
+pub enum BbsError {
+    /// Error from the `bbs` crate.
+    #[error("BBS+ error: {0}")]
+    Bbs(BBSError),
+    /// Unable to convert BLS key to BBS+ key.
+    #[error(transparent)]
+    BlsToBbsPublicKey(#[from] BlsToBbsPublicKeyError),
+    /// Unable to generate BLS key pair.
+    #[error(transparent)]
+    GenerateKeyPair(#[from] BlsGenerateKeyPairError),
+    /// Signature does not verify.
+    #[error("Invalid BBS+ signature")]
+    InvalidSignature,
+    /// Proof is too short to hold the message count and revealed message bitvector.
+    #[error("Invalid BBS+ signature proof length: {0}")]
+    InvalidProofLength(usize),
+    /// Revealed message index exceeds the number of signed messages.
+    #[error("Revealed message index {0} out of range for {1} messages")]
+    RevealedIndexOutOfRange(usize, usize),
+    /// Number of revealed messages does not match the proof.
+    #[error("Expected {0} revealed messages but found {1}")]
+    RevealedMessageCount(usize, usize),
+    /// Derived proof is missing its nonce.
+    #[error("Missing nonce in BBS+ signature proof")]
+    MissingNonce,
+    /// Revealed statement is not one of the signed statements.
+    #[error("Statement is not in the signed data: {0}")]
+    UnsignedStatement(String),
+    /// Blank nodes of the document could not be given stable identifiers.
+    #[error("Unable to identify blank nodes of signed document")]
+    Skolemize,
+    /// Document does not match the frame.
+    #[error("Document does not match frame")]
+    FrameMismatch,
+}
+
+impl From<BBSError> for BbsError {
+    fn from(err: BBSError) -> Self {
+        BbsError::Bbs(err)
+    }
+}
+
+fn bbs_public_key(public_key: &[u8], message_count: usize) -> Result<PublicKey, BbsError> {
+    let dpk = DeterministicPublicKey::try_from(public_key)?;
+    Ok(dpk.to_public_key(message_count)?)
+}
+
+/// Sign messages with a BBS+ signature, deriving the message generators from a BLS12-381 G2
+/// public key.
+///
+/// The signature is returned in compressed form.
+pub fn sign(messages: &[&[u8]], secret_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, BbsError> {
+    let sk = SecretKey::try_from(secret_key)?;
+    let pk = bbs_public_key(public_key, messages.len())?;
+    let messages: Vec<SignatureMessage> = messages.iter().map(SignatureMessage::hash).collect();
+    let signature = Signature::new(&messages, &sk, &pk)?;
+    Ok(signature.to_bytes_compressed_form().to_vec())
+}
+
+/// Verify a BBS+ signature over messages.
+pub fn verify(messages: &[&[u8]], public_key: &[u8], signature: &[u8]) -> Result<(), BbsError> {
+    let pk = bbs_public_key(public_key, messages.len())?;
+    let signature = Signature::try_from(signature)?;
+    let messages: Vec<SignatureMessage> = messages.iter().map(SignatureMessage::hash).collect();
+    if !signature.verify(&messages, &pk)? {
+        return Err(BbsError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// Generate a random nonce for [create_proof].
+pub fn generate_nonce() -> Vec<u8> {
+    let mut nonce = vec![0u8; 32];
+    thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Encode revealed message indices as a big-endian bitvector.
+fn revealed_to_bitvector(total: usize, revealed: &BTreeSet<usize>) -> Vec<u8> {
+    let mut bitvector = vec![0u8; total / 8 + 1];
+    for i in revealed {
+        bitvector[i / 8] |= 1 << (i % 8);
+    }
+    bitvector.reverse();
+    bitvector
+}
+
+fn bitvector_to_revealed(bitvector: &[u8]) -> BTreeSet<usize> {
+    let mut revealed = BTreeSet::new();
+    for (i, byte) in bitvector.iter().rev().enumerate() {
+        for bit in 0..8 {
+            if byte & (1 << bit) != 0 {
+                revealed.insert(i * 8 + bit);
+            }
+        }
+    }
+    revealed
+}
+
+/// Create a zero-knowledge proof of a BBS+ signature that selectively discloses some messages.
+///
+/// The proof is encoded as the message count (2 bytes, big-endian), then a bitvector of the
+/// revealed message indices, then the compressed proof of knowledge, as in
+/// [MATTR's bbs-signatures](https://github.com/mattrglobal/bbs-signatures).
+pub fn create_proof(
+    signature: &[u8],
+    public_key: &[u8],
+    messages: &[&[u8]],
+    revealed: &BTreeSet<usize>,
+    nonce: &[u8],
+) -> Result<Vec<u8>, BbsError> {
+    let message_count = messages.len();
+    if let Some(i) = revealed.iter().find(|i| **i >= message_count) {
+        return Err(BbsError::RevealedIndexOutOfRange(*i, message_count));
+    }
+    let pk = bbs_public_key(public_key, message_count)?;
+    let signature = Signature::try_from(signature)?;
+    let proof_messages: Vec<ProofMessage> = messages
+        .iter()
+        .enumerate()
+        .map(|(i, message)| {
+            let message = SignatureMessage::hash(message);
+            if revealed.contains(&i) {
+                ProofMessage::Revealed(message)
+            } else {
+                ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(message))
+            }
+        })
+        .collect();
+    let revealed_indices: Vec<usize> = revealed.iter().copied().collect();
+    let request = Verifier::new_proof_request(&revealed_indices, &pk)?;
+    let pok = Prover::commit_signature_pok(&request, &proof_messages, &signature)?;
+    let nonce = ProofNonce::hash(nonce);
+    let challenge = Prover::create_challenge_hash(std::slice::from_ref(&pok), None, &nonce)?;
+    let proof = Prover::generate_signature_pok(pok, &challenge)?;
+    let mut output = (message_count as u16).to_be_bytes().to_vec();
+    output.extend(revealed_to_bitvector(message_count, revealed));
+    output.extend(proof.proof.to_bytes_compressed_form());
+    Ok(output)
+}
+
+/// Verify a proof created by [create_proof], given the revealed messages in index order.
+pub fn verify_proof(
+    proof: &[u8],
+    public_key: &[u8],
+    revealed_messages: &[&[u8]],
+    nonce: &[u8],
+) -> Result<(), BbsError> {
+    if proof.len() < 2 {
+        return Err(BbsError::InvalidProofLength(proof.len()));
+    }
+    let message_count = u16::from_be_bytes([proof[0], proof[1]]) as usize;
+    let offset = 2 + message_count / 8 + 1;
+    if proof.len() < offset {
+        return Err(BbsError::InvalidProofLength(proof.len()));
+    }
+    let revealed = bitvector_to_revealed(&proof[2..offset]);
+    if let Some(i) = revealed.iter().find(|i| **i >= message_count) {
+        return Err(BbsError::RevealedIndexOutOfRange(*i, message_count));
+    }
+    if revealed.len() != revealed_messages.len() {
+        return Err(BbsError::RevealedMessageCount(
+            revealed.len(),
+            revealed_messages.len(),
+        ));
+    }
+    let pk = bbs_public_key(public_key, message_count)?;
+    let signature_proof = SignatureProof {
+        revealed_messages: revealed
+            .iter()
+            .copied()
+            .zip(revealed_messages.iter().map(SignatureMessage::hash))
+            .collect(),
+        proof: PoKOfSignatureProof::from_bytes_compressed_form(&proof[offset..])?,
+    };
+    let request = ProofRequest {
+        revealed_messages: revealed,
+        verification_key: pk,
+    };
+    Verifier::verify_signature_pok(&request, &signature_proof, &ProofNonce::hash(nonce))?;
+    Ok(())
+}
@@ -0,0 +1,755 @@
+//! [BBS+ Signatures 2020](https://w3c-ccg.github.io/ldp-bbs2020/) linked data proofs.
+//!
+//! A `BbsBlsSignature2020` proof signs each statement of the canonical N-Quads of the proof
+//! options and of the document as a separate BBS+ message. From it, a holder can derive a
+//! `BbsBlsSignatureProof2020` proof for a framed copy of the document, which reveals only the
+//! statements selected by the frame.
+use std::collections::{BTreeSet, HashMap as Map, HashSet};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::bbs::{self, BbsError};
+use crate::did_resolve::DIDResolver;
+use crate::error::Error;
+use crate::jsonld::{json_to_dataset, ContextLoader, BBS_V1_CONTEXT};
+use crate::jwk::{Params as JWKParams, JWK};
+use crate::ldp::{
+    document_has_context, resolve_key, LinkedDataDocument, ProofPreparation, ProofSuite,
+    VerificationWarnings,
+};
+use crate::one_or_many::OneOrMany;
+use crate::rdf::{BlankNodeLabel, DataSet, GraphLabel, IRIRef, Object, Statement, Subject};
+use crate::signer::Signer;
+use crate::urdna2015;
+use crate::vc::{Credential, LinkedDataProofOptions, Proof};
+
+pub const BBS_SIGNATURE_TYPE: &str = "BbsBlsSignature2020";
+pub const BBS_SIGNATURE_PROOF_TYPE: &str = "BbsBlsSignatureProof2020";
+
+/// IRI prefix under which blank nodes of a derived document are identified, so that revealed
+/// statements about them can be matched to the signed statements.
+const BNID_PREFIX: &str = "urn:bnid:";
+
+/// Blank node label prefix for nodes of a document before canonical labels are known.
+const SKOLEM_LABEL_PREFIX: &str = "_:skolem";
+
+pub(crate) fn is_bls12381g2(key: &JWK) -> bool {
+    matches!(key.params, JWKParams::OKP(ref okp) if okp.curve == "Bls12381G2")
+}
+
+fn bls12381g2_public_key(key: &JWK) -> Result<&[u8], Error> {
+    match key.params {
+        JWKParams::OKP(ref okp) if okp.curve == "Bls12381G2" => Ok(&okp.public_key.0),
+        _ => Err(Error::UnsupportedKeyType),
+    }
+}
+
+fn bls12381g2_secret_key(key: &JWK) -> Result<&[u8], Error> {
+    match key.params {
+        JWKParams::OKP(ref okp) if okp.curve == "Bls12381G2" => {
+            Ok(&okp.private_key.as_ref().ok_or(Error::MissingPrivateKey)?.0)
+        }
+        _ => Err(Error::UnsupportedKeyType),
+    }
+}
+
+/// Canonical N-Quad statements of a dataset, without line terminators.
+fn canonical_statements(dataset: &DataSet) -> Result<Vec<String>, Error> {
+    let nquads = urdna2015::normalize(dataset)?.to_nquads()?;
+    Ok(nquads.lines().map(String::from).collect())
+}
+
+/// Statements of the proof options followed by the statements of the document, each signed as
+/// a message.
+async fn signed_statements(
+    document: &(dyn LinkedDataDocument + Sync),
+    proof: &Proof,
+    context_loader: &mut ContextLoader,
+) -> Result<(Vec<String>, Vec<String>), Error> {
+    let proof_dataset = proof
+        .to_dataset_for_signing(Some(document), context_loader)
+        .await?;
+    let doc_dataset = document
+        .to_dataset_for_signing(None, context_loader)
+        .await?;
+    Ok((
+        canonical_statements(&proof_dataset)?,
+        canonical_statements(&doc_dataset)?,
+    ))
+}
+
+fn to_messages(statements: &[String]) -> Vec<&[u8]> {
+    statements.iter().map(|s| s.as_bytes()).collect()
+}
+
+/// Replace `urn:bnid:` IRIs in a statement with the blank nodes they identify.
+fn deskolemize(statement: &mut Statement) {
+    let blank_node = |iri: &IRIRef| {
+        iri.0
+            .strip_prefix(BNID_PREFIX)
+            .filter(|label| label.starts_with("_:"))
+            .map(|label| BlankNodeLabel(label.to_string()))
+    };
+    if let Subject::IRIRef(ref iri) = statement.subject {
+        if let Some(label) = blank_node(iri) {
+            statement.subject = Subject::BlankNodeLabel(label);
+        }
+    }
+    if let Object::IRIRef(ref iri) = statement.object {
+        if let Some(label) = blank_node(iri) {
+            statement.object = Object::BlankNodeLabel(label);
+        }
+    }
+    if let Some(GraphLabel::IRIRef(ref iri)) = statement.graph_label {
+        if let Some(label) = blank_node(iri) {
+            statement.graph_label = Some(GraphLabel::BlankNodeLabel(label));
+        }
+    }
+}
+
+fn deskolemize_dataset(dataset: &DataSet) -> DataSet {
+    let mut output = DataSet::default();
+    for mut statement in dataset.statements() {
+        deskolemize(&mut statement);
+        output.add_statement(statement);
+    }
+    output
+}
+
+/// Statements of a dataset with `urn:bnid:` IRIs replaced by blank nodes, without line
+/// terminators, sorted.
+fn deskolemized_statements(dataset: &DataSet) -> Result<Vec<String>, Error> {
+    let nquads = deskolemize_dataset(dataset).to_nquads()?;
+    Ok(nquads.lines().map(String::from).collect())
+}
+
+fn is_node_object(object: &serde_json::Map<String, Value>) -> bool {
+    !(object.contains_key("@value") || object.contains_key("@list") || object.contains_key("@set"))
+}
+
+fn id_key(object: &serde_json::Map<String, Value>) -> &'static str {
+    if object.contains_key("@id") {
+        "@id"
+    } else {
+        "id"
+    }
+}
+
+/// Identify every node object lacking an IRI, using a `urn:bnid:` IRI with a temporary label.
+///
+/// Returns the identifiers given, with the blank node identifier each node had before, if any.
+fn skolemize(value: &mut Value, labels: &mut Vec<(String, Option<String>)>) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                skolemize(item, labels);
+            }
+        }
+        Value::Object(object) => {
+            if is_node_object(object) {
+                let key = id_key(object);
+                let blank_node_id = match object.get(key) {
+                    None => Some(None),
+                    Some(Value::String(id)) if id.starts_with("_:") => Some(Some(id.clone())),
+                    Some(_) => None,
+                };
+                if let Some(blank_node_id) = blank_node_id {
+                    let iri = match blank_node_id
+                        .as_ref()
+                        .and_then(|id| labels.iter().find(|(_, orig)| orig.as_ref() == Some(id)))
+                    {
+                        Some((iri, _)) => iri.clone(),
+                        None => {
+                            let iri =
+                                format!("{}{}{}", BNID_PREFIX, SKOLEM_LABEL_PREFIX, labels.len());
+                            labels.push((iri.clone(), blank_node_id));
+                            iri
+                        }
+                    };
+                    object.insert(key.to_string(), Value::String(iri));
+                }
+            }
+            for (key, value) in object.iter_mut() {
+                if key != "@context" {
+                    skolemize(value, labels);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Replace node identifiers given by [skolemize].
+///
+/// Identifiers missing from `replacements` are restored to their previous value.
+fn relabel(
+    value: &mut Value,
+    labels: &[(String, Option<String>)],
+    replacements: &Map<String, String>,
+) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                relabel(item, labels, replacements);
+            }
+        }
+        Value::Object(object) => {
+            let key = id_key(object);
+            let id = object.get(key).and_then(Value::as_str).map(String::from);
+            if let Some(id) = id {
+                if let Some((iri, orig)) = labels.iter().find(|(iri, _)| *iri == id) {
+                    match (replacements.get(iri), orig) {
+                        (Some(replacement), _) => {
+                            object.insert(key.to_string(), Value::String(replacement.clone()));
+                        }
+                        (None, Some(orig)) => {
+                            object.insert(key.to_string(), Value::String(orig.clone()));
+                        }
+                        (None, None) => {
+                            object.remove(key);
+                        }
+                    }
+                }
+            }
+            for (key, value) in object.iter_mut() {
+                if key != "@context" {
+                    relabel(value, labels, replacements);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+fn iris(dataset: &DataSet) -> HashSet<String> {
+    let mut iris = HashSet::new();
+    for statement in dataset.statements() {
+        if let Subject::IRIRef(IRIRef(iri)) = statement.subject {
+            iris.insert(iri);
+        }
+        if let Object::IRIRef(IRIRef(iri)) = statement.object {
+            iris.insert(iri);
+        }
+        if let Some(GraphLabel::IRIRef(IRIRef(iri))) = statement.graph_label {
+            iris.insert(iri);
+        }
+    }
+    iris
+}
+
+async fn to_dataset(
+    document: &Value,
+    context_loader: &mut ContextLoader,
+) -> Result<DataSet, Error> {
+    let json = serde_json::to_string(document)?;
+    json_to_dataset(&json, None, false, None, context_loader).await
+}
+
+/// Identify the blank nodes of a document by `urn:bnid:` IRIs using their canonical labels, so
+/// that the statements of any framed part of the document match the signed statements.
+async fn skolemize_document(
+    document: &Value,
+    signed_statements: &[String],
+    context_loader: &mut ContextLoader,
+) -> Result<Value, Error> {
+    let mut skolemized = document.clone();
+    let mut labels = Vec::new();
+    skolemize(&mut skolemized, &mut labels);
+    let mut dataset = to_dataset(&skolemized, context_loader).await?;
+    // Objects that are not interpreted as nodes (e.g. JSON literals) keep their original form.
+    let used = iris(&dataset);
+    let (labels, unused_labels): (Vec<_>, Vec<_>) =
+        labels.into_iter().partition(|(iri, _)| used.contains(iri));
+    if !unused_labels.is_empty() {
+        relabel(&mut skolemized, &unused_labels, &Map::new());
+        dataset = to_dataset(&skolemized, context_loader).await?;
+    }
+    let (normalized, issuer) = urdna2015::normalize_with_issuer(&deskolemize_dataset(&dataset))?;
+    if canonical_statements(&normalized)? != signed_statements {
+        return Err(BbsError::Skolemize.into());
+    }
+    let mut replacements = Map::new();
+    for (iri, _) in labels.iter() {
+        let label = &iri[BNID_PREFIX.len()..];
+        let canonical_label = issuer
+            .find_issued_identifier(label)
+            .ok_or(BbsError::Skolemize)?;
+        replacements.insert(iri.clone(), format!("{}{}", BNID_PREFIX, canonical_label));
+    }
+    relabel(&mut skolemized, &labels, &replacements);
+    Ok(skolemized)
+}
+
+fn types(node: &serde_json::Map<String, Value>) -> Vec<&Value> {
+    match node.get("type").or_else(|| node.get("@type")) {
+        Some(Value::Array(types)) => types.iter().collect(),
+        Some(type_) => vec![type_],
+        None => Vec::new(),
+    }
+}
+
+fn frame_value(value: &Value, frame: &serde_json::Map<String, Value>) -> Option<Value> {
+    match value {
+        Value::Array(items) => {
+            let items: Vec<Value> = items
+                .iter()
+                .filter_map(|item| frame_value(item, frame))
+                .collect();
+            if items.is_empty() {
+                None
+            } else {
+                Some(Value::Array(items))
+            }
+        }
+        Value::Object(node) if is_node_object(node) => frame_node(node, frame).map(Value::Object),
+        _ => Some(value.clone()),
+    }
+}
+
+/// Select the parts of a node object matching a [JSON-LD frame](https://www.w3.org/TR/json-ld11-framing/).
+///
+/// This supports the subset of framing used for selective disclosure on a compacted document
+/// using the same context as the frame: a node matches if it has all the types in the frame;
+/// with `@explicit`, only the properties in the frame are kept; and nested frames select within
+/// the values of their property. Identifiers and types of matching nodes are always kept.
+fn frame_node(
+    node: &serde_json::Map<String, Value>,
+    frame: &serde_json::Map<String, Value>,
+) -> Option<serde_json::Map<String, Value>> {
+    let node_types = types(node);
+    for type_ in types(frame) {
+        if type_.is_string() && !node_types.contains(&type_) {
+            return None;
+        }
+    }
+    let explicit = frame
+        .get("@explicit")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let mut output = serde_json::Map::new();
+    for (key, value) in node {
+        if matches!(key.as_str(), "@context" | "id" | "@id" | "type" | "@type") {
+            output.insert(key.clone(), value.clone());
+            continue;
+        }
+        let property_frame = match frame.get(key) {
+            Some(Value::Array(frames)) if frames.len() == 1 => frames.first(),
+            property_frame => property_frame,
+        };
+        match property_frame {
+            Some(Value::Object(property_frame)) => {
+                if let Some(value) = frame_value(value, property_frame) {
+                    output.insert(key.clone(), value);
+                }
+            }
+            Some(_) => {
+                output.insert(key.clone(), value.clone());
+            }
+            None if !explicit => {
+                output.insert(key.clone(), value.clone());
+            }
+            None => (),
+        }
+    }
+    Some(output)
+}
+
+/// Derive a credential revealing only the parts of a credential selected by a frame, with a
+/// `BbsBlsSignatureProof2020` proof from the credential's `BbsBlsSignature2020` proof.
+///
+/// Nodes of the credential without an `id` are identified in the derived credential by
+/// `urn:bnid:` IRIs. If no nonce is given, a random one is used.
+pub async fn derive_credential(
+    credential: &Credential,
+    frame: &Value,
+    nonce: Option<&[u8]>,
+    resolver: &dyn DIDResolver,
+    context_loader: &mut ContextLoader,
+) -> Result<Credential, Error> {
+    let proof = credential
+        .proof
+        .iter()
+        .flat_map(|proofs| proofs.into_iter())
+        .find(|proof| proof.type_ == BBS_SIGNATURE_TYPE)
+        .ok_or(Error::MissingProof)?;
+    let frame = frame.as_object().ok_or(Error::ExpectedObject)?;
+    let verification_method = proof
+        .verification_method
+        .as_ref()
+        .ok_or(Error::MissingVerificationMethod)?;
+    let key = resolve_key(verification_method, resolver).await?;
+    let public_key = bls12381g2_public_key(&key)?;
+    let signature = base64::decode(
+        proof
+            .proof_value
+            .as_ref()
+            .ok_or(Error::MissingProofSignature)?,
+    )?;
+
+    let mut unsigned_credential = credential.clone();
+    unsigned_credential.proof = None;
+    let (proof_statements, doc_statements) =
+        signed_statements(&unsigned_credential, proof, context_loader).await?;
+
+    let document = serde_json::to_value(&unsigned_credential)?;
+    let skolemized = skolemize_document(&document, &doc_statements, context_loader).await?;
+    let revealed = frame_node(skolemized.as_object().ok_or(Error::ExpectedObject)?, frame)
+        .ok_or(BbsError::FrameMismatch)?;
+    let mut derived_credential: Credential = serde_json::from_value(Value::Object(revealed))?;
+
+    let doc_indices: Map<&str, usize> = doc_statements
+        .iter()
+        .enumerate()
+        .map(|(i, statement)| (statement.as_str(), proof_statements.len() + i))
+        .collect();
+    let mut revealed_indices: BTreeSet<usize> = (0..proof_statements.len()).collect();
+    let revealed_dataset = derived_credential
+        .to_dataset_for_signing(None, context_loader)
+        .await?;
+    for statement in deskolemized_statements(&revealed_dataset)? {
+        let i = doc_indices
+            .get(statement.as_str())
+            .ok_or(BbsError::UnsignedStatement(statement.clone()))?;
+        revealed_indices.insert(*i);
+    }
+
+    let messages = [proof_statements, doc_statements].concat();
+    let nonce = match nonce {
+        Some(nonce) => nonce.to_vec(),
+        None => bbs::generate_nonce(),
+    };
+    let proof_bytes = bbs::create_proof(
+        &signature,
+        public_key,
+        &to_messages(&messages),
+        &revealed_indices,
+        &nonce,
+    )?;
+    derived_credential.proof = Some(OneOrMany::One(Proof {
+        type_: BBS_SIGNATURE_PROOF_TYPE.to_string(),
+        proof_value: Some(base64::encode(proof_bytes)),
+        nonce: Some(base64::encode(nonce)),
+        ..proof.clone()
+    }));
+    Ok(derived_credential)
+}
+
+/// <https://w3c-ccg.github.io/ldp-bbs2020/#the-bbsblssignature2020-suite>
+pub struct BbsBlsSignature2020;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ProofSuite for BbsBlsSignature2020 {
+    async fn sign(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        key: &dyn Signer,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<Proof, Error> {
+        // BBS+ signs the statements as separate messages, so it needs the private key itself.
+        let key = key.private_key().ok_or(Error::MissingPrivateKey)?;
+        let public_key = bls12381g2_public_key(key)?;
+        let secret_key = bls12381g2_secret_key(key)?;
+        let mut proof = Proof::new(BBS_SIGNATURE_TYPE)
+            .with_options(options)
+            .with_properties(extra_proof_properties);
+        if !document_has_context(document, BBS_V1_CONTEXT)? {
+            proof.context = serde_json::json!([BBS_V1_CONTEXT]);
+        }
+        let (proof_statements, doc_statements) =
+            signed_statements(document, &proof, context_loader).await?;
+        let messages = [proof_statements, doc_statements].concat();
+        let signature = bbs::sign(&to_messages(&messages), secret_key, public_key)?;
+        proof.proof_value = Some(base64::encode(signature));
+        Ok(proof)
+    }
+
+    async fn prepare(
+        &self,
+        _document: &(dyn LinkedDataDocument + Sync),
+        _options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        _context_loader: &mut ContextLoader,
+        _public_key: &JWK,
+        _extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<ProofPreparation, Error> {
+        Err(Error::NotImplemented)
+    }
+
+    async fn complete(
+        &self,
+        _preparation: ProofPreparation,
+        _signature: &str,
+    ) -> Result<Proof, Error> {
+        Err(Error::NotImplemented)
+    }
+
+    async fn verify(
+        &self,
+        proof: &Proof,
+        document: &(dyn LinkedDataDocument + Sync),
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> Result<VerificationWarnings, Error> {
+        let proof_value = proof
+            .proof_value
+            .as_ref()
+            .ok_or(Error::MissingProofSignature)?;
+        let verification_method = proof
+            .verification_method
+            .as_ref()
+            .ok_or(Error::MissingVerificationMethod)?;
+        let key = resolve_key(verification_method, resolver).await?;
+        let public_key = bls12381g2_public_key(&key)?;
+        let (proof_statements, doc_statements) =
+            signed_statements(document, proof, context_loader).await?;
+        let messages = [proof_statements, doc_statements].concat();
+        let signature = base64::decode(proof_value)?;
+        bbs::verify(&to_messages(&messages), public_key, &signature)?;
+        Ok(Default::default())
+    }
+}
+
+/// <https://w3c-ccg.github.io/ldp-bbs2020/#the-bbsblssignatureproof2020-suite>
+///
+/// Proofs of this type are created by [derive_credential].
+pub struct BbsBlsSignatureProof2020;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ProofSuite for BbsBlsSignatureProof2020 {
+    async fn sign(
+        &self,
+        _document: &(dyn LinkedDataDocument + Sync),
+        _options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        _context_loader: &mut ContextLoader,
+        _key: &dyn Signer,
+        _extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<Proof, Error> {
+        Err(Error::NotImplemented)
+    }
+
+    async fn prepare(
+        &self,
+        _document: &(dyn LinkedDataDocument + Sync),
+        _options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        _context_loader: &mut ContextLoader,
+        _public_key: &JWK,
+        _extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<ProofPreparation, Error> {
+        Err(Error::NotImplemented)
+    }
+
+    async fn complete(
+        &self,
+        _preparation: ProofPreparation,
+        _signature: &str,
+    ) -> Result<Proof, Error> {
+        Err(Error::NotImplemented)
+    }
+
+    async fn verify(
+        &self,
+        proof: &Proof,
+        document: &(dyn LinkedDataDocument + Sync),
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> Result<VerificationWarnings, Error> {
+        let proof_value = proof
+            .proof_value
+            .as_ref()
+            .ok_or(Error::MissingProofSignature)?;
+        let nonce = proof.nonce.as_ref().ok_or(BbsError::MissingNonce)?;
+        let verification_method = proof
+            .verification_method
+            .as_ref()
+            .ok_or(Error::MissingVerificationMethod)?;
+        let key = resolve_key(verification_method, resolver).await?;
+        let public_key = bls12381g2_public_key(&key)?;
+        // The proof options are signed as those of the original signature.
+        let signature_proof = Proof {
+            type_: BBS_SIGNATURE_TYPE.to_string(),
+            nonce: None,
+            ..proof.clone()
+        };
+        let proof_dataset = signature_proof
+            .to_dataset_for_signing(Some(document), context_loader)
+            .await?;
+        let doc_dataset = document
+            .to_dataset_for_signing(None, context_loader)
+            .await?;
+        let messages = [
+            canonical_statements(&proof_dataset)?,
+            deskolemized_statements(&urdna2015::normalize(&doc_dataset)?)?,
+        ]
+        .concat();
+        bbs::verify_proof(
+            &base64::decode(proof_value)?,
+            public_key,
+            &to_messages(&messages),
+            &base64::decode(nonce)?,
+        )?;
+        Ok(Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+    use crate::vc::URI;
+    use serde_json::json;
+
+    const VC: &str = r#"{
+        "@context": [
+            "https://www.w3.org/2018/credentials/v1",
+            "https://www.w3.org/2018/credentials/examples/v1",
+            "https://w3id.org/security/bbs/v1"
+        ],
+        "id": "http://example.org/credentials/3731",
+        "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+        "issuer": "did:example:bls12381",
+        "issuanceDate": "2020-08-19T21:41:50Z",
+        "credentialSubject": {
+            "id": "did:example:d23dd687a7dc6787646f2eb98d0",
+            "givenName": "Jane",
+            "familyName": "Doe",
+            "degree": {
+                "type": "BachelorDegree",
+                "name": "Bachelor of Science and Arts",
+                "degreeSchool": "Example University"
+            }
+        }
+    }"#;
+
+    fn bls12381g2_key() -> JWK {
+        serde_json::from_str(include_str!("../tests/bls12381g2-2026-10-17.json")).unwrap()
+    }
+
+    async fn issue(context_loader: &mut ContextLoader) -> Credential {
+        let mut vc = Credential::from_json_unsigned(VC).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:bls12381#key1".to_string())),
+            ..Default::default()
+        };
+        let proof = vc
+            .generate_proof(
+                &bls12381g2_key(),
+                &issue_options,
+                &DIDExample,
+                context_loader,
+            )
+            .await
+            .unwrap();
+        assert_eq!(proof.type_, BBS_SIGNATURE_TYPE);
+        vc.add_proof(proof);
+        vc
+    }
+
+    #[async_std::test]
+    async fn bbs_bls_signature_2020() {
+        let mut context_loader = ContextLoader::default();
+        let vc = issue(&mut context_loader).await;
+        println!("{}", serde_json::to_string_pretty(&vc).unwrap());
+        let verification_result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        let mut vc_bad = vc.clone();
+        vc_bad.id = Some(URI::String("http://example.org/credentials/0".to_string()));
+        let verification_result = vc_bad.verify(None, &DIDExample, &mut context_loader).await;
+        assert!(!verification_result.errors.is_empty());
+    }
+
+    #[async_std::test]
+    async fn bbs_bls_signature_proof_2020() {
+        let mut context_loader = ContextLoader::default();
+        let vc = issue(&mut context_loader).await;
+        let frame = json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                "https://www.w3.org/2018/credentials/examples/v1",
+                "https://w3id.org/security/bbs/v1"
+            ],
+            "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+            "@explicit": true,
+            "issuer": {},
+            "issuanceDate": {},
+            "credentialSubject": {
+                "@explicit": true,
+                "givenName": {},
+                "degree": {
+                    "@explicit": true,
+                    "type": "BachelorDegree",
+                    "name": {}
+                }
+            }
+        });
+        let derived_vc =
+            derive_credential(&vc, &frame, Some(b"abc"), &DIDExample, &mut context_loader)
+                .await
+                .unwrap();
+        println!("{}", serde_json::to_string_pretty(&derived_vc).unwrap());
+        let subject = serde_json::to_value(derived_vc.credential_subject.first().unwrap()).unwrap();
+        assert_eq!(subject["givenName"], "Jane");
+        assert!(subject.get("familyName").is_none());
+        assert_eq!(subject["degree"]["name"], "Bachelor of Science and Arts");
+        assert!(subject["degree"].get("degreeSchool").is_none());
+        assert!(subject["degree"]["id"]
+            .as_str()
+            .unwrap()
+            .starts_with("urn:bnid:_:c14n"));
+        let proof = derived_vc.proof.as_ref().unwrap().first().unwrap();
+        assert_eq!(proof.type_, BBS_SIGNATURE_PROOF_TYPE);
+        assert_eq!(proof.nonce.as_deref(), Some("YWJj"));
+
+        let verification_result = derived_vc
+            .verify(None, &DIDExample, &mut context_loader)
+            .await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        // Round-trip through JSON
+        let derived_vc_json = serde_json::to_string(&derived_vc).unwrap();
+        let derived_vc1 = Credential::from_json(&derived_vc_json).unwrap();
+        let verification_result = derived_vc1
+            .verify(None, &DIDExample, &mut context_loader)
+            .await;
+        assert!(verification_result.errors.is_empty());
+
+        // Tamper with a revealed statement
+        let mut derived_vc_bad = derived_vc.clone();
+        derived_vc_bad.issuance_date = Some("2021-08-19T21:41:50Z".parse().unwrap());
+        let verification_result = derived_vc_bad
+            .verify(None, &DIDExample, &mut context_loader)
+            .await;
+        assert!(!verification_result.errors.is_empty());
+
+        // Tamper with the nonce
+        let mut derived_vc_bad = derived_vc.clone();
+        if let Some(OneOrMany::One(ref mut proof)) = derived_vc_bad.proof {
+            proof.nonce = Some("YWJk".to_string());
+        }
+        let verification_result = derived_vc_bad
+            .verify(None, &DIDExample, &mut context_loader)
+            .await;
+        assert!(!verification_result.errors.is_empty());
+    }
+
+    #[async_std::test]
+    async fn frame_type_mismatch() {
+        let mut context_loader = ContextLoader::default();
+        let vc = issue(&mut context_loader).await;
+        let frame = json!({
+            "type": ["VerifiableCredential", "AlumniCredential"]
+        });
+        let err = derive_credential(&vc, &frame, None, &DIDExample, &mut context_loader)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Bbs(BbsError::FrameMismatch)));
+    }
+}
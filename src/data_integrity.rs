@@ -0,0 +1,520 @@
+//! [Verifiable Credential Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proofs.
+//!
+//! A `DataIntegrityProof` names its [`Cryptosuite`] in the `cryptosuite` property, and carries the
+//! signature as a multibase-encoded `proofValue`.
+use std::collections::HashMap as Map;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::did_resolve::DIDResolver;
+use crate::error::Error;
+use crate::hash::sha256;
+use crate::jsonld::{
+    ContextLoader, CREDENTIALS_V2_CONTEXT, W3ID_DATA_INTEGRITY_V1_CONTEXT,
+    W3ID_DATA_INTEGRITY_V2_CONTEXT,
+};
+use crate::jwk::{Algorithm, Base64urlUInt, JWK};
+use crate::ldp::{
+    document_has_context, resolve_key, to_jws_payload, LinkedDataDocument, ProofPreparation,
+    ProofSuite, ProofSuiteRegistry, SigningInput, VerificationWarnings,
+};
+use crate::signer::Signer;
+use crate::vc::{LinkedDataProofOptions, Proof};
+
+pub const DATA_INTEGRITY_PROOF_TYPE: &str = "DataIntegrityProof";
+
+/// A cryptographic suite for Data Integrity proofs: the transformation and hashing of the
+/// document and proof configuration, and the signature algorithm applied to the result.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Cryptosuite {
+    /// Identifier of the suite, as used in the `cryptosuite` property of a proof.
+    fn name(&self) -> &'static str;
+
+    /// Signature algorithm used to sign the hash data.
+    fn algorithm(&self) -> Algorithm;
+
+    /// Whether the suite interprets the document as JSON-LD, and so needs the proof terms to be
+    /// defined in its context.
+    fn is_linked_data(&self) -> bool {
+        true
+    }
+
+    /// Compute the data to be signed for a document and proof configuration.
+    async fn hash_data(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        proof: &Proof,
+        context_loader: &mut ContextLoader,
+    ) -> Result<Vec<u8>, Error>;
+}
+
+/// Get a cryptosuite by name, from the [global](ProofSuiteRegistry::global) registry.
+pub fn get_cryptosuite(name: &str) -> Result<&'static (dyn Cryptosuite + Sync), Error> {
+    ProofSuiteRegistry::read_global().get_cryptosuite(name)
+}
+
+pub(crate) fn get_builtin_cryptosuite(
+    name: &str,
+) -> Result<&'static (dyn Cryptosuite + Sync), Error> {
+    Ok(match name {
+        "eddsa-rdfc-2022" => &EddsaRdfc2022,
+        "eddsa-jcs-2022" => &EddsaJcs2022,
+        "ecdsa-rdfc-2019" => &EcdsaRdfc2019,
+        _ => return Err(Error::CryptosuiteNotImplemented(name.to_string())),
+    })
+}
+
+/// Pick a default cryptosuite for a key.
+pub fn pick_cryptosuite(key: &JWK) -> Result<&'static (dyn Cryptosuite + Sync), Error> {
+    let algorithm = key.get_algorithm().ok_or(Error::MissingAlgorithm)?;
+    Ok(match algorithm {
+        Algorithm::EdDSA => &EddsaRdfc2022,
+        Algorithm::ES256 => &EcdsaRdfc2019,
+        _ => return Err(Error::AlgorithmNotImplemented),
+    })
+}
+
+/// <https://www.w3.org/TR/vc-di-eddsa/#eddsa-rdfc-2022>
+pub struct EddsaRdfc2022;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Cryptosuite for EddsaRdfc2022 {
+    fn name(&self) -> &'static str {
+        "eddsa-rdfc-2022"
+    }
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::EdDSA
+    }
+    async fn hash_data(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        proof: &Proof,
+        context_loader: &mut ContextLoader,
+    ) -> Result<Vec<u8>, Error> {
+        to_jws_payload(document, proof, context_loader).await
+    }
+}
+
+/// <https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022>
+pub struct EddsaJcs2022;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Cryptosuite for EddsaJcs2022 {
+    fn name(&self) -> &'static str {
+        "eddsa-jcs-2022"
+    }
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::EdDSA
+    }
+    fn is_linked_data(&self) -> bool {
+        false
+    }
+    async fn hash_data(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        proof: &Proof,
+        _context_loader: &mut ContextLoader,
+    ) -> Result<Vec<u8>, Error> {
+        jcs_hash_data(document, proof)
+    }
+}
+
+/// <https://www.w3.org/TR/vc-di-ecdsa/#ecdsa-rdfc-2019>
+///
+/// Only P-256 keys are supported.
+pub struct EcdsaRdfc2019;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Cryptosuite for EcdsaRdfc2019 {
+    fn name(&self) -> &'static str {
+        "ecdsa-rdfc-2019"
+    }
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::ES256
+    }
+    async fn hash_data(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        proof: &Proof,
+        context_loader: &mut ContextLoader,
+    ) -> Result<Vec<u8>, Error> {
+        to_jws_payload(document, proof, context_loader).await
+    }
+}
+
+/// Hash the JCS-canonicalized proof configuration and document (without proof), with the proof
+/// configuration taking the document's `@context`.
+fn jcs_hash_data(
+    document: &(dyn LinkedDataDocument + Sync),
+    proof: &Proof,
+) -> Result<Vec<u8>, Error> {
+    let mut doc_value = document.to_value()?;
+    let doc_obj = doc_value.as_object_mut().ok_or(Error::ExpectedObject)?;
+    doc_obj.remove("proof");
+    let mut proof_value = serde_json::to_value(proof)?;
+    let proof_obj = proof_value.as_object_mut().ok_or(Error::ExpectedObject)?;
+    proof_obj.remove("proofValue");
+    proof_obj.remove("@context");
+    if let Some(context) = doc_obj.get("@context") {
+        proof_obj.insert("@context".to_string(), context.clone());
+    }
+    let proof_config_digest = sha256(serde_jcs::to_string(&proof_value)?.as_bytes())?;
+    let doc_digest = sha256(serde_jcs::to_string(&doc_value)?.as_bytes())?;
+    Ok([proof_config_digest.to_vec(), doc_digest.to_vec()].concat())
+}
+
+/// Whether a document's context already defines the `DataIntegrityProof` terms.
+fn document_has_data_integrity_context(
+    document: &(dyn LinkedDataDocument + Sync),
+) -> Result<bool, Error> {
+    Ok(document_has_context(document, CREDENTIALS_V2_CONTEXT)?
+        || document_has_context(document, W3ID_DATA_INTEGRITY_V2_CONTEXT)?
+        || document_has_context(document, W3ID_DATA_INTEGRITY_V1_CONTEXT)?)
+}
+
+/// <https://www.w3.org/TR/vc-data-integrity/#dataintegrityproof>
+pub struct DataIntegrityProof;
+impl DataIntegrityProof {
+    fn cryptosuite(
+        options: &LinkedDataProofOptions,
+        key: &JWK,
+    ) -> Result<&'static (dyn Cryptosuite + Sync), Error> {
+        let cryptosuite = match options.cryptosuite {
+            Some(ref name) => get_cryptosuite(name)?,
+            None => pick_cryptosuite(key)?,
+        };
+        if let Some(key_algorithm) = key.algorithm {
+            if key_algorithm != cryptosuite.algorithm() {
+                return Err(Error::AlgorithmMismatch);
+            }
+        }
+        Ok(cryptosuite)
+    }
+
+    fn new_proof(
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        cryptosuite: &dyn Cryptosuite,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<Proof, Error> {
+        let mut proof = Proof {
+            cryptosuite: Some(cryptosuite.name().to_string()),
+            ..Proof::new(DATA_INTEGRITY_PROOF_TYPE)
+                .with_options(options)
+                .with_properties(extra_proof_properties)
+        };
+        if cryptosuite.is_linked_data() && !document_has_data_integrity_context(document)? {
+            proof.context = serde_json::json!([W3ID_DATA_INTEGRITY_V2_CONTEXT]);
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ProofSuite for DataIntegrityProof {
+    async fn sign(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        key: &dyn Signer,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<Proof, Error> {
+        let cryptosuite = Self::cryptosuite(options, &key.public_key())?;
+        let mut proof = Self::new_proof(document, options, cryptosuite, extra_proof_properties)?;
+        let message = cryptosuite
+            .hash_data(document, &proof, context_loader)
+            .await?;
+        let sig = key.sign(cryptosuite.algorithm(), &message).await?;
+        proof.proof_value = Some(multibase::encode(multibase::Base::Base58Btc, sig));
+        Ok(proof)
+    }
+
+    async fn prepare(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        public_key: &JWK,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<ProofPreparation, Error> {
+        let cryptosuite = Self::cryptosuite(options, public_key)?;
+        let proof = Self::new_proof(document, options, cryptosuite, extra_proof_properties)?;
+        let message = cryptosuite
+            .hash_data(document, &proof, context_loader)
+            .await?;
+        Ok(ProofPreparation {
+            proof,
+            jws_header: None,
+            signing_input: SigningInput::Bytes(Base64urlUInt(message)),
+        })
+    }
+
+    async fn complete(
+        &self,
+        preparation: ProofPreparation,
+        signature: &str,
+    ) -> Result<Proof, Error> {
+        let mut proof = preparation.proof;
+        proof.proof_value = Some(signature.to_string());
+        Ok(proof)
+    }
+
+    async fn verify(
+        &self,
+        proof: &Proof,
+        document: &(dyn LinkedDataDocument + Sync),
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> Result<VerificationWarnings, Error> {
+        let cryptosuite_name = proof
+            .cryptosuite
+            .as_ref()
+            .ok_or(Error::MissingCryptosuite)?;
+        let cryptosuite = get_cryptosuite(cryptosuite_name)?;
+        let proof_value = proof
+            .proof_value
+            .as_ref()
+            .ok_or(Error::MissingProofSignature)?;
+        let verification_method = proof
+            .verification_method
+            .as_ref()
+            .ok_or(Error::MissingVerificationMethod)?;
+        let key = resolve_key(verification_method, resolver).await?;
+        let message = cryptosuite
+            .hash_data(document, proof, context_loader)
+            .await?;
+        let (_base, sig) = multibase::decode(proof_value)?;
+        crate::jws::verify_bytes_warnable(cryptosuite.algorithm(), &message, &key, &sig)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+    use crate::one_or_many::OneOrMany;
+    use crate::vc::{Credential, Presentation, ProofPurpose, URI};
+
+    const VC_V1: &str = r#"{
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "id": "http://example.org/credentials/3731",
+        "type": ["VerifiableCredential"],
+        "issuer": "did:example:foo",
+        "issuanceDate": "2020-08-19T21:41:50Z",
+        "credentialSubject": {
+            "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+        }
+    }"#;
+
+    const VC_V2: &str = r#"{
+        "@context": "https://www.w3.org/ns/credentials/v2",
+        "id": "http://example.org/credentials/3732",
+        "type": ["VerifiableCredential"],
+        "issuer": "did:example:foo",
+        "validFrom": "2020-08-19T21:41:50Z",
+        "credentialSubject": {
+            "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+        }
+    }"#;
+
+    fn ed25519_key() -> JWK {
+        serde_json::from_str(include_str!("../tests/ed25519-2020-10-18.json")).unwrap()
+    }
+
+    async fn issue_verify(vc_str: &str, key: &JWK, vm: &str, cryptosuite: Option<&str>) {
+        let mut vc = Credential::from_json_unsigned(vc_str).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String(vm.to_string())),
+            type_: Some(DATA_INTEGRITY_PROOF_TYPE.to_string()),
+            cryptosuite: cryptosuite.map(|name| name.to_string()),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        let proof = vc
+            .generate_proof(key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        println!("{}", serde_json::to_string_pretty(&proof).unwrap());
+        assert_eq!(proof.type_, DATA_INTEGRITY_PROOF_TYPE);
+        assert!(proof.proof_value.as_ref().unwrap().starts_with('z'));
+        if let Some(name) = cryptosuite {
+            assert_eq!(proof.cryptosuite.as_deref(), Some(name));
+        }
+        vc.add_proof(proof);
+        vc.validate().unwrap();
+        let verification_result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        // Round-trip through JSON
+        let vc_json = serde_json::to_string(&vc).unwrap();
+        let vc1 = Credential::from_json(&vc_json).unwrap();
+        let verification_result = vc1.verify(None, &DIDExample, &mut context_loader).await;
+        assert!(verification_result.errors.is_empty());
+
+        // Tamper with the credential
+        let mut vc_bad = vc.clone();
+        vc_bad.id = Some(URI::String("http://example.org/credentials/0".to_string()));
+        let verification_result = vc_bad.verify(None, &DIDExample, &mut context_loader).await;
+        assert!(!verification_result.errors.is_empty());
+
+        // Tamper with the proof's cryptosuite
+        let mut vc_bad = vc.clone();
+        if let Some(OneOrMany::One(ref mut proof)) = vc_bad.proof {
+            proof.cryptosuite = Some("eddsa-2022".to_string());
+        }
+        let verification_result = vc_bad.verify(None, &DIDExample, &mut context_loader).await;
+        assert!(!verification_result.errors.is_empty());
+    }
+
+    #[async_std::test]
+    async fn eddsa_rdfc_2022() {
+        let key = ed25519_key();
+        issue_verify(VC_V1, &key, "did:example:foo#key2", None).await;
+        issue_verify(VC_V2, &key, "did:example:foo#key2", Some("eddsa-rdfc-2022")).await;
+    }
+
+    #[async_std::test]
+    async fn eddsa_rdfc_2022_test_vector() {
+        // https://www.w3.org/TR/vc-di-eddsa/#representation-eddsa-rdfc-2022
+        let (_, sk) =
+            multibase::decode("z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq").unwrap();
+        let (_, pk) =
+            multibase::decode("z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2").unwrap();
+        let key = JWK::from(crate::jwk::Params::OKP(crate::jwk::OctetParams {
+            curve: "Ed25519".to_string(),
+            public_key: Base64urlUInt(pk[2..].to_vec()),
+            private_key: Some(Base64urlUInt(sk[2..].to_vec())),
+        }));
+        let vc = Credential::from_json_unsigned(
+            r#"{
+                "@context": [
+                    "https://www.w3.org/ns/credentials/v2",
+                    "https://www.w3.org/ns/credentials/examples/v2"
+                ],
+                "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+                "type": ["VerifiableCredential", "AlumniCredential"],
+                "name": "Alumni Credential",
+                "description": "A minimum viable example of an Alumni Credential.",
+                "issuer": "https://vc.example/issuers/5678",
+                "validFrom": "2023-01-01T00:00:00Z",
+                "credentialSubject": {
+                    "id": "did:example:abcdefgh",
+                    "alumniOf": "The School of Examples"
+                }
+            }"#,
+        )
+        .unwrap();
+        let options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2".to_string())),
+            created: Some("2023-02-24T23:36:38Z".parse().unwrap()),
+            cryptosuite: Some("eddsa-rdfc-2022".to_string()),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        let proof = DataIntegrityProof
+            .sign(&vc, &options, &DIDExample, &mut context_loader, &key, None)
+            .await
+            .unwrap();
+        assert_eq!(proof.context, Value::Null);
+        assert_eq!(
+            proof.proof_value.unwrap(),
+            "z2YwC8z3ap7yx1nZYCg4L3j3ApHsF8kgPdSb5xoS1VR7vPG3F561B52hYnQF9iseabecm3ijx4K1FBTQsCZahKZme"
+        );
+    }
+
+    #[async_std::test]
+    async fn eddsa_jcs_2022() {
+        let key = ed25519_key();
+        issue_verify(VC_V1, &key, "did:example:foo#key2", Some("eddsa-jcs-2022")).await;
+        issue_verify(VC_V2, &key, "did:example:foo#key2", Some("eddsa-jcs-2022")).await;
+    }
+
+    #[async_std::test]
+    #[cfg(feature = "p256")]
+    async fn ecdsa_rdfc_2019() {
+        let key: JWK =
+            serde_json::from_str(include_str!("../tests/secp256r1-2021-03-18.json")).unwrap();
+        let vc_v1 = VC_V1.replace("did:example:foo", "did:example:p256");
+        let vc_v2 = VC_V2.replace("did:example:foo", "did:example:p256");
+        issue_verify(&vc_v1, &key, "did:example:p256#key1", None).await;
+        issue_verify(
+            &vc_v2,
+            &key,
+            "did:example:p256#key1",
+            Some("ecdsa-rdfc-2019"),
+        )
+        .await;
+    }
+
+    #[async_std::test]
+    async fn cryptosuite_key_mismatch() {
+        let mut key = ed25519_key();
+        key.algorithm = Some(Algorithm::EdDSA);
+        let vc = Credential::from_json_unsigned(VC_V2).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key2".to_string())),
+            type_: Some(DATA_INTEGRITY_PROOF_TYPE.to_string()),
+            cryptosuite: Some("ecdsa-rdfc-2019".to_string()),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        vc.generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap_err();
+        let issue_options = LinkedDataProofOptions {
+            cryptosuite: Some("unknown-2000".to_string()),
+            ..issue_options
+        };
+        let err = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::CryptosuiteNotImplemented(_)));
+    }
+
+    #[async_std::test]
+    async fn presentation_eddsa_rdfc_2022() {
+        let vp_str = r#"{
+            "@context": "https://www.w3.org/ns/credentials/v2",
+            "type": ["VerifiablePresentation"],
+            "holder": "did:example:foo"
+        }"#;
+        let mut vp: Presentation = serde_json::from_str(vp_str).unwrap();
+        let vp_issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key2".to_string())),
+            proof_purpose: Some(ProofPurpose::Authentication),
+            cryptosuite: Some("eddsa-rdfc-2022".to_string()),
+            challenge: Some("abc".to_string()),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        let proof = vp
+            .generate_proof(
+                &ed25519_key(),
+                &vp_issue_options,
+                &DIDExample,
+                &mut context_loader,
+            )
+            .await
+            .unwrap();
+        assert_eq!(proof.type_, DATA_INTEGRITY_PROOF_TYPE);
+        vp.add_proof(proof);
+        let vp_verify_options = LinkedDataProofOptions {
+            proof_purpose: Some(ProofPurpose::Authentication),
+            challenge: Some("abc".to_string()),
+            ..Default::default()
+        };
+        let verification_result = vp
+            .verify(Some(vp_verify_options), &DIDExample, &mut context_loader)
+            .await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+    }
+}
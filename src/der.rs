@@ -13,9 +13,13 @@
 // ISO/IEC 8825-1:2015 (E)
 // https://tools.ietf.org/html/rfc8017#page-55
 // https://tools.ietf.org/html/rfc8410
+// https://tools.ietf.org/html/rfc5958
+// https://tools.ietf.org/html/rfc5915
 
-use num_bigint::{BigInt, Sign};
-use simple_asn1::{der_encode, ASN1Block, ASN1Class, ASN1DecodeErr, FromASN1, ToASN1};
+use std::convert::TryFrom;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use simple_asn1::{der_encode, ASN1Block, ASN1Class, ASN1DecodeErr, FromASN1, ToASN1, OID};
 
 use crate::error::Error;
 
@@ -62,6 +66,56 @@ pub struct Ed25519PrivateKey {
     pub private_key: OctetString,
 }
 
+/// Algorithm identifier for ASN.1 encoding, as specified in [RFC 5280].
+///
+/// [RFC 5280]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.1.2 "RFC 5280 Internet X.509 Public Key Infrastructure Certificate and CRL Profile - 4.1.1.2. signatureAlgorithm"
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmIdentifier {
+    pub algorithm: OID,
+    pub parameters: Option<ASN1Block>,
+}
+
+/// Public key with its algorithm for ASN.1 encoding, as specified in [RFC 5280].
+///
+/// [RFC 5280]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.7 "RFC 5280 Internet X.509 Public Key Infrastructure Certificate and CRL Profile - 4.1.2.7. Subject Public Key Info"
+#[derive(Debug, Clone)]
+pub struct SubjectPublicKeyInfo {
+    pub algorithm: AlgorithmIdentifier,
+    pub subject_public_key: BitString,
+}
+
+/// Private key with its algorithm (`OneAsymmetricKey`) for ASN.1 encoding, as specified in
+/// [RFC 5958].
+///
+/// [RFC 5958]: https://datatracker.ietf.org/doc/html/rfc5958#section-2 "RFC 5958 Asymmetric Key Packages - 2. Asymmetric Key Package CMS Content Type"
+#[derive(Debug, Clone)]
+pub struct PrivateKeyInfo {
+    pub algorithm: AlgorithmIdentifier,
+    /// Private key, itself DER-encoded in a structure specific to the algorithm.
+    pub private_key: OctetString,
+    pub public_key: Option<BitString>,
+}
+
+/// Encrypted [private key info][PrivateKeyInfo] for ASN.1 encoding, as specified in [RFC 5958].
+///
+/// [RFC 5958]: https://datatracker.ietf.org/doc/html/rfc5958#section-3 "RFC 5958 Asymmetric Key Packages - 3. Encrypted Private Key Info"
+#[derive(Debug, Clone)]
+pub struct EncryptedPrivateKeyInfo {
+    pub encryption_algorithm: AlgorithmIdentifier,
+    pub encrypted_data: OctetString,
+}
+
+/// Elliptic curve private key for ASN.1 encoding, as specified in [RFC 5915] (SEC 1).
+///
+/// [RFC 5915]: https://datatracker.ietf.org/doc/html/rfc5915#section-3 "RFC 5915 Elliptic Curve Private Key Structure - 3. Details of ECPrivateKey"
+#[derive(Debug, Clone)]
+pub struct ECPrivateKey {
+    pub private_key: OctetString,
+    /// Named curve
+    pub parameters: Option<OID>,
+    pub public_key: Option<BitString>,
+}
+
 /// Additional primes in a [RSA private key][RSAPrivateKey], as specified in [RFC 8017].
 ///
 /// [RFC 8017]: https://datatracker.ietf.org/doc/html/rfc8017#page-56 "RFC 8017 PKCS #1 v2.2 - Page 56"
@@ -212,6 +266,342 @@ impl ToASN1 for Ed25519PublicKey {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum KeyFromASN1Error {
+    #[error("Unexpected ASN.1 structure for {0}")]
+    Unexpected(&'static str),
+    #[error("Unsupported {0} version: {1}")]
+    UnsupportedVersion(&'static str, BigInt),
+    #[error("ASN1 decoding error: {0:?}")]
+    ASN1Decode(#[from] ASN1DecodeErr),
+}
+
+/// Get the version of a structure, which must be one of the supported versions.
+fn check_version(
+    block: &ASN1Block,
+    name: &'static str,
+    supported: &[u32],
+) -> Result<u32, KeyFromASN1Error> {
+    let version = match block {
+        ASN1Block::Integer(_, version) => version,
+        _ => return Err(KeyFromASN1Error::Unexpected(name)),
+    };
+    supported
+        .iter()
+        .copied()
+        .find(|supported| *version == BigInt::from(*supported))
+        .ok_or_else(|| KeyFromASN1Error::UnsupportedVersion(name, version.clone()))
+}
+
+/// Get an explicitly or implicitly tagged block with the given context-specific tag.
+fn find_tagged(blocks: &[ASN1Block], tag: u8) -> Option<&ASN1Block> {
+    let tag = BigUint::from(tag);
+    blocks.iter().find(|block| match block {
+        ASN1Block::Explicit(ASN1Class::ContextSpecific, _, block_tag, _)
+        | ASN1Block::Unknown(ASN1Class::ContextSpecific, _, _, block_tag, _) => *block_tag == tag,
+        _ => false,
+    })
+}
+
+/// Decode the content of an implicitly tagged bit string.
+fn implicit_bit_string(block: &ASN1Block) -> Option<BitString> {
+    match block {
+        ASN1Block::Unknown(_, false, _, _, content) => match content.split_first() {
+            Some((0, bytes)) => Some(BitString(bytes.to_vec())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl FromASN1 for RSAPrivateKey {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "RSAPrivateKey";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let vec = match block {
+            ASN1Block::Sequence(_, vec) => vec,
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let (version, integers) = vec
+            .split_first()
+            .ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        check_version(version, NAME, &[0, 1])?;
+        let integers = integers
+            .iter()
+            .take(8)
+            .map(|block| match block {
+                ASN1Block::Integer(_, integer) => Ok(Integer(integer.clone())),
+                _ => Err(KeyFromASN1Error::Unexpected(NAME)),
+            })
+            .collect::<Result<Vec<Integer>, KeyFromASN1Error>>()?;
+        let key = match <[Integer; 8]>::try_from(integers) {
+            Ok([n, e, d, p, q, dp, dq, qi]) => Self {
+                modulus: n,
+                public_exponent: e,
+                private_exponent: d,
+                prime1: p,
+                prime2: q,
+                exponent1: dp,
+                exponent2: dq,
+                coefficient: qi,
+                // TODO: support multi-prime keys
+                other_prime_infos: None,
+            },
+            Err(_) => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        Ok((key, rest))
+    }
+}
+
+impl AlgorithmIdentifier {
+    pub fn to_block(&self) -> ASN1Block {
+        let mut vec = vec![ASN1Block::ObjectIdentifier(0, self.algorithm.clone())];
+        vec.extend(self.parameters.clone());
+        ASN1Block::Sequence(0, vec)
+    }
+}
+
+impl ToASN1 for AlgorithmIdentifier {
+    type Error = Error;
+    fn to_asn1_class(&self, _class: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
+        Ok(vec![self.to_block()])
+    }
+}
+
+impl FromASN1 for AlgorithmIdentifier {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "AlgorithmIdentifier";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let algorithm_identifier = match block {
+            ASN1Block::Sequence(_, vec) => match vec.as_slice() {
+                [ASN1Block::ObjectIdentifier(_, algorithm)] => Self {
+                    algorithm: algorithm.clone(),
+                    parameters: None,
+                },
+                [ASN1Block::ObjectIdentifier(_, algorithm), parameters] => Self {
+                    algorithm: algorithm.clone(),
+                    parameters: Some(parameters.clone()),
+                },
+                _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+            },
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        Ok((algorithm_identifier, rest))
+    }
+}
+
+impl ToASN1 for SubjectPublicKeyInfo {
+    type Error = Error;
+    fn to_asn1_class(&self, class: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
+        Ok(vec![ASN1Block::Sequence(
+            0,
+            [
+                self.algorithm.to_asn1_class(class)?,
+                self.subject_public_key.to_asn1_class(class)?,
+            ]
+            .concat(),
+        )])
+    }
+}
+
+impl FromASN1 for SubjectPublicKeyInfo {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "SubjectPublicKeyInfo";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let vec = match block {
+            ASN1Block::Sequence(_, vec) => vec,
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let (algorithm, vec) = AlgorithmIdentifier::from_asn1(vec)?;
+        let subject_public_key = match vec {
+            [ASN1Block::BitString(_, nbits, bytes)] if *nbits == bytes.len() * 8 => {
+                BitString(bytes.clone())
+            }
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let spki = Self {
+            algorithm,
+            subject_public_key,
+        };
+        Ok((spki, rest))
+    }
+}
+
+impl ToASN1 for PrivateKeyInfo {
+    type Error = Error;
+    fn to_asn1_class(&self, class: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
+        // Version 2 (v2) if the public key is included, otherwise version 1 (v1).
+        let version = if self.public_key.is_some() { 1 } else { 0 };
+        let public_key = match self.public_key {
+            Some(ref public_key) => vec![ASN1Block::Unknown(
+                ASN1Class::ContextSpecific,
+                false,
+                0,
+                BigUint::from(1u8),
+                [&[0], &public_key.0[..]].concat(),
+            )],
+            None => Vec::new(),
+        };
+        Ok(vec![ASN1Block::Sequence(
+            0,
+            [
+                vec![ASN1Block::Integer(0, BigInt::from(version))],
+                self.algorithm.to_asn1_class(class)?,
+                self.private_key.to_asn1_class(class)?,
+                public_key,
+            ]
+            .concat(),
+        )])
+    }
+}
+
+impl FromASN1 for PrivateKeyInfo {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "PrivateKeyInfo";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let vec = match block {
+            ASN1Block::Sequence(_, vec) => vec,
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let (version, vec) = vec
+            .split_first()
+            .ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        check_version(version, NAME, &[0, 1])?;
+        let (algorithm, vec) = AlgorithmIdentifier::from_asn1(vec)?;
+        let (private_key, attributes) = match vec.split_first() {
+            Some((ASN1Block::OctetString(_, private_key), attributes)) => {
+                (OctetString(private_key.clone()), attributes)
+            }
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let public_key = match find_tagged(attributes, 1) {
+            Some(block) => {
+                Some(implicit_bit_string(block).ok_or(KeyFromASN1Error::Unexpected(NAME))?)
+            }
+            None => None,
+        };
+        let key = Self {
+            algorithm,
+            private_key,
+            public_key,
+        };
+        Ok((key, rest))
+    }
+}
+
+impl ToASN1 for EncryptedPrivateKeyInfo {
+    type Error = Error;
+    fn to_asn1_class(&self, class: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
+        Ok(vec![ASN1Block::Sequence(
+            0,
+            [
+                self.encryption_algorithm.to_asn1_class(class)?,
+                self.encrypted_data.to_asn1_class(class)?,
+            ]
+            .concat(),
+        )])
+    }
+}
+
+impl FromASN1 for EncryptedPrivateKeyInfo {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "EncryptedPrivateKeyInfo";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let vec = match block {
+            ASN1Block::Sequence(_, vec) => vec,
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let (encryption_algorithm, vec) = AlgorithmIdentifier::from_asn1(vec)?;
+        let encrypted_data = match vec {
+            [ASN1Block::OctetString(_, encrypted_data)] => OctetString(encrypted_data.clone()),
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let key = Self {
+            encryption_algorithm,
+            encrypted_data,
+        };
+        Ok((key, rest))
+    }
+}
+
+impl ToASN1 for ECPrivateKey {
+    type Error = Error;
+    fn to_asn1_class(&self, class: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
+        let version = 1;
+        let mut vec = vec![ASN1Block::Integer(0, BigInt::from(version))];
+        vec.extend(self.private_key.to_asn1_class(class)?);
+        if let Some(ref parameters) = self.parameters {
+            vec.push(ASN1Block::Explicit(
+                ASN1Class::ContextSpecific,
+                0,
+                BigUint::from(0u8),
+                Box::new(ASN1Block::ObjectIdentifier(0, parameters.clone())),
+            ));
+        }
+        if let Some(ref public_key) = self.public_key {
+            let mut public_key = public_key.to_asn1_class(class)?;
+            vec.push(ASN1Block::Explicit(
+                ASN1Class::ContextSpecific,
+                0,
+                BigUint::from(1u8),
+                Box::new(public_key.remove(0)),
+            ));
+        }
+        Ok(vec![ASN1Block::Sequence(0, vec)])
+    }
+}
+
+impl FromASN1 for ECPrivateKey {
+    type Error = KeyFromASN1Error;
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Self::Error> {
+        const NAME: &str = "ECPrivateKey";
+        let (block, rest) = v.split_first().ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        let vec = match block {
+            ASN1Block::Sequence(_, vec) => vec,
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let (version, vec) = vec
+            .split_first()
+            .ok_or(KeyFromASN1Error::Unexpected(NAME))?;
+        check_version(version, NAME, &[1])?;
+        let (private_key, vec) = match vec.split_first() {
+            Some((ASN1Block::OctetString(_, private_key), vec)) => {
+                (OctetString(private_key.clone()), vec)
+            }
+            _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+        };
+        let parameters = match find_tagged(vec, 0) {
+            Some(ASN1Block::Explicit(_, _, _, block)) => match block.as_ref() {
+                ASN1Block::ObjectIdentifier(_, oid) => Some(oid.clone()),
+                _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+            },
+            Some(_) => return Err(KeyFromASN1Error::Unexpected(NAME)),
+            None => None,
+        };
+        let public_key = match find_tagged(vec, 1) {
+            Some(ASN1Block::Explicit(_, _, _, block)) => match block.as_ref() {
+                ASN1Block::BitString(_, nbits, bytes) if *nbits == bytes.len() * 8 => {
+                    Some(BitString(bytes.clone()))
+                }
+                _ => return Err(KeyFromASN1Error::Unexpected(NAME)),
+            },
+            Some(_) => return Err(KeyFromASN1Error::Unexpected(NAME)),
+            None => None,
+        };
+        let key = Self {
+            private_key,
+            parameters,
+            public_key,
+        };
+        Ok((key, rest))
+    }
+}
+
 impl ToASN1 for Integer {
     type Error = Error;
     fn to_asn1_class(&self, _: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
@@ -229,7 +619,11 @@ impl ToASN1 for OctetString {
 impl ToASN1 for BitString {
     type Error = Error;
     fn to_asn1_class(&self, _: ASN1Class) -> Result<Vec<ASN1Block>, Self::Error> {
-        Ok(vec![ASN1Block::BitString(0, 0, self.0.clone())])
+        Ok(vec![ASN1Block::BitString(
+            0,
+            self.0.len() * 8,
+            self.0.clone(),
+        )])
     }
 }
 
@@ -53,6 +53,7 @@ pub const ALT_DEFAULT_CONTEXT: &str = crate::jsonld::W3ID_DID_V1_CONTEXT;
 pub const V0_11_CONTEXT: &str = "https://w3id.org/did/v0.11";
 
 const MULTICODEC_ED25519_PREFIX: [u8; 2] = [0xed, 0x01];
+const MULTICODEC_X25519_PREFIX: [u8; 2] = [0xec, 0x01];
 
 // @TODO parsed data structs for DID and DIDURL
 #[allow(clippy::upper_case_acronyms)]
@@ -840,6 +841,24 @@ impl VerificationMethodMap {
                     private_key: None,
                 })
             }
+            "X25519KeyAgreementKey2019" => crate::jwk::Params::OKP(crate::jwk::OctetParams {
+                curve: "X25519".to_string(),
+                public_key: crate::jwk::Base64urlUInt(pk_bytes),
+                private_key: None,
+            }),
+            "X25519KeyAgreementKey2020" => {
+                if pk_bytes.len() != 34 {
+                    return Err(Error::MultibaseKeyLength(34, pk_bytes.len()));
+                }
+                if pk_bytes[0..2] != MULTICODEC_X25519_PREFIX {
+                    return Err(Error::MultibaseKeyPrefix);
+                }
+                crate::jwk::Params::OKP(crate::jwk::OctetParams {
+                    curve: "X25519".to_string(),
+                    public_key: crate::jwk::Base64urlUInt(pk_bytes[2..].to_owned()),
+                    private_key: None,
+                })
+            }
             #[cfg(feature = "k256")]
             "EcdsaSecp256k1VerificationKey2019" | "EcdsaSecp256k1RecoveryMethod2020" => {
                 use crate::jwk::secp256k1_parse;
@@ -1282,6 +1301,44 @@ impl Document {
         Ok(vm_ids)
     }
 
+    /// Get the [key agreement](https://www.w3.org/TR/did-core/#key-agreement) verification
+    /// methods of a DID document, selecting those referenced by DID URL from the document.
+    pub fn select_key_agreement_methods(&self) -> Result<Vec<VerificationMethodMap>, Error> {
+        self.key_agreement
+            .iter()
+            .flatten()
+            .map(|vm| match vm {
+                VerificationMethod::Map(vmm) => {
+                    let mut vmm = vmm.clone();
+                    vmm.id = vmm.get_id(&self.id);
+                    Ok(vmm)
+                }
+                VerificationMethod::DIDURL(_) | VerificationMethod::RelativeDIDURL(_) => {
+                    let id = vm.get_id(&self.id);
+                    match self.select_object(&DIDURL::try_from(id.clone())?)? {
+                        Resource::VerificationMethod(mut vmm) => {
+                            vmm.id = id;
+                            Ok(vmm)
+                        }
+                        _ => Err(Error::ResourceNotFound(id)),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Get the key agreement keys of a DID document, with the verification method ids as key ids.
+    pub fn get_key_agreement_keys(&self) -> Result<Vec<JWK>, Error> {
+        self.select_key_agreement_methods()?
+            .into_iter()
+            .map(|vmm| {
+                let mut jwk = vmm.get_jwk()?;
+                jwk.key_id = Some(vmm.id);
+                Ok(jwk)
+            })
+            .collect()
+    }
+
     /// Serialize a DID document with a given
     /// [representation](https://www.w3.org/TR/did-core/#representations) identified by a
     /// content-type string.
@@ -1305,6 +1362,9 @@ pub mod example {
     const DOC_JSON_FOO: &str = include_str!("../tests/did-example-foo.json");
     const DOC_JSON_BAR: &str = include_str!("../tests/did-example-bar.json");
     const DOC_JSON_12345: &str = include_str!("../tests/did-example-12345.json");
+    const DOC_JSON_P256: &str = include_str!("../tests/did-example-p256.json");
+    const DOC_JSON_BLS12381: &str = include_str!("../tests/did-example-bls12381.json");
+    const DOC_JSON_X25519: &str = include_str!("../tests/did-example-x25519.json");
 
     // For vc-test-suite
     const DOC_JSON_TEST_ISSUER: &str = include_str!("../tests/did-example-test-issuer.json");
@@ -1344,6 +1404,9 @@ pub mod example {
                 "did:example:bar" => DOC_JSON_BAR,
                 "did:example:0xab" => DOC_JSON_TEST_ISSUER,
                 "did:example:12345" => DOC_JSON_12345,
+                "did:example:p256" => DOC_JSON_P256,
+                "did:example:bls12381" => DOC_JSON_BLS12381,
+                "did:example:x25519" => DOC_JSON_X25519,
                 "did:example:ebfeb1f712ebc6f1c276e12ec21" => DOC_JSON_TEST_HOLDER,
                 _ => return (ResolutionMetadata::from_error(ERROR_NOT_FOUND), None, None),
             };
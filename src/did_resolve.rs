@@ -4,6 +4,7 @@
 
 use async_trait::async_trait;
 use chrono::prelude::{DateTime, Utc};
+use chrono::Duration;
 #[cfg(feature = "http-did")]
 use reqwest::{header, Client, StatusCode, Url};
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,7 @@ use serde_json::Value;
 use serde_urlencoded;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
+use std::sync::Mutex;
 
 // https://w3c-ccg.github.io/did-resolution/
 use crate::did::{
@@ -1323,6 +1325,185 @@ impl<'a> DIDResolver for SeriesResolver<'a> {
     }
 }
 
+type ResolveOutput = (
+    ResolutionMetadata,
+    Option<Document>,
+    Option<DocumentMetadata>,
+);
+
+struct CachedResolution {
+    result: ResolveOutput,
+    expires: DateTime<Utc>,
+}
+
+/// DID resolver caching the results of another DID resolver.
+///
+/// Successful resolution results are cached for a [time-to-live](Self::with_ttl), and errors
+/// (e.g. [`notFound`][ERROR_NOT_FOUND]) for a usually shorter [one](Self::with_error_ttl). When
+/// the cache is [full](Self::with_capacity), the entry closest to expiry is evicted.
+///
+/// Resolution with the `versionId` or `versionTime` input metadata bypasses the cache, as does
+/// resolution with the `noCache` option, whose result replaces the cached one.
+/// [`resolve_representation`][DIDResolver::resolve_representation] and
+/// [`dereference`][DIDResolver::dereference] are not cached.
+pub struct CachingResolver<R: DIDResolver> {
+    resolver: R,
+    ttl: Duration,
+    error_ttl: Duration,
+    capacity: usize,
+    cache: Mutex<HashMap<String, CachedResolution>>,
+}
+
+impl<R: DIDResolver> CachingResolver<R> {
+    /// Default time-to-live of successful resolution results
+    pub const DEFAULT_TTL: i64 = 300;
+    /// Default time-to-live of resolution errors
+    pub const DEFAULT_ERROR_TTL: i64 = 30;
+    /// Default maximum number of cached resolution results
+    pub const DEFAULT_CAPACITY: usize = 1000;
+
+    /// Wrap a DID resolver, with the default time-to-live and capacity.
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            ttl: Duration::seconds(Self::DEFAULT_TTL),
+            error_ttl: Duration::seconds(Self::DEFAULT_ERROR_TTL),
+            capacity: Self::DEFAULT_CAPACITY,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the time-to-live of successful resolution results.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the time-to-live of resolution errors.
+    pub fn with_error_ttl(mut self, error_ttl: Duration) -> Self {
+        self.error_ttl = error_ttl;
+        self
+    }
+
+    /// Set the maximum number of cached resolution results.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Get the wrapped DID resolver.
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+
+    /// Remove the cached resolution result of a DID, if any.
+    pub fn invalidate(&self, did: &str) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.remove(did);
+        }
+    }
+
+    /// Remove all cached resolution results.
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    fn get(&self, did: &str, now: DateTime<Utc>) -> Option<ResolveOutput> {
+        let cache = self.cache.lock().ok()?;
+        match cache.get(did) {
+            Some(cached) if cached.expires > now => Some(cached.result.clone()),
+            _ => None,
+        }
+    }
+
+    fn insert(&self, did: &str, result: &ResolveOutput, now: DateTime<Utc>) {
+        let ttl = match result.0.error {
+            None => self.ttl,
+            Some(_) => self.error_ttl,
+        };
+        let mut cache = match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(_) => return,
+        };
+        if ttl <= Duration::zero() || self.capacity == 0 {
+            cache.remove(did);
+            return;
+        }
+        if cache.len() >= self.capacity && !cache.contains_key(did) {
+            cache.retain(|_, cached| cached.expires > now);
+            if cache.len() >= self.capacity {
+                let first_expiring = cache
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.expires)
+                    .map(|(did, _)| did.clone());
+                if let Some(first_expiring) = first_expiring {
+                    cache.remove(&first_expiring);
+                }
+            }
+        }
+        cache.insert(
+            did.to_string(),
+            CachedResolution {
+                result: result.clone(),
+                expires: now + ttl,
+            },
+        );
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<R: DIDResolver> DIDResolver for CachingResolver<R> {
+    async fn resolve(
+        &self,
+        did: &str,
+        input_metadata: &ResolutionInputMetadata,
+    ) -> (
+        ResolutionMetadata,
+        Option<Document>,
+        Option<DocumentMetadata>,
+    ) {
+        if input_metadata.version_id.is_some() || input_metadata.version_time.is_some() {
+            return self.resolver.resolve(did, input_metadata).await;
+        }
+        let now = Utc::now();
+        if input_metadata.no_cache != Some(true) {
+            if let Some(result) = self.get(did, now) {
+                return result;
+            }
+        }
+        let result = self.resolver.resolve(did, input_metadata).await;
+        self.insert(did, &result, now);
+        result
+    }
+
+    async fn resolve_representation(
+        &self,
+        did: &str,
+        input_metadata: &ResolutionInputMetadata,
+    ) -> (ResolutionMetadata, Vec<u8>, Option<DocumentMetadata>) {
+        self.resolver
+            .resolve_representation(did, input_metadata)
+            .await
+    }
+
+    async fn dereference(
+        &self,
+        primary_did_url: &PrimaryDIDURL,
+        input_metadata: &DereferencingInputMetadata,
+    ) -> Option<(DereferencingMetadata, Content, ContentMetadata)> {
+        self.resolver
+            .dereference(primary_did_url, input_metadata)
+            .await
+    }
+
+    fn to_did_method(&self) -> Option<&dyn DIDMethod> {
+        self.resolver.to_did_method()
+    }
+}
+
 // TODO: replace with Try trait implementation once stabilized.
 // <https://github.com/rust-lang/rust/issues/84277>
 pub(crate) async fn easy_resolve(did: &str, resolver: &dyn DIDResolver) -> Result<Document, Error> {
@@ -1545,6 +1726,83 @@ mod tests {
         assert_eq!(doc.id, EXAMPLE_123_ID);
     }
 
+    /// Resolver counting its resolutions
+    #[derive(Default)]
+    struct CountingResolver {
+        count: std::sync::atomic::AtomicUsize,
+    }
+
+    impl CountingResolver {
+        fn count(&self) -> usize {
+            self.count.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl DIDResolver for CountingResolver {
+        async fn resolve(
+            &self,
+            did: &str,
+            input_metadata: &ResolutionInputMetadata,
+        ) -> (
+            ResolutionMetadata,
+            Option<Document>,
+            Option<DocumentMetadata>,
+        ) {
+            self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            ExampleResolver {}.resolve(did, input_metadata).await
+        }
+    }
+
+    #[async_std::test]
+    async fn caching_resolver() {
+        let input_metadata = ResolutionInputMetadata::default();
+        let resolver = CachingResolver::new(CountingResolver::default());
+        let (res_meta, doc, _) = resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        assert_eq!(res_meta.error, None);
+        assert_eq!(doc.unwrap().id, EXAMPLE_123_ID);
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        assert_eq!(resolver.inner().count(), 1);
+
+        // Errors are cached too.
+        let (res_meta, _, _) = resolver.resolve("did:example:xyz", &input_metadata).await;
+        assert_eq!(res_meta.error, Some(ERROR_NOT_FOUND.to_string()));
+        resolver.resolve("did:example:xyz", &input_metadata).await;
+        assert_eq!(resolver.inner().count(), 2);
+
+        // Versioned and no-cache resolutions bypass the cache.
+        let versioned = ResolutionInputMetadata {
+            version_id: Some("1".to_string()),
+            ..Default::default()
+        };
+        resolver.resolve(EXAMPLE_123_ID, &versioned).await;
+        let no_cache = ResolutionInputMetadata {
+            no_cache: Some(true),
+            ..Default::default()
+        };
+        resolver.resolve(EXAMPLE_123_ID, &no_cache).await;
+        assert_eq!(resolver.inner().count(), 4);
+        resolver.invalidate(EXAMPLE_123_ID);
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        assert_eq!(resolver.inner().count(), 5);
+
+        // Errors expire separately.
+        let resolver =
+            CachingResolver::new(CountingResolver::default()).with_error_ttl(Duration::zero());
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        resolver.resolve("did:example:xyz", &input_metadata).await;
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        resolver.resolve("did:example:xyz", &input_metadata).await;
+        assert_eq!(resolver.inner().count(), 3);
+
+        // The cache is bounded.
+        let resolver = CachingResolver::new(CountingResolver::default()).with_capacity(1);
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        resolver.resolve("did:example:xyz", &input_metadata).await;
+        resolver.resolve(EXAMPLE_123_ID, &input_metadata).await;
+        assert_eq!(resolver.inner().count(), 3);
+    }
+
     #[async_std::test]
     async fn resolve_representation() {
         let resolver = ExampleResolver {};
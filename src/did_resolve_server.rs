@@ -0,0 +1,321 @@
+//! # DID Resolution HTTP(S) Binding server
+//!
+//! Server side of the [DID Resolution HTTP(S)
+//! Binding](https://w3c-ccg.github.io/did-resolution/#bindings-https), serving
+//! `/1.0/identifiers/{did}` using any [`DIDResolver`], e.g. a
+//! [`DIDMethods`](crate::did::DIDMethods). The client side is
+//! [`HTTPDIDResolver`](crate::did_resolve::HTTPDIDResolver).
+//!
+//! DIDs are [resolved][DIDResolver::resolve], returning a DID Resolution Result by default, or a
+//! DID document [representation][DIDResolver::resolve_representation] if the `Accept` header
+//! requests another media type. DID URLs are [dereferenced][dereference]. The query string of
+//! the request is used as resolution input metadata.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use http::header::{ACCEPT, CONTENT_TYPE, LOCATION};
+use http::{Method, Request, Response, StatusCode};
+use serde_json::json;
+
+use crate::did_resolve::{
+    dereference, Content, DIDResolver, DereferencingInputMetadata, DocumentMetadata,
+    ResolutionInputMetadata, ResolutionMetadata, ResolutionResult, ERROR_INVALID_DID,
+    ERROR_INVALID_DID_URL, ERROR_METHOD_NOT_SUPPORTED, ERROR_NOT_FOUND,
+    ERROR_REPRESENTATION_NOT_SUPPORTED, ERROR_UNAUTHORIZED, TYPE_DID_LD_JSON, TYPE_DID_RESOLUTION,
+    TYPE_JSON, TYPE_LD_JSON,
+};
+
+/// Path under which DIDs and DID URLs are served
+pub const PATH_PREFIX: &str = "/1.0/identifiers/";
+
+/// HTTP status code for a DID resolution or DID URL dereferencing error.
+pub fn error_status(error: &str) -> StatusCode {
+    match error {
+        ERROR_INVALID_DID | ERROR_INVALID_DID_URL => StatusCode::BAD_REQUEST,
+        ERROR_UNAUTHORIZED => StatusCode::UNAUTHORIZED,
+        ERROR_NOT_FOUND => StatusCode::NOT_FOUND,
+        ERROR_REPRESENTATION_NOT_SUPPORTED => StatusCode::NOT_ACCEPTABLE,
+        ERROR_METHOD_NOT_SUPPORTED => StatusCode::NOT_IMPLEMENTED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn response(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Vec<u8>> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    if let Ok(content_type) = content_type.parse() {
+        response.headers_mut().insert(CONTENT_TYPE, content_type);
+    }
+    response
+}
+
+fn json_response(
+    status: StatusCode,
+    content_type: &str,
+    value: &impl serde::Serialize,
+) -> Response<Vec<u8>> {
+    match serde_json::to_vec_pretty(value) {
+        Ok(body) => response(status, content_type, body),
+        Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string()),
+    }
+}
+
+fn error_response(status: StatusCode, error: &str) -> Response<Vec<u8>> {
+    let result = ResolutionResult {
+        did_resolution_metadata: Some(ResolutionMetadata::from_error(error)),
+        ..Default::default()
+    };
+    json_response(status, TYPE_DID_RESOLUTION, &result)
+}
+
+/// Whether the `Accept` header asks for a DID Resolution Result rather than a representation.
+fn accepts_resolution_result(accept: Option<&str>) -> bool {
+    match accept {
+        None => true,
+        Some(accept) => accept.split(',').any(|media_type| {
+            let media_type = media_type.trim();
+            media_type.starts_with("*/*") || media_type.contains("https://w3id.org/did-resolution")
+        }),
+    }
+}
+
+fn status_for(res_meta: &ResolutionMetadata, doc_meta: Option<&DocumentMetadata>) -> StatusCode {
+    match res_meta.error {
+        Some(ref error) => error_status(error),
+        None if doc_meta.and_then(|meta| meta.deactivated) == Some(true) => StatusCode::GONE,
+        None => StatusCode::OK,
+    }
+}
+
+async fn resolve(
+    resolver: &dyn DIDResolver,
+    did: &str,
+    mut input_metadata: ResolutionInputMetadata,
+    accept: Option<&str>,
+) -> Response<Vec<u8>> {
+    if accepts_resolution_result(accept) {
+        let (res_meta, doc, doc_meta) = resolver.resolve(did, &input_metadata).await;
+        let status = status_for(&res_meta, doc_meta.as_ref());
+        let result = ResolutionResult {
+            did_document: doc,
+            did_resolution_metadata: Some(res_meta),
+            did_document_metadata: doc_meta,
+            ..Default::default()
+        };
+        return json_response(status, TYPE_DID_RESOLUTION, &result);
+    }
+    if input_metadata.accept.is_none() {
+        input_metadata.accept = accept
+            .and_then(|accept| accept.split(',').next())
+            .map(|media_type| media_type.trim().to_string());
+    }
+    let (res_meta, representation, doc_meta) =
+        resolver.resolve_representation(did, &input_metadata).await;
+    if let Some(ref error) = res_meta.error {
+        return error_response(error_status(error), error);
+    }
+    let content_type = res_meta.content_type.as_deref().unwrap_or(TYPE_DID_LD_JSON);
+    response(
+        status_for(&res_meta, doc_meta.as_ref()),
+        content_type,
+        representation,
+    )
+}
+
+async fn dereference_url(
+    resolver: &dyn DIDResolver,
+    did_url: &str,
+    input_metadata: ResolutionInputMetadata,
+    accept: Option<&str>,
+) -> Response<Vec<u8>> {
+    let input_metadata = DereferencingInputMetadata {
+        accept: input_metadata
+            .accept
+            .or_else(|| accept.map(|accept| accept.to_string())),
+        service_type: None,
+        follow_redirect: None,
+        property_set: input_metadata.property_set,
+    };
+    let (deref_meta, content, _) = dereference(resolver, did_url, &input_metadata).await;
+    if let Some(ref error) = deref_meta.error {
+        let status = error_status(error);
+        return json_response(
+            status,
+            TYPE_JSON,
+            &json!({ "dereferencingMetadata": deref_meta }),
+        );
+    }
+    let content_type = deref_meta.content_type.as_deref();
+    match content {
+        Content::URL(url) => {
+            let mut response = response(
+                StatusCode::SEE_OTHER,
+                "text/uri-list",
+                url.clone().into_bytes(),
+            );
+            if let Ok(location) = url.parse() {
+                response.headers_mut().insert(LOCATION, location);
+            }
+            response
+        }
+        Content::DIDDocument(doc) => json_response(
+            StatusCode::OK,
+            content_type.unwrap_or(TYPE_DID_LD_JSON),
+            &doc,
+        ),
+        Content::Object(object) => json_response(
+            StatusCode::OK,
+            content_type.unwrap_or(TYPE_LD_JSON),
+            &object,
+        ),
+        Content::Data(data) => response(
+            StatusCode::OK,
+            content_type.unwrap_or("application/octet-stream"),
+            data,
+        ),
+        Content::Null => error_response(StatusCode::NOT_FOUND, ERROR_NOT_FOUND),
+    }
+}
+
+/// Handle a request of the DID Resolution HTTP(S) Binding using a DID resolver.
+pub async fn handle<B>(resolver: &dyn DIDResolver, request: &Request<B>) -> Response<Vec<u8>> {
+    if request.method() != Method::GET {
+        return response(StatusCode::METHOD_NOT_ALLOWED, "text/plain", Vec::new());
+    }
+    let identifier = match request.uri().path().strip_prefix(PATH_PREFIX) {
+        Some(identifier) if !identifier.is_empty() => identifier,
+        _ => return error_response(StatusCode::NOT_FOUND, ERROR_NOT_FOUND),
+    };
+    let identifier = match percent_encoding::percent_decode_str(identifier).decode_utf8() {
+        Ok(identifier) => identifier,
+        Err(_) => return error_response(StatusCode::BAD_REQUEST, ERROR_INVALID_DID),
+    };
+    let input_metadata: ResolutionInputMetadata =
+        match serde_urlencoded::from_str(request.uri().query().unwrap_or("")) {
+            Ok(input_metadata) => input_metadata,
+            Err(err) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("Unable to parse resolution input metadata: {}", err),
+                )
+            }
+        };
+    let accept = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|accept| accept.to_str().ok());
+    if identifier.contains(['/', '?', '#']) {
+        dereference_url(resolver, &identifier, input_metadata, accept).await
+    } else {
+        resolve(resolver, &identifier, input_metadata, accept).await
+    }
+}
+
+/// Bind a [hyper] server to an address, serving the DID Resolution HTTP(S) Binding using a DID
+/// resolver.
+///
+/// Returns the bound address, e.g. to find the port when binding to port 0, and the server
+/// future, which must be run in a Tokio runtime.
+pub fn bind<R: DIDResolver + Send + 'static>(
+    resolver: R,
+    addr: &SocketAddr,
+) -> Result<(SocketAddr, impl Future<Output = Result<(), hyper::Error>>), hyper::Error> {
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Server};
+    let resolver = Arc::new(resolver);
+    let make_svc = make_service_fn(move |_| {
+        let resolver = resolver.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                let resolver = resolver.clone();
+                async move {
+                    let response = handle(&*resolver, &request).await;
+                    Ok::<_, Infallible>(response.map(Body::from))
+                }
+            }))
+        }
+    });
+    let server = Server::try_bind(addr)?.serve(make_svc);
+    Ok((server.local_addr(), server))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+
+    async fn get(uri: &str, accept: Option<&str>) -> Response<Vec<u8>> {
+        let mut request = Request::get(uri);
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        handle(&DIDExample, &request.body(()).unwrap()).await
+    }
+
+    #[async_std::test]
+    async fn resolve_did() {
+        let response = get("/1.0/identifiers/did:example:foo", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], TYPE_DID_RESOLUTION);
+        let result: ResolutionResult = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(result.did_document.unwrap().id, "did:example:foo");
+
+        let response = get("/1.0/identifiers/did:example:foo", Some(TYPE_DID_LD_JSON)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], TYPE_DID_LD_JSON);
+        let doc: crate::did::Document = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(doc.id, "did:example:foo");
+
+        let response = get("/1.0/identifiers/did:example:missing", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let request = Request::post("/1.0/identifiers/did:example:foo")
+            .body(())
+            .unwrap();
+        let response = handle(&DIDExample, &request).await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        let response = get("/1.0/other/did:example:foo", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[async_std::test]
+    async fn dereference_did_url() {
+        let response = get("/1.0/identifiers/did:example:foo%23key1", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let vm: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(vm["id"], "did:example:foo#key1");
+
+        let response = get(
+            "/1.0/identifiers/did:example:12345%3Fservice%3Dstatus%26relativeRef%3D%252F3",
+            None,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers()[LOCATION],
+            "https://example.com/credentials/status/3"
+        );
+
+        let response = get("/1.0/identifiers/did:example:missing%23key1", None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn serve() {
+        let addr = ([127, 0, 0, 1], 0).into();
+        let (addr, server) = bind(DIDExample, &addr).unwrap();
+        let server = tokio::task::spawn(server);
+        let resp = reqwest::Client::new()
+            .get(format!("http://{}{}did:example:foo", addr, PATH_PREFIX))
+            .header(ACCEPT.as_str(), TYPE_DID_RESOLUTION)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+        let result: ResolutionResult = resp.json().await.unwrap();
+        assert_eq!(result.did_document.unwrap().id, "did:example:foo");
+        server.abort();
+    }
+}
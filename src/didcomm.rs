@@ -0,0 +1,843 @@
+//! [DIDComm Messaging v2][didcomm] message packing and unpacking
+//!
+//! A plaintext [`Message`] is packed into a signed envelope ([`pack_signed`], a JWS in the
+//! general JSON serialization) or an encrypted envelope ([`pack_encrypted`], a [JWE](crate::jwe)
+//! to the `keyAgreement` keys of the recipient). Encryption is anonymous (anoncrypt,
+//! `ECDH-ES+A256KW`) or authenticated with a key agreement key of the sender (authcrypt,
+//! `ECDH-1PU+A256KW`), and may wrap a signed message. If the recipient has a
+//! `DIDCommMessaging` service with routing keys, the encrypted message is wrapped in
+//! [forward](FORWARD_TYPE) messages for the mediators.
+//!
+//! [`unpack`] decrypts and verifies the envelopes, checking that the keys used to sign or
+//! authcrypt the message belong to the DID in its `from` property.
+//!
+//! [didcomm]: https://identity.foundation/didcomm-messaging/spec/v2.0/
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::did::{ServiceEndpoint, VerificationRelationship, DIDURL};
+use crate::did_resolve::{easy_resolve, get_verification_methods, DIDResolver};
+use crate::error::Error;
+use crate::jwe::{
+    curve_opt, resolve_key_agreement_keys, ContentEncryptionAlgorithm, Header as JWEHeader,
+    JWEError, KeyManagementAlgorithm, RecipientKey, JWE,
+};
+use crate::jwk::{Base64urlUInt, JWK};
+use crate::jws::{GeneralJWS, Header as JWSHeader};
+use crate::signer::Signer;
+
+/// Media type of a plaintext message.
+pub const DIDCOMM_PLAIN: &str = "application/didcomm-plain+json";
+/// Media type of a signed message.
+pub const DIDCOMM_SIGNED: &str = "application/didcomm-signed+json";
+/// Media type of an encrypted message.
+pub const DIDCOMM_ENCRYPTED: &str = "application/didcomm-encrypted+json";
+
+/// Message type of a [Routing Protocol 2.0](https://identity.foundation/didcomm-messaging/spec/v2.0/#routing-protocol-20)
+/// forward message.
+pub const FORWARD_TYPE: &str = "https://didcomm.org/routing/2.0/forward";
+
+/// Type of the DID document service for DIDComm messaging.
+pub const DIDCOMM_MESSAGING_SERVICE: &str = "DIDCommMessaging";
+
+/// Maximum number of envelopes to unpack in one message, e.g. an anoncrypt envelope around an
+/// authcrypt envelope around a signed message.
+const MAX_ENVELOPES: usize = 3;
+
+/// Error from packing or unpacking a DIDComm message.
+#[derive(Error, Debug)]
+pub enum DIDCommError {
+    #[error(transparent)]
+    JWE(#[from] JWEError),
+    #[error(transparent)]
+    JSON(#[from] serde_json::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    Key(#[from] Error),
+    #[error("Unrecognized message envelope")]
+    UnknownEnvelope,
+    #[error("Too many nested envelopes")]
+    EnvelopeLimit,
+    #[error("Message has no sender")]
+    MissingSender,
+    #[error("Key does not belong to the message sender: {0}")]
+    SenderMismatch(String),
+    #[error("Key does not belong to a message recipient: {0}")]
+    RecipientMismatch(String),
+    #[error("Signing key is not an authentication key of the sender: {0}")]
+    InvalidSigningKey(String),
+    #[error("Missing signature algorithm")]
+    MissingAlgorithm,
+    #[error("Missing key ID")]
+    MissingKeyId,
+    #[error("Signed message must have exactly one signature")]
+    SignatureCount,
+    #[error("No key available to decrypt the message")]
+    MissingRecipientKey,
+    #[error("No key agreement key of the recipient uses the curve of the sender key")]
+    NoCompatibleKey,
+    #[error("Message expired")]
+    Expired,
+}
+
+/// Plaintext DIDComm message.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Message {
+    pub id: String,
+    /// Media type, `application/didcomm-plain+json`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Message type URI.
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<String>>,
+    /// Thread ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thid: Option<String>,
+    /// Parent thread ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pthid: Option<String>,
+    /// Creation time, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<i64>,
+    /// Expiration time, in seconds since the Unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_time: Option<i64>,
+    pub body: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
+    #[serde(flatten)]
+    pub property_set: Map<String, Value>,
+}
+
+/// Message attachment.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Attachment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    pub data: AttachmentData,
+    #[serde(flatten)]
+    pub property_set: Map<String, Value>,
+}
+
+/// Data of an attachment, embedded as JSON or base64url, or linked.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AttachmentData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base64: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jws: Option<Value>,
+}
+
+impl Message {
+    pub fn new(id: &str, type_: &str, body: Value) -> Self {
+        Self {
+            id: id.to_string(),
+            typ: Some(DIDCOMM_PLAIN.to_string()),
+            type_: type_.to_string(),
+            body,
+            ..Default::default()
+        }
+    }
+
+    /// Create a forward message, to send a packed message to a mediator for the next hop.
+    pub fn forward(id: &str, next: &str, to: &str, message: Value) -> Self {
+        Self {
+            to: Some(vec![to.to_string()]),
+            attachments: Some(vec![Attachment {
+                data: AttachmentData {
+                    json: Some(message),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }]),
+            ..Self::new(id, FORWARD_TYPE, serde_json::json!({ "next": next }))
+        }
+    }
+
+    /// If this is a forward message, get the next hop and the forwarded message.
+    pub fn as_forward(&self) -> Option<(&str, &Value)> {
+        if self.type_ != FORWARD_TYPE {
+            return None;
+        }
+        let next = self.body.get("next")?.as_str()?;
+        let message = self.attachments.as_ref()?.first()?.data.json.as_ref()?;
+        Some((next, message))
+    }
+}
+
+/// `DIDCommMessaging` service of a DID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessagingService {
+    pub id: String,
+    pub uri: String,
+    pub accept: Option<Vec<String>>,
+    /// Key IDs of the mediators, in the order that the message passes through them.
+    pub routing_keys: Vec<String>,
+}
+
+/// Options for [packing an encrypted message](pack_encrypted).
+#[derive(Default)]
+pub struct PackOptions<'a> {
+    /// Key agreement key ID and private key of the sender, to authcrypt the message. If not
+    /// set, the message is anoncrypted.
+    pub sender: Option<(&'a str, &'a JWK)>,
+    /// Verification method ID and signer, to sign the message before encrypting it.
+    pub signer: Option<(&'a str, &'a dyn Signer)>,
+    /// Content encryption algorithm. Defaults to `XC20P`.
+    pub encryption_algorithm: Option<ContentEncryptionAlgorithm>,
+    /// Wrap the message in forward messages for the routing keys of the recipient's
+    /// `DIDCommMessaging` service.
+    pub forward: bool,
+}
+
+/// Packed message, with the messaging service to send it to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackedMessage {
+    pub message: String,
+    /// Service the message is to be sent to, if the recipient has one.
+    pub service: Option<MessagingService>,
+}
+
+/// Information about the envelopes of an unpacked message.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UnpackMetadata {
+    pub encrypted: bool,
+    /// The message was encrypted with authcrypt.
+    pub authenticated: bool,
+    /// The message was signed.
+    pub non_repudiation: bool,
+    /// Key ID of the recipient key used to decrypt the message.
+    pub encrypted_to_kid: Option<String>,
+    /// Key ID of the sender key used to authcrypt the message.
+    pub encrypted_from_kid: Option<String>,
+    /// Verification method ID of the key used to sign the message.
+    pub sign_from: Option<String>,
+}
+
+/// Unpacked message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnpackedMessage {
+    pub message: Message,
+    pub metadata: UnpackMetadata,
+}
+
+fn did_of(did_url: &str) -> &str {
+    did_url.split('#').next().unwrap_or(did_url)
+}
+
+/// Pack a message in a signed envelope, signed with the given verification method, which must be
+/// an `authentication` key of the `from` DID.
+pub async fn pack_signed(
+    message: &Message,
+    verification_method: &str,
+    signer: &dyn Signer,
+) -> Result<String, DIDCommError> {
+    let from = message.from.as_deref().ok_or(DIDCommError::MissingSender)?;
+    if did_of(verification_method) != did_of(from) {
+        return Err(DIDCommError::SenderMismatch(
+            verification_method.to_string(),
+        ));
+    }
+    let algorithm = signer.algorithm().ok_or(DIDCommError::MissingAlgorithm)?;
+    let protected = JWSHeader {
+        algorithm,
+        type_: Some(DIDCOMM_SIGNED.to_string()),
+        ..Default::default()
+    };
+    let mut unprotected = Map::new();
+    unprotected.insert(
+        "kid".to_string(),
+        Value::String(verification_method.to_string()),
+    );
+    let mut signed = GeneralJWS::new(&serde_json::to_vec(message)?);
+    signed
+        .sign_with_signer(&protected, Some(unprotected), signer)
+        .await?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Pack a message in an encrypted envelope for a recipient DID, or a key agreement key of the
+/// recipient identified by a DID URL.
+pub async fn pack_encrypted(
+    message: &Message,
+    to: &str,
+    options: &PackOptions<'_>,
+    resolver: &dyn DIDResolver,
+) -> Result<PackedMessage, DIDCommError> {
+    if let Some(ref recipients) = message.to {
+        if !recipients.iter().any(|did| did == did_of(to)) {
+            return Err(DIDCommError::RecipientMismatch(to.to_string()));
+        }
+    }
+    let payload = match options.signer {
+        Some((verification_method, signer)) => {
+            pack_signed(message, verification_method, signer).await?
+        }
+        None => serde_json::to_string(message)?,
+    };
+    let recipient_keys = resolve_key_agreement_keys(to, resolver).await?;
+    let enc = options
+        .encryption_algorithm
+        .unwrap_or(ContentEncryptionAlgorithm::XC20P);
+    if let Some((sender_key_id, _)) = options.sender {
+        let from = message.from.as_deref().ok_or(DIDCommError::MissingSender)?;
+        if did_of(sender_key_id) != did_of(from) {
+            return Err(DIDCommError::SenderMismatch(sender_key_id.to_string()));
+        }
+    }
+    let encrypted = encrypt(payload.as_bytes(), recipient_keys, options.sender, enc)?;
+    let service = messaging_service(did_of(to), resolver).await?;
+    let message = match service {
+        Some(ref service) if options.forward && !service.routing_keys.is_empty() => {
+            wrap_forward(&encrypted, did_of(to), &service.routing_keys, enc, resolver).await?
+        }
+        _ => encrypted,
+    };
+    Ok(PackedMessage { message, service })
+}
+
+/// Encrypt a payload to the recipient keys that use the curve of the sender key, or of the first
+/// recipient key for anoncrypt.
+fn encrypt(
+    payload: &[u8],
+    recipient_keys: Vec<RecipientKey>,
+    sender: Option<(&str, &JWK)>,
+    enc: ContentEncryptionAlgorithm,
+) -> Result<String, DIDCommError> {
+    let curve = match sender {
+        Some((_, sender_key)) => curve_opt(sender_key),
+        None => recipient_keys.first().and_then(|key| curve_opt(&key.jwk)),
+    }
+    .map(|curve| curve.to_string());
+    let recipient_keys: Vec<RecipientKey> = recipient_keys
+        .into_iter()
+        .filter(|key| curve.is_some() && curve_opt(&key.jwk) == curve.as_deref())
+        .collect();
+    if recipient_keys.is_empty() {
+        return Err(DIDCommError::NoCompatibleKey);
+    }
+    // apv is the hash of the sorted recipient key IDs.
+    let mut key_ids: Vec<&str> = recipient_keys
+        .iter()
+        .filter_map(|key| key.key_id.as_deref())
+        .collect();
+    key_ids.sort_unstable();
+    let apv = crate::hash::sha256(key_ids.join(".").as_bytes())?;
+    let algorithm = match sender {
+        Some(_) => KeyManagementAlgorithm::ECDH1PUA256KW,
+        None => KeyManagementAlgorithm::ECDHESA256KW,
+    };
+    let header = JWEHeader {
+        algorithm: Some(algorithm),
+        encryption_algorithm: Some(enc),
+        type_: Some(DIDCOMM_ENCRYPTED.to_string()),
+        sender_key_id: sender.map(|(sender_key_id, _)| sender_key_id.to_string()),
+        agreement_party_v_info: Some(Base64urlUInt(apv.to_vec())),
+        ..Default::default()
+    };
+    let jwe = crate::jwe::encrypt(
+        payload,
+        header,
+        &recipient_keys,
+        sender.map(|(_, sender_key)| sender_key),
+    )?;
+    Ok(serde_json::to_string(&jwe)?)
+}
+
+/// Wrap an encrypted message in anoncrypted forward messages, the outermost one being for the
+/// first routing key.
+async fn wrap_forward(
+    encrypted: &str,
+    to: &str,
+    routing_keys: &[String],
+    enc: ContentEncryptionAlgorithm,
+    resolver: &dyn DIDResolver,
+) -> Result<String, DIDCommError> {
+    let mut message = encrypted.to_string();
+    let mut next = to.to_string();
+    for routing_key in routing_keys.iter().rev() {
+        let mut id = [0u8; 16];
+        rand_old::RngCore::fill_bytes(&mut rand_old::rngs::OsRng {}, &mut id);
+        let forward = Message::forward(
+            &hex::encode(id),
+            &next,
+            did_of(routing_key),
+            serde_json::from_str(&message)?,
+        );
+        let keys = resolve_key_agreement_keys(routing_key, resolver).await?;
+        message = encrypt(&serde_json::to_vec(&forward)?, keys, None, enc)?;
+        next = did_of(routing_key).to_string();
+    }
+    Ok(message)
+}
+
+/// Get the first `DIDCommMessaging` service of a DID.
+///
+/// The service endpoint is a map with `uri`, `accept` and `routingKeys` properties, or a URI
+/// with `routingKeys` and `accept` in the service.
+pub async fn messaging_service(
+    did: &str,
+    resolver: &dyn DIDResolver,
+) -> Result<Option<MessagingService>, DIDCommError> {
+    let document = easy_resolve(did, resolver).await?;
+    for service in document.service.iter().flatten() {
+        if !service
+            .type_
+            .any(|type_| type_ == DIDCOMM_MESSAGING_SERVICE)
+        {
+            continue;
+        }
+        let endpoint = match service.service_endpoint.as_ref().and_then(|ep| ep.first()) {
+            Some(endpoint) => endpoint,
+            None => continue,
+        };
+        let (uri, properties) = match endpoint {
+            ServiceEndpoint::URI(uri) => (
+                uri.to_string(),
+                service
+                    .property_set
+                    .iter()
+                    .flatten()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            ServiceEndpoint::Map(Value::Object(map)) => {
+                match map.get("uri").and_then(Value::as_str) {
+                    Some(uri) => (uri.to_string(), map.clone()),
+                    None => continue,
+                }
+            }
+            ServiceEndpoint::Map(_) => continue,
+        };
+        let strings = |name: &str| -> Option<Vec<String>> {
+            properties.get(name)?.as_array().map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+        };
+        let routing_keys = strings("routingKeys")
+            .unwrap_or_default()
+            .iter()
+            .map(|key| match DIDURL::try_from(key.to_string()) {
+                Ok(_) => key.to_string(),
+                Err(_) => format!("{}{}", did, key),
+            })
+            .collect();
+        return Ok(Some(MessagingService {
+            id: service.id.clone(),
+            uri,
+            accept: strings("accept"),
+            routing_keys,
+        }));
+    }
+    Ok(None)
+}
+
+/// Unpack a message: decrypt it with the private key agreement keys given by key ID, verify its
+/// signature, and check that the keys of the sender belong to the `from` DID.
+pub async fn unpack(
+    message: &str,
+    keys: &HashMap<String, JWK>,
+    resolver: &dyn DIDResolver,
+) -> Result<UnpackedMessage, DIDCommError> {
+    let mut metadata = UnpackMetadata::default();
+    let mut value: Value = serde_json::from_str(message)?;
+    // The innermost plaintext message is unpacked after the envelopes.
+    for _ in 0..=MAX_ENVELOPES {
+        let plaintext = if value.get("ciphertext").is_some() {
+            let jwe: JWE = serde_json::from_value(value)?;
+            decrypt(&jwe, keys, resolver, &mut metadata).await?
+        } else if value.get("signatures").is_some() {
+            let signed: GeneralJWS = serde_json::from_value(value)?;
+            verify(&signed, resolver, &mut metadata).await?
+        } else if value.get("body").is_some() {
+            let message: Message = serde_json::from_value(value)?;
+            check_message(&message, &metadata)?;
+            return Ok(UnpackedMessage { message, metadata });
+        } else {
+            return Err(DIDCommError::UnknownEnvelope);
+        };
+        value = serde_json::from_slice(&plaintext)?;
+    }
+    Err(DIDCommError::EnvelopeLimit)
+}
+
+async fn decrypt(
+    jwe: &JWE,
+    keys: &HashMap<String, JWK>,
+    resolver: &dyn DIDResolver,
+    metadata: &mut UnpackMetadata,
+) -> Result<Vec<u8>, DIDCommError> {
+    let header = jwe.header()?;
+    let sender_key = match header.sender_key_id {
+        Some(ref sender_key_id) => {
+            let sender_keys = resolve_key_agreement_keys(sender_key_id, resolver).await?;
+            sender_keys.into_iter().next().map(|key| key.jwk)
+        }
+        None => None,
+    };
+    let recipient_key_ids: Vec<Option<&str>> = jwe
+        .recipients
+        .iter()
+        .map(|recipient| {
+            recipient
+                .header
+                .as_ref()
+                .and_then(|header| header.key_id.as_deref())
+                .or_else(|| header.key_id.as_deref())
+        })
+        .collect();
+    // Try the keys of the recipients with a key ID, then every key if a recipient has none.
+    let keys_by_id = recipient_key_ids
+        .iter()
+        .flatten()
+        .filter_map(|key_id| Some((*key_id, keys.get(*key_id)?, Some(*key_id))));
+    let other_keys = recipient_key_ids.contains(&None).then(|| {
+        keys.iter()
+            .map(|(key_id, key)| (key_id.as_str(), key, None))
+    });
+    let mut result = Err(DIDCommError::MissingRecipientKey);
+    for (key_id, key, recipient_key_id) in keys_by_id.chain(other_keys.into_iter().flatten()) {
+        match jwe.decrypt(key, recipient_key_id, sender_key.as_ref()) {
+            Ok(plaintext) => {
+                result = Ok((key_id, plaintext));
+                break;
+            }
+            Err(err) => result = Err(err.into()),
+        }
+    }
+    let (key_id, plaintext) = result?;
+    metadata.encrypted = true;
+    // Keep the innermost recipient key, and the sender key of an authcrypt envelope.
+    metadata.encrypted_to_kid = Some(key_id.to_string());
+    if header.algorithm == Some(KeyManagementAlgorithm::ECDH1PUA256KW)
+        || header.algorithm == Some(KeyManagementAlgorithm::ECDH1PU)
+    {
+        metadata.authenticated = true;
+        metadata.encrypted_from_kid = header.sender_key_id;
+    }
+    Ok(plaintext)
+}
+
+async fn verify(
+    signed: &GeneralJWS,
+    resolver: &dyn DIDResolver,
+    metadata: &mut UnpackMetadata,
+) -> Result<Vec<u8>, DIDCommError> {
+    if signed.signatures.len() != 1 {
+        return Err(DIDCommError::SignatureCount);
+    }
+    let header = signed.headers()?.remove(0);
+    let key_id = header.key_id.ok_or(DIDCommError::MissingKeyId)?;
+    let payload = signed.decode_payload()?;
+    let message: Message = serde_json::from_slice(&payload)?;
+    let from = message.from.as_deref().ok_or(DIDCommError::MissingSender)?;
+    if did_of(&key_id) != did_of(from) {
+        return Err(DIDCommError::SenderMismatch(key_id));
+    }
+    let vmms = get_verification_methods(
+        did_of(from),
+        VerificationRelationship::Authentication,
+        resolver,
+    )
+    .await?;
+    let vmm = vmms
+        .get(&key_id)
+        .ok_or_else(|| DIDCommError::InvalidSigningKey(key_id.to_string()))?;
+    let key = vmm.get_jwk()?;
+    signed.verify(&[key]).remove(0).result?;
+    metadata.non_repudiation = true;
+    metadata.sign_from = Some(key_id);
+    Ok(payload)
+}
+
+/// Check the plaintext message against its envelopes.
+fn check_message(message: &Message, metadata: &UnpackMetadata) -> Result<(), DIDCommError> {
+    if let Some(ref sender_key_id) = metadata.encrypted_from_kid {
+        let from = message.from.as_deref().ok_or(DIDCommError::MissingSender)?;
+        if did_of(sender_key_id) != did_of(from) {
+            return Err(DIDCommError::SenderMismatch(sender_key_id.to_string()));
+        }
+    }
+    if let (Some(ref key_id), Some(ref to)) = (&metadata.encrypted_to_kid, &message.to) {
+        if !to.iter().any(|did| did == did_of(key_id)) {
+            return Err(DIDCommError::RecipientMismatch(key_id.to_string()));
+        }
+    }
+    if let Some(expires_time) = message.expires_time {
+        if expires_time < chrono::Utc::now().timestamp() {
+            return Err(DIDCommError::Expired);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::Document;
+    use crate::trust_store::TrustStore;
+    use serde_json::json;
+
+    struct Party {
+        did: String,
+        signing_key: JWK,
+        agreement_key: JWK,
+    }
+
+    impl Party {
+        fn new(did: &str) -> Self {
+            Self {
+                did: did.to_string(),
+                signing_key: JWK::generate_ed25519().unwrap(),
+                agreement_key: JWK::generate_x25519().unwrap(),
+            }
+        }
+
+        fn signing_key_id(&self) -> String {
+            format!("{}#key-1", self.did)
+        }
+
+        fn agreement_key_id(&self) -> String {
+            format!("{}#key-x25519-1", self.did)
+        }
+
+        fn document(&self, service: Option<Value>) -> Document {
+            serde_json::from_value(json!({
+                "@context": [
+                    "https://www.w3.org/ns/did/v1",
+                    "https://w3id.org/security/suites/jws-2020/v1"
+                ],
+                "id": self.did,
+                "verificationMethod": [{
+                    "id": self.signing_key_id(),
+                    "type": "JsonWebKey2020",
+                    "controller": self.did,
+                    "publicKeyJwk": self.signing_key.to_public()
+                }, {
+                    "id": self.agreement_key_id(),
+                    "type": "JsonWebKey2020",
+                    "controller": self.did,
+                    "publicKeyJwk": self.agreement_key.to_public()
+                }],
+                "authentication": [self.signing_key_id()],
+                "keyAgreement": [self.agreement_key_id()],
+                "service": service.into_iter().collect::<Vec<Value>>()
+            }))
+            .unwrap()
+        }
+
+        fn keys(&self) -> HashMap<String, JWK> {
+            let mut keys = HashMap::new();
+            keys.insert(self.agreement_key_id(), self.agreement_key.clone());
+            keys
+        }
+    }
+
+    fn setup() -> (Party, Party, Party, TrustStore) {
+        let alice = Party::new("did:example:alice");
+        let bob = Party::new("did:example:bob");
+        let mediator = Party::new("did:example:mediator");
+        let mut resolver = TrustStore::new();
+        resolver.insert_document(alice.document(None)).unwrap();
+        resolver
+            .insert_document(bob.document(Some(json!({
+                "id": "did:example:bob#didcomm-1",
+                "type": "DIDCommMessaging",
+                "serviceEndpoint": {
+                    "uri": "https://mediator.example.com/didcomm",
+                    "accept": ["didcomm/v2"],
+                    "routingKeys": [mediator.agreement_key_id()]
+                }
+            }))))
+            .unwrap();
+        resolver.insert_document(mediator.document(None)).unwrap();
+        (alice, bob, mediator, resolver)
+    }
+
+    fn message(alice: &Party, bob: &Party) -> Message {
+        Message {
+            from: Some(alice.did.clone()),
+            to: Some(vec![bob.did.clone()]),
+            created_time: Some(1516269022),
+            ..Message::new(
+                "1234567890",
+                "http://example.com/protocols/lets_do_lunch/1.0/proposal",
+                json!({"messagespecificattribute": "and its value"}),
+            )
+        }
+    }
+
+    #[async_std::test]
+    async fn signed() {
+        let (alice, bob, _, resolver) = setup();
+        let message = message(&alice, &bob);
+        let packed = pack_signed(&message, &alice.signing_key_id(), &alice.signing_key)
+            .await
+            .unwrap();
+        let unpacked = unpack(&packed, &HashMap::new(), &resolver).await.unwrap();
+        assert_eq!(unpacked.message, message);
+        assert!(unpacked.metadata.non_repudiation);
+        assert!(!unpacked.metadata.encrypted);
+        assert_eq!(unpacked.metadata.sign_from, Some(alice.signing_key_id()));
+
+        // Key of another DID
+        assert!(
+            pack_signed(&message, &bob.signing_key_id(), &bob.signing_key)
+                .await
+                .is_err()
+        );
+        // Key that is not an authentication key of the sender
+        let packed = pack_signed(&message, &alice.agreement_key_id(), &alice.signing_key)
+            .await
+            .unwrap();
+        assert!(unpack(&packed, &HashMap::new(), &resolver).await.is_err());
+    }
+
+    #[async_std::test]
+    async fn anoncrypt_and_authcrypt() {
+        let (alice, bob, _, resolver) = setup();
+        let message = message(&alice, &bob);
+
+        let packed = pack_encrypted(&message, &bob.did, &PackOptions::default(), &resolver)
+            .await
+            .unwrap();
+        let service = packed.service.unwrap();
+        assert_eq!(service.uri, "https://mediator.example.com/didcomm");
+        let unpacked = unpack(&packed.message, &bob.keys(), &resolver)
+            .await
+            .unwrap();
+        assert_eq!(unpacked.message, message);
+        assert!(unpacked.metadata.encrypted);
+        assert!(!unpacked.metadata.authenticated);
+        assert_eq!(
+            unpacked.metadata.encrypted_to_kid,
+            Some(bob.agreement_key_id())
+        );
+        assert!(unpack(&packed.message, &alice.keys(), &resolver)
+            .await
+            .is_err());
+
+        let sender_key_id = alice.agreement_key_id();
+        let options = PackOptions {
+            sender: Some((&sender_key_id, &alice.agreement_key)),
+            signer: Some((&alice.signing_key_id(), &alice.signing_key)),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            ..Default::default()
+        };
+        let packed = pack_encrypted(&message, &bob.did, &options, &resolver)
+            .await
+            .unwrap();
+        let jwe: JWE = serde_json::from_str(&packed.message).unwrap();
+        let header = jwe.header().unwrap();
+        assert_eq!(header.type_.as_deref(), Some(DIDCOMM_ENCRYPTED));
+        assert_eq!(header.sender_key_id, Some(alice.agreement_key_id()));
+        let unpacked = unpack(&packed.message, &bob.keys(), &resolver)
+            .await
+            .unwrap();
+        assert_eq!(unpacked.message, message);
+        assert!(unpacked.metadata.authenticated);
+        assert!(unpacked.metadata.non_repudiation);
+        assert_eq!(
+            unpacked.metadata.encrypted_from_kid,
+            Some(alice.agreement_key_id())
+        );
+
+        // Anoncrypt envelope around the authcrypt envelope
+        let recipient_keys = resolve_key_agreement_keys(&bob.did, &resolver)
+            .await
+            .unwrap();
+        let anoncrypted = encrypt(
+            packed.message.as_bytes(),
+            recipient_keys,
+            None,
+            ContentEncryptionAlgorithm::XC20P,
+        )
+        .unwrap();
+        let unpacked = unpack(&anoncrypted, &bob.keys(), &resolver).await.unwrap();
+        assert_eq!(unpacked.message, message);
+        assert!(unpacked.metadata.authenticated);
+        assert!(unpacked.metadata.non_repudiation);
+
+        // Recipients without a key ID are tried with every key.
+        let recipient_key = RecipientKey {
+            key_id: None,
+            jwk: bob.agreement_key.to_public(),
+        };
+        let header = JWEHeader {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::XC20P),
+            ..Default::default()
+        };
+        let payload = serde_json::to_vec(&message).unwrap();
+        let jwe = crate::jwe::encrypt(&payload, header, &[recipient_key], None).unwrap();
+        let jwe = serde_json::to_string(&jwe).unwrap();
+        let unpacked = unpack(&jwe, &bob.keys(), &resolver).await.unwrap();
+        assert_eq!(unpacked.message, message);
+        assert_eq!(
+            unpacked.metadata.encrypted_to_kid,
+            Some(bob.agreement_key_id())
+        );
+        assert!(unpack(&jwe, &alice.keys(), &resolver).await.is_err());
+
+        // The sender key must be a key of the from DID.
+        let mut other_message = message.clone();
+        other_message.from = Some(bob.did.clone());
+        let options = PackOptions {
+            sender: Some((&sender_key_id, &alice.agreement_key)),
+            ..Default::default()
+        };
+        assert!(
+            pack_encrypted(&other_message, &bob.did, &options, &resolver)
+                .await
+                .is_err()
+        );
+    }
+
+    #[async_std::test]
+    async fn forward() {
+        let (alice, bob, mediator, resolver) = setup();
+        let message = message(&alice, &bob);
+        let options = PackOptions {
+            forward: true,
+            ..Default::default()
+        };
+        let packed = pack_encrypted(&message, &bob.did, &options, &resolver)
+            .await
+            .unwrap();
+        assert!(unpack(&packed.message, &bob.keys(), &resolver)
+            .await
+            .is_err());
+
+        let forward = unpack(&packed.message, &mediator.keys(), &resolver)
+            .await
+            .unwrap()
+            .message;
+        let (next, forwarded) = forward.as_forward().unwrap();
+        assert_eq!(next, bob.did);
+        let unpacked = unpack(&forwarded.to_string(), &bob.keys(), &resolver)
+            .await
+            .unwrap();
+        assert_eq!(unpacked.message, message);
+    }
+}
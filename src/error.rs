@@ -1,14 +1,20 @@
 //! Error types for `ssi` crate
 #[cfg(feature = "aleosig")]
 use crate::aleo::{AleoGeneratePrivateKeyError, AleoSignError, AleoVerifyError};
+use crate::bbs::BbsError;
 use crate::caip10::BlockchainAccountIdParseError;
 use crate::caip10::BlockchainAccountIdVerifyError;
+use crate::der::KeyFromASN1Error;
 #[cfg(feature = "keccak-hash")]
 use crate::eip712::TypedDataConstructionError;
 #[cfg(feature = "keccak-hash")]
 use crate::eip712::TypedDataConstructionJSONError;
 #[cfg(feature = "keccak-hash")]
 use crate::eip712::TypedDataHashError;
+use crate::json_schema::JsonSchemaError;
+use crate::jwk::RsaX509PubParseError;
+use crate::presentation_exchange::PresentationExchangeError;
+use crate::sd_jwt::SdJwtError;
 use crate::tzkey::{DecodeTezosSignatureError, EncodeTezosSignedMessageError};
 use base64::DecodeError as Base64Error;
 #[cfg(feature = "ed25519-dalek")]
@@ -24,6 +30,7 @@ use multibase::Error as MultibaseError;
 use openssl::error::ErrorStack as OpenSSLErrors;
 #[cfg(feature = "p256")]
 use p256::ecdsa::Error as Secp256r1Error;
+use pem::PemError;
 #[cfg(feature = "ring")]
 use ring::error::KeyRejected as KeyRejectedError;
 #[cfg(feature = "ring")]
@@ -58,6 +65,10 @@ pub enum Error {
     AlgorithmNotImplemented,
     /// Linked Data Proof type not implemented
     ProofTypeNotImplemented,
+    /// Data Integrity cryptosuite not implemented
+    CryptosuiteNotImplemented(String),
+    /// Missing cryptosuite in Data Integrity proof
+    MissingCryptosuite,
     /// Missing algorithm in JWT
     MissingAlgorithm,
     /// Missing curve in JWK
@@ -86,6 +97,10 @@ pub enum Error {
     AlgorithmMismatch,
     /// Verification method id does not match JWK id
     KeyIdVMMismatch(String, String),
+    /// Verification method controller does not match the DID of its id
+    ControllerVMMismatch(String, String),
+    /// Verification method id is not of the DID of its document
+    DocumentVMMismatch(String, String),
     /// RDF statement object does not match value
     ObjectMismatch(String, String, String),
     /// Missing RDF statement object
@@ -144,6 +159,12 @@ pub enum Error {
     MissingProof,
     /// Missing issuance date
     MissingIssuanceDate,
+    /// Credential validFrom is later than validUntil
+    InvalidValidityPeriod,
+    /// Credential is not yet valid
+    CredentialNotYetValid,
+    /// Credential has expired
+    ExpiredCredential,
     /// Credential subject must be non-empty
     ///
     /// [Verifiable credential subject](crate::vc::CredentialSubject) arity must be positive
@@ -234,6 +255,8 @@ pub enum Error {
     InvalidProofTypeType,
     /// Invalid key length
     InvalidKeyLength(usize),
+    /// Key agreement produced an all-zero shared secret
+    NonContributoryKeyAgreement,
     /// Inconsistent DID Key
     InconsistentDIDKey,
     /// Crypto error from `ring` crate
@@ -311,6 +334,8 @@ pub enum Error {
     KeyRejected(KeyRejectedError),
     /// Error parsing a UTF-8 string
     FromUtf8(FromUtf8Error),
+    /// Error reading or writing a file
+    IO(std::io::Error),
     /// Error from `rsa` crate
     #[cfg(feature = "rsa")]
     Rsa(RsaError),
@@ -327,6 +352,12 @@ pub enum Error {
     OpenSSL(OpenSSLErrors),
     /// Error encoding ASN.1 data structure.
     ASN1Encode(ASN1EncodeError),
+    /// Error decoding ASN.1 key structure
+    KeyFromASN1(KeyFromASN1Error),
+    /// Error parsing RSA public key
+    RsaX509PubParse(RsaX509PubParseError),
+    /// Error parsing PEM
+    PEM(PemError),
     /// Error decoding Base64
     Base64(Base64Error),
     /// Error parsing or producing multibase
@@ -364,6 +395,14 @@ pub enum Error {
     BlockchainAccountIdParse(BlockchainAccountIdParseError),
     /// Error verifying CAIP-10 blockchain account id against a public key
     BlockchainAccountIdVerify(BlockchainAccountIdVerifyError),
+    /// Error from BBS+ signing or proof operations
+    Bbs(BbsError),
+    /// Error loading a JSON Schema or validating a value against it
+    JsonSchema(JsonSchemaError),
+    /// Error evaluating a Presentation Exchange presentation definition
+    PresentationExchange(PresentationExchangeError),
+    /// Error issuing, presenting or verifying an SD-JWT
+    SdJwt(SdJwtError),
     /// Error constructing EIP-712 TypedData from a linked data document using JSON-LD/RDF
     #[cfg(feature = "keccak-hash")]
     TypedDataConstruction(TypedDataConstructionError),
@@ -387,10 +426,24 @@ pub enum Error {
     KeyPrefix,
     /// Unable to resolve DID
     UnableToResolve(String),
+    /// JWK Set URL (`jku`) not trusted
+    UntrustedJWKSetURL(String),
+    /// Unable to fetch JWK Set
+    JWKSetFetch(String),
+    /// Unsupported key algorithm or key encryption algorithm
+    UnsupportedKeyAlgorithm(String),
+    /// Unexpected PEM label
+    UnexpectedPEMLabel(String),
+    /// Unable to decrypt private key
+    KeyDecryption,
+    /// PBKDF2 iteration count limit exceeded
+    PBKDF2IterationLimit(usize),
     /// Expected 64 byte uncompressed key or 33 bytes compressed key
     P256KeyLength(usize),
     /// Expected 96 byte uncompressed key or 49 bytes compressed key (P-384)
     P384KeyLength(usize),
+    /// Expected 132 byte uncompressed key or 67 bytes compressed key (P-521)
+    P521KeyLength(usize),
     /// Unable to encode elliptic curve key
     ECEncodingError,
     /// Unable to decompress elliptic curve
@@ -425,6 +478,9 @@ impl fmt::Display for Error {
             Error::MissingProof => write!(f, "Missing proof property"),
             Error::EmptyCredentialSubject => write!(f, "Credential subject must be non-empty"),
             Error::MissingIssuanceDate => write!(f, "Missing issuance date"),
+            Error::InvalidValidityPeriod => write!(f, "Credential validFrom is later than validUntil"),
+            Error::CredentialNotYetValid => write!(f, "Credential is not yet valid"),
+            Error::ExpiredCredential => write!(f, "Credential has expired"),
             Error::MissingTypeVerifiableCredential => {
                 write!(f, "Missing type VerifiableCredential")
             }
@@ -444,6 +500,8 @@ impl fmt::Display for Error {
             Error::NotImplemented => write!(f, "Not implemented"),
             Error::AlgorithmNotImplemented => write!(f, "JWA algorithm not implemented"),
             Error::ProofTypeNotImplemented => write!(f, "Linked Data Proof type not implemented"),
+            Error::CryptosuiteNotImplemented(name) => write!(f, "Data Integrity cryptosuite not implemented: {}", name),
+            Error::MissingCryptosuite => write!(f, "Missing cryptosuite in Data Integrity proof"),
             Error::MissingAlgorithm => write!(f, "Missing algorithm in JWT"),
             Error::MissingCurve => write!(f, "Missing curve in JWK"),
             Error::MissingPoint => write!(f, "Missing elliptic curve point in JWK"),
@@ -458,6 +516,8 @@ impl fmt::Display for Error {
             Error::ExpectedLang => write!(f, "Expected RDF language tag"),
             Error::AlgorithmMismatch => write!(f, "Algorithm in JWS header does not match JWK"),
             Error::KeyIdVMMismatch(vm, kid) => write!(f, "Verification method id does not match JWK id. VM id: {}, JWK key id: {}", vm, kid),
+            Error::ControllerVMMismatch(controller, did) => write!(f, "Verification method controller does not match its DID. Controller: {}, DID: {}", controller, did),
+            Error::DocumentVMMismatch(id, did) => write!(f, "Verification method is not of the DID document. VM id: {}, DID: {}", id, did),
             Error::ObjectMismatch(predicate, expected, actual) => write!(f, "RDF statement object does not match value. Predicate: {}. Expected: {}. Actual: {}", predicate, expected, actual),
             Error::ExpectedObjectForPredicate(predicate, expected) => write!(f, "Missing RDF statement object. Predicate: {}. Expected value: {}", predicate, expected),
             Error::UnexpectedObjectForPredicate(predicate, value) => write!(f, "Unexpected RDF statement object. Predicate: {}. Value: {}", predicate, value),
@@ -505,6 +565,7 @@ impl fmt::Display for Error {
             Error::ResourceNotFound(id) => write!(f, "Resource not found: {}", id),
             Error::InvalidProofTypeType => write!(f, "Invalid ProofType type"),
             Error::InvalidKeyLength(len) => write!(f, "Invalid key length: {}", len),
+            Error::NonContributoryKeyAgreement => write!(f, "Key agreement produced an all-zero shared secret"),
             Error::InconsistentDIDKey => write!(f, "Inconsistent DID Key"),
             Error::DIDURL => write!(f, "Invalid DID URL"),
             Error::UnexpectedDIDFragment => write!(f, "Unexpected DID fragment"),
@@ -552,7 +613,14 @@ impl fmt::Display for Error {
             Error::SignaturePrefix => write!(f, "Unknown signature prefix"),
             Error::KeyPrefix => write!(f, "Unknown key prefix"),
             Error::UnableToResolve(error) => write!(f, "Unable to resolve: {}", error),
+            Error::UntrustedJWKSetURL(url) => write!(f, "Untrusted JWK Set URL: {}", url),
+            Error::JWKSetFetch(error) => write!(f, "Unable to fetch JWK Set: {}", error),
+            Error::UnsupportedKeyAlgorithm(oid) => write!(f, "Unsupported key algorithm: {}", oid),
+            Error::UnexpectedPEMLabel(label) => write!(f, "Unexpected PEM label: {}", label),
+            Error::KeyDecryption => write!(f, "Unable to decrypt private key"),
+            Error::PBKDF2IterationLimit(iterations) => write!(f, "PBKDF2 iteration count limit exceeded: {}", iterations),
             Error::FromUtf8(e) => e.fmt(f),
+            Error::IO(e) => e.fmt(f),
             Error::TryFromSlice(e) => e.fmt(f),
             #[cfg(feature = "ring")]
             Error::KeyRejected(e) => e.fmt(f),
@@ -569,6 +637,9 @@ impl fmt::Display for Error {
             Error::Base64(e) => e.fmt(f),
             Error::Multibase(e) => e.fmt(f),
             Error::ASN1Encode(e) => e.fmt(f),
+            Error::KeyFromASN1(e) => e.fmt(f),
+            Error::RsaX509PubParse(e) => e.fmt(f),
+            Error::PEM(e) => e.fmt(f),
             Error::JSON(e) => e.fmt(f),
             Error::SerdeJSON(e) => e.fmt(f),
             Error::SerdeUrlEncoded(e) => e.fmt(f),
@@ -578,6 +649,10 @@ impl fmt::Display for Error {
             Error::CharTryFrom(e) => e.fmt(f),
             Error::BlockchainAccountIdParse(e) => e.fmt(f),
             Error::BlockchainAccountIdVerify(e) => e.fmt(f),
+            Error::Bbs(e) => e.fmt(f),
+            Error::JsonSchema(e) => e.fmt(f),
+            Error::PresentationExchange(e) => e.fmt(f),
+            Error::SdJwt(e) => e.fmt(f),
             #[cfg(feature = "aleosig")]
             Error::AleoSign(e) => e.fmt(f),
             #[cfg(feature = "aleosig")]
@@ -598,6 +673,7 @@ impl fmt::Display for Error {
             Error::P256EC(e) => e.fmt(f),
             Error::P256KeyLength(len) => write!(f, "Expected 64 byte uncompressed key or 33 bytes compressed key but found length: {}", len),
             Error::P384KeyLength(len) => write!(f, "Expected 96 byte uncompressed key or 49 bytes compressed key but found length: {}", len),
+            Error::P521KeyLength(len) => write!(f, "Expected 132 byte uncompressed key or 67 bytes compressed key but found length: {}", len),
             Error::ECEncodingError => write!(f, "Unable to encode EC key"),
             Error::ECDecompress => write!(f, "Unable to decompress elliptic curve"),
             Error::MissingFeatures(features) => write!(f, "Missing features: {}", features),
@@ -625,6 +701,24 @@ impl From<ASN1EncodeError> for Error {
     }
 }
 
+impl From<KeyFromASN1Error> for Error {
+    fn from(err: KeyFromASN1Error) -> Error {
+        Error::KeyFromASN1(err)
+    }
+}
+
+impl From<RsaX509PubParseError> for Error {
+    fn from(err: RsaX509PubParseError) -> Error {
+        Error::RsaX509PubParse(err)
+    }
+}
+
+impl From<PemError> for Error {
+    fn from(err: PemError) -> Error {
+        Error::PEM(err)
+    }
+}
+
 impl From<JSONError> for Error {
     fn from(err: JSONError) -> Error {
         Error::JSON(err)
@@ -681,6 +775,12 @@ impl From<FromUtf8Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::IO(err)
+    }
+}
+
 impl From<ParseIntError> for Error {
     fn from(err: ParseIntError) -> Error {
         Error::ParseInt(err)
@@ -725,6 +825,30 @@ impl From<BlockchainAccountIdVerifyError> for Error {
     }
 }
 
+impl From<BbsError> for Error {
+    fn from(err: BbsError) -> Error {
+        Error::Bbs(err)
+    }
+}
+
+impl From<JsonSchemaError> for Error {
+    fn from(err: JsonSchemaError) -> Error {
+        Error::JsonSchema(err)
+    }
+}
+
+impl From<PresentationExchangeError> for Error {
+    fn from(err: PresentationExchangeError) -> Error {
+        Error::PresentationExchange(err)
+    }
+}
+
+impl From<SdJwtError> for Error {
+    fn from(err: SdJwtError) -> Error {
+        Error::SdJwt(err)
+    }
+}
+
 #[cfg(feature = "aleosig")]
 impl From<AleoSignError> for Error {
     fn from(err: AleoSignError) -> Error {
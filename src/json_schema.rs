@@ -0,0 +1,735 @@
+//! [JSON Schema] validation
+//!
+//! This module validates JSON values against a JSON Schema, as used by [Presentation
+//! Exchange](crate::presentation_exchange) filters and [`credentialSchema`][schema] entries of
+//! credentials, which are loaded with a [`SchemaLoader`]. It supports the validation keywords of
+//! draft-07 and 2020-12 that do not need external resources:
+//!
+//! - `type`, `enum`, `const`;
+//! - `minLength`, `maxLength`, `pattern`, and the `date-time`, `date` and `uri` formats;
+//! - `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `multipleOf`;
+//! - `items`, `prefixItems`, `additionalItems`, `minItems`, `maxItems`, `uniqueItems`,
+//!   `contains`, `minContains`, `maxContains`;
+//! - `properties`, `patternProperties`, `additionalProperties`, `required`, `propertyNames`,
+//!   `minProperties`, `maxProperties`, `dependentRequired`;
+//! - `allOf`, `anyOf`, `oneOf`, `not`, `if`/`then`/`else`;
+//! - `$ref` to JSON pointers within the same schema.
+//!
+//! Unknown keywords and formats are ignored.
+//!
+//! ## Example
+//! ```
+//! use ssi::json_schema;
+//! use serde_json::json;
+//!
+//! let schema = json!({"type": "string", "pattern": "^did:"});
+//! json_schema::validate(&schema, &json!("did:example:foo"))?;
+//! assert!(json_schema::validate(&schema, &json!("urn:example:foo")).is_err());
+//! # Ok::<(), ssi::json_schema::JsonSchemaError>(())
+//! ```
+//!
+//! [JSON Schema]: https://json-schema.org/
+//! [schema]: https://www.w3.org/TR/vc-data-model/#data-schemas
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::error::Error;
+
+/// Error validating a value against a JSON Schema.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum JsonSchemaError {
+    /// The value does not conform to the schema. Contains a JSON pointer to the invalid part of
+    /// the value, and the reason it is invalid.
+    #[error("Invalid value at '{0}': {1}")]
+    Invalid(String, String),
+    /// The schema itself is invalid or unsupported.
+    #[error("Invalid JSON Schema: {0}")]
+    Schema(String),
+    /// The schema loader does not know the schema.
+    #[error("Unknown JSON Schema: {0}")]
+    UnknownSchema(String),
+}
+
+/// Loader of JSON Schemas by ID, e.g. for validating [`credentialSchema`][schema] entries.
+///
+/// [schema]: https://www.w3.org/TR/vc-data-model/#data-schemas
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait SchemaLoader: Send + Sync {
+    /// Load the schema with the given ID.
+    ///
+    /// Returns [`JsonSchemaError::UnknownSchema`] if the loader does not have the schema.
+    async fn load(&self, id: &str) -> Result<Value, Error>;
+}
+
+/// [`SchemaLoader`] of schemas held in memory.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySchemaLoader {
+    schemas: HashMap<String, Value>,
+}
+
+impl MemorySchemaLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a schema, returning the schema previously added with the same ID, if any.
+    pub fn insert(&mut self, id: &str, schema: Value) -> Option<Value> {
+        self.schemas.insert(id.to_string(), schema)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl SchemaLoader for MemorySchemaLoader {
+    async fn load(&self, id: &str) -> Result<Value, Error> {
+        match self.schemas.get(id) {
+            Some(schema) => Ok(schema.clone()),
+            None => Err(JsonSchemaError::UnknownSchema(id.to_string()).into()),
+        }
+    }
+}
+
+/// [`SchemaLoader`] of schemas stored in files, e.g. for offline use. The files are read each
+/// time a schema is loaded.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default)]
+pub struct FileSchemaLoader {
+    paths: HashMap<String, PathBuf>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileSchemaLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the path of the JSON file containing a schema.
+    pub fn insert(&mut self, id: &str, path: impl Into<PathBuf>) -> Option<PathBuf> {
+        self.paths.insert(id.to_string(), path.into())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl SchemaLoader for FileSchemaLoader {
+    async fn load(&self, id: &str) -> Result<Value, Error> {
+        let path = self
+            .paths
+            .get(id)
+            .ok_or_else(|| JsonSchemaError::UnknownSchema(id.to_string()))?
+            .clone();
+        let schema = async_std::task::spawn_blocking(move || std::fs::read_to_string(path)).await?;
+        Ok(serde_json::from_str(&schema)?)
+    }
+}
+
+/// Validate a value against a JSON Schema.
+pub fn validate(schema: &Value, instance: &Value) -> Result<(), JsonSchemaError> {
+    Validator {
+        root: schema,
+        active_refs: RefCell::new(HashSet::new()),
+    }
+    .validate(schema, instance, "")
+}
+
+/// Check whether a value conforms to a JSON Schema.
+///
+/// Errors in the schema itself are returned, rather than treated as the value not conforming.
+pub fn is_valid(schema: &Value, instance: &Value) -> Result<bool, JsonSchemaError> {
+    match validate(schema, instance) {
+        Ok(()) => Ok(true),
+        Err(JsonSchemaError::Invalid(..)) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    /// `$ref`s being followed, with the instance they are applied to, to detect cycles.
+    active_refs: RefCell<HashSet<(String, *const Value)>>,
+}
+
+fn invalid(path: &str, reason: impl Into<String>) -> JsonSchemaError {
+    let path = if path.is_empty() { "/" } else { path };
+    JsonSchemaError::Invalid(path.to_string(), reason.into())
+}
+
+fn schema_error(reason: impl Into<String>) -> JsonSchemaError {
+    JsonSchemaError::Schema(reason.into())
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+fn get_usize(schema: &Map<String, Value>, keyword: &str) -> Result<Option<usize>, JsonSchemaError> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(|n| Some(n as usize))
+            .ok_or_else(|| schema_error(format!("{} must be a non-negative integer", keyword))),
+    }
+}
+
+fn get_number(schema: &Map<String, Value>, keyword: &str) -> Result<Option<f64>, JsonSchemaError> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(value) => value
+            .as_f64()
+            .map(Some)
+            .ok_or_else(|| schema_error(format!("{} must be a number", keyword))),
+    }
+}
+
+fn get_array<'a>(
+    schema: &'a Map<String, Value>,
+    keyword: &str,
+) -> Result<Option<&'a Vec<Value>>, JsonSchemaError> {
+    match schema.get(keyword) {
+        None => Ok(None),
+        Some(Value::Array(items)) => Ok(Some(items)),
+        Some(_) => Err(schema_error(format!("{} must be an array", keyword))),
+    }
+}
+
+fn type_matches(type_: &str, instance: &Value) -> Result<bool, JsonSchemaError> {
+    Ok(match type_ {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => match instance {
+            Value::Number(n) => n.is_i64() || n.is_u64() || n.as_f64().is_some_and(is_integer),
+            _ => false,
+        },
+        _ => return Err(schema_error(format!("Unknown type: {}", type_))),
+    })
+}
+
+fn is_integer(n: f64) -> bool {
+    n.is_finite() && n.fract() == 0.0
+}
+
+fn format_matches(format: &str, value: &str) -> bool {
+    match format {
+        "date-time" => chrono::DateTime::parse_from_rfc3339(value).is_ok(),
+        "date" => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "uri" => iref::Iri::new(value).is_ok(),
+        _ => true,
+    }
+}
+
+impl<'a> Validator<'a> {
+    fn resolve_ref(&self, reference: &str) -> Result<&'a Value, JsonSchemaError> {
+        let pointer = reference
+            .strip_prefix('#')
+            .ok_or_else(|| schema_error(format!("Unsupported $ref: {}", reference)))?;
+        self.root
+            .pointer(pointer)
+            .ok_or_else(|| schema_error(format!("Unresolved $ref: {}", reference)))
+    }
+
+    fn is_valid(&self, schema: &Value, instance: &Value) -> Result<bool, JsonSchemaError> {
+        match self.validate(schema, instance, "") {
+            Ok(()) => Ok(true),
+            Err(JsonSchemaError::Invalid(..)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn validate(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => return Err(invalid(path, "no value is allowed")),
+            Value::Object(schema) => schema,
+            _ => return Err(schema_error("schema must be an object or a boolean")),
+        };
+        if let Some(reference) = schema.get("$ref") {
+            let reference = reference
+                .as_str()
+                .ok_or_else(|| schema_error("$ref must be a string"))?;
+            // Applying a $ref to an instance it is already being applied to would never end.
+            let key = (reference.to_string(), instance as *const Value);
+            if !self.active_refs.borrow_mut().insert(key.clone()) {
+                return Err(schema_error(format!("Circular $ref: {}", reference)));
+            }
+            let result = self
+                .resolve_ref(reference)
+                .and_then(|resolved| self.validate(resolved, instance, path));
+            self.active_refs.borrow_mut().remove(&key);
+            result?;
+        }
+        self.validate_generic(schema, instance, path)?;
+        match instance {
+            Value::String(string) => self.validate_string(schema, string, path)?,
+            Value::Number(number) => {
+                if let Some(number) = number.as_f64() {
+                    self.validate_number(schema, number, path)?
+                }
+            }
+            Value::Array(items) => self.validate_array(schema, items, path)?,
+            Value::Object(object) => self.validate_object(schema, object, path)?,
+            _ => {}
+        }
+        self.validate_combinators(schema, instance, path)
+    }
+
+    fn validate_generic(
+        &self,
+        schema: &Map<String, Value>,
+        instance: &Value,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        match schema.get("type") {
+            None => {}
+            Some(Value::String(type_)) if !type_matches(type_, instance)? => {
+                return Err(invalid(path, format!("expected {}", type_)));
+            }
+            Some(Value::String(_)) => {}
+            Some(Value::Array(types)) => {
+                let mut matched = false;
+                for type_ in types {
+                    let type_ = type_
+                        .as_str()
+                        .ok_or_else(|| schema_error("type must be a string or array of strings"))?;
+                    matched |= type_matches(type_, instance)?;
+                }
+                if !matched {
+                    return Err(invalid(
+                        path,
+                        format!("expected one of types {}", Value::from(types.clone())),
+                    ));
+                }
+            }
+            Some(_) => return Err(schema_error("type must be a string or array of strings")),
+        }
+        if let Some(values) = get_array(schema, "enum")? {
+            if !values.contains(instance) {
+                return Err(invalid(path, "value is not in enum"));
+            }
+        }
+        if let Some(value) = schema.get("const") {
+            if value != instance {
+                return Err(invalid(path, format!("expected {}", value)));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_string(
+        &self,
+        schema: &Map<String, Value>,
+        string: &str,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        let length = string.chars().count();
+        if let Some(min) = get_usize(schema, "minLength")? {
+            if length < min {
+                return Err(invalid(path, format!("shorter than {} characters", min)));
+            }
+        }
+        if let Some(max) = get_usize(schema, "maxLength")? {
+            if length > max {
+                return Err(invalid(path, format!("longer than {} characters", max)));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern") {
+            let pattern = pattern
+                .as_str()
+                .ok_or_else(|| schema_error("pattern must be a string"))?;
+            let regex = regex::Regex::new(pattern)
+                .map_err(|e| schema_error(format!("Invalid pattern: {}", e)))?;
+            if !regex.is_match(string) {
+                return Err(invalid(path, format!("does not match pattern {}", pattern)));
+            }
+        }
+        if let Some(Value::String(format)) = schema.get("format") {
+            if !format_matches(format, string) {
+                return Err(invalid(path, format!("not a valid {}", format)));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_number(
+        &self,
+        schema: &Map<String, Value>,
+        number: f64,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        if let Some(min) = get_number(schema, "minimum")? {
+            if number < min {
+                return Err(invalid(path, format!("less than {}", min)));
+            }
+        }
+        if let Some(max) = get_number(schema, "maximum")? {
+            if number > max {
+                return Err(invalid(path, format!("greater than {}", max)));
+            }
+        }
+        if let Some(min) = get_number(schema, "exclusiveMinimum")? {
+            if number <= min {
+                return Err(invalid(path, format!("not greater than {}", min)));
+            }
+        }
+        if let Some(max) = get_number(schema, "exclusiveMaximum")? {
+            if number >= max {
+                return Err(invalid(path, format!("not less than {}", max)));
+            }
+        }
+        if let Some(divisor) = get_number(schema, "multipleOf")? {
+            if divisor <= 0.0 {
+                return Err(schema_error("multipleOf must be greater than 0"));
+            }
+            if !is_integer(number / divisor) {
+                return Err(invalid(path, format!("not a multiple of {}", divisor)));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_array(
+        &self,
+        schema: &Map<String, Value>,
+        items: &[Value],
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        if let Some(min) = get_usize(schema, "minItems")? {
+            if items.len() < min {
+                return Err(invalid(path, format!("fewer than {} items", min)));
+            }
+        }
+        if let Some(max) = get_usize(schema, "maxItems")? {
+            if items.len() > max {
+                return Err(invalid(path, format!("more than {} items", max)));
+            }
+        }
+        if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+            for (i, item) in items.iter().enumerate() {
+                if items[..i].contains(item) {
+                    return Err(invalid(path, "items are not unique"));
+                }
+            }
+        }
+        // Items validated by position, with `prefixItems` (2020-12) or array-valued `items`
+        // (draft-07); the remaining items are validated by `items` or `additionalItems`
+        // respectively.
+        let (prefix, rest) = match (schema.get("prefixItems"), schema.get("items")) {
+            (Some(Value::Array(prefix)), rest) => (prefix.as_slice(), rest),
+            (Some(_), _) => return Err(schema_error("prefixItems must be an array")),
+            (None, Some(Value::Array(prefix))) => {
+                (prefix.as_slice(), schema.get("additionalItems"))
+            }
+            (None, rest) => (&[][..], rest),
+        };
+        for (i, item) in items.iter().enumerate() {
+            let item_schema = match prefix.get(i) {
+                Some(item_schema) => item_schema,
+                None => match rest {
+                    Some(item_schema) => item_schema,
+                    None => break,
+                },
+            };
+            self.validate(item_schema, item, &child_path(path, &i.to_string()))?;
+        }
+        if let Some(contains) = schema.get("contains") {
+            let mut count = 0;
+            for item in items {
+                if self.is_valid(contains, item)? {
+                    count += 1;
+                }
+            }
+            let min = get_usize(schema, "minContains")?.unwrap_or(1);
+            if count < min {
+                return Err(invalid(
+                    path,
+                    format!("fewer than {} items match contains", min),
+                ));
+            }
+            if let Some(max) = get_usize(schema, "maxContains")? {
+                if count > max {
+                    return Err(invalid(
+                        path,
+                        format!("more than {} items match contains", max),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_object(
+        &self,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        if let Some(min) = get_usize(schema, "minProperties")? {
+            if object.len() < min {
+                return Err(invalid(path, format!("fewer than {} properties", min)));
+            }
+        }
+        if let Some(max) = get_usize(schema, "maxProperties")? {
+            if object.len() > max {
+                return Err(invalid(path, format!("more than {} properties", max)));
+            }
+        }
+        if let Some(required) = get_array(schema, "required")? {
+            for name in required {
+                let name = name
+                    .as_str()
+                    .ok_or_else(|| schema_error("required must be an array of strings"))?;
+                if !object.contains_key(name) {
+                    return Err(invalid(path, format!("missing property {}", name)));
+                }
+            }
+        }
+        if let Some(dependent_required) = schema.get("dependentRequired") {
+            let dependent_required = dependent_required
+                .as_object()
+                .ok_or_else(|| schema_error("dependentRequired must be an object"))?;
+            for (name, required) in dependent_required {
+                if !object.contains_key(name) {
+                    continue;
+                }
+                for dependency in required.as_array().into_iter().flatten() {
+                    let dependency = dependency.as_str().unwrap_or_default();
+                    if !object.contains_key(dependency) {
+                        return Err(invalid(
+                            path,
+                            format!("property {} requires property {}", name, dependency),
+                        ));
+                    }
+                }
+            }
+        }
+        if let Some(property_names) = schema.get("propertyNames") {
+            for name in object.keys() {
+                self.validate(property_names, &Value::String(name.clone()), path)?;
+            }
+        }
+        let properties = match schema.get("properties") {
+            None => None,
+            Some(Value::Object(properties)) => Some(properties),
+            Some(_) => return Err(schema_error("properties must be an object")),
+        };
+        let mut pattern_properties = Vec::new();
+        match schema.get("patternProperties") {
+            None => {}
+            Some(Value::Object(patterns)) => {
+                for (pattern, property_schema) in patterns {
+                    let regex = regex::Regex::new(pattern)
+                        .map_err(|e| schema_error(format!("Invalid pattern: {}", e)))?;
+                    pattern_properties.push((regex, property_schema));
+                }
+            }
+            Some(_) => return Err(schema_error("patternProperties must be an object")),
+        }
+        let additional_properties = schema.get("additionalProperties");
+        for (name, value) in object {
+            let property_path = child_path(path, name);
+            let mut evaluated = false;
+            if let Some(property_schema) = properties.and_then(|properties| properties.get(name)) {
+                self.validate(property_schema, value, &property_path)?;
+                evaluated = true;
+            }
+            for (regex, property_schema) in &pattern_properties {
+                if regex.is_match(name) {
+                    self.validate(property_schema, value, &property_path)?;
+                    evaluated = true;
+                }
+            }
+            if !evaluated {
+                if let Some(additional_properties) = additional_properties {
+                    self.validate(additional_properties, value, &property_path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_combinators(
+        &self,
+        schema: &Map<String, Value>,
+        instance: &Value,
+        path: &str,
+    ) -> Result<(), JsonSchemaError> {
+        if let Some(schemas) = get_array(schema, "allOf")? {
+            for subschema in schemas {
+                self.validate(subschema, instance, path)?;
+            }
+        }
+        if let Some(schemas) = get_array(schema, "anyOf")? {
+            let mut matched = false;
+            for subschema in schemas {
+                if self.is_valid(subschema, instance)? {
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                return Err(invalid(path, "does not match any schema in anyOf"));
+            }
+        }
+        if let Some(schemas) = get_array(schema, "oneOf")? {
+            let mut count = 0;
+            for subschema in schemas {
+                if self.is_valid(subschema, instance)? {
+                    count += 1;
+                }
+            }
+            if count != 1 {
+                return Err(invalid(
+                    path,
+                    format!("matches {} schemas in oneOf instead of one", count),
+                ));
+            }
+        }
+        if let Some(not) = schema.get("not") {
+            if self.is_valid(not, instance)? {
+                return Err(invalid(path, "matches schema in not"));
+            }
+        }
+        if let Some(condition) = schema.get("if") {
+            let branch = if self.is_valid(condition, instance)? {
+                schema.get("then")
+            } else {
+                schema.get("else")
+            };
+            if let Some(branch) = branch {
+                self.validate(branch, instance, path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate_keywords() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name", "age"],
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "age": {"type": "integer", "minimum": 18},
+                "email": {"type": "string", "pattern": "^[^@]+@[^@]+$"},
+                "birthDate": {"type": "string", "format": "date"},
+                "tags": {"type": "array", "items": {"type": "string"}, "uniqueItems": true}
+            },
+            "additionalProperties": false
+        });
+        validate(&schema, &json!({"name": "Alice", "age": 30})).unwrap();
+        validate(
+            &schema,
+            &json!({
+                "name": "Alice",
+                "age": 30,
+                "email": "alice@example.org",
+                "birthDate": "1990-01-01",
+                "tags": ["a", "b"]
+            }),
+        )
+        .unwrap();
+        let invalid_instances = [
+            json!({"name": "Alice"}),
+            json!({"name": "", "age": 30}),
+            json!({"name": "Alice", "age": 17}),
+            json!({"name": "Alice", "age": 30.5}),
+            json!({"name": "Alice", "age": 30, "email": "alice"}),
+            json!({"name": "Alice", "age": 30, "birthDate": "01/01/1990"}),
+            json!({"name": "Alice", "age": 30, "tags": ["a", "a"]}),
+            json!({"name": "Alice", "age": 30, "nickname": "Al"}),
+        ];
+        for instance in &invalid_instances {
+            assert!(!is_valid(&schema, instance).unwrap(), "{}", instance);
+        }
+        assert_eq!(
+            validate(&schema, &json!({"name": "Alice", "age": 17})).unwrap_err(),
+            JsonSchemaError::Invalid("/age".to_string(), "less than 18".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_combinators() {
+        let schema = json!({
+            "$defs": {"did": {"type": "string", "pattern": "^did:"}},
+            "type": "array",
+            "contains": {"$ref": "#/$defs/did"},
+            "items": {"anyOf": [{"$ref": "#/$defs/did"}, {"type": "string", "format": "uri"}]},
+            "not": {"const": []}
+        });
+        assert!(is_valid(&schema, &json!(["did:example:foo", "https://example.org/"])).unwrap());
+        assert!(!is_valid(&schema, &json!(["https://example.org/"])).unwrap());
+        assert!(!is_valid(&schema, &json!(["did:example:foo", 1])).unwrap());
+        assert!(matches!(
+            validate(&json!({"$ref": "#/definitions/missing"}), &json!(1)),
+            Err(JsonSchemaError::Schema(_))
+        ));
+        assert!(is_valid(
+            &json!({"oneOf": [{"minimum": 1}, {"maximum": 5}]}),
+            &json!(10)
+        )
+        .unwrap());
+        assert!(matches!(
+            validate(&json!({"$ref": "#"}), &json!(1)),
+            Err(JsonSchemaError::Schema(_))
+        ));
+        assert!(matches!(
+            validate(
+                &json!({
+                    "$defs": {
+                        "a": {"anyOf": [{"$ref": "#/$defs/b"}]},
+                        "b": {"not": {"$ref": "#/$defs/a"}}
+                    },
+                    "$ref": "#/$defs/a"
+                }),
+                &json!({"foo": 1})
+            ),
+            Err(JsonSchemaError::Schema(_))
+        ));
+        assert!(!is_valid(
+            &json!({"oneOf": [{"minimum": 1}, {"maximum": 5}]}),
+            &json!(3)
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn validate_recursive_ref() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "value": {"type": "integer"},
+                "children": {"type": "array", "items": {"$ref": "#"}}
+            }
+        });
+        let tree = json!({"value": 1, "children": [{"value": 2, "children": [{"value": 3}]}]});
+        assert!(is_valid(&schema, &tree).unwrap());
+        let tree = json!({"value": 1, "children": [{"children": [{"value": "3"}]}]});
+        assert_eq!(
+            validate(&schema, &tree),
+            Err(invalid("/children/0/children/0/value", "expected integer"))
+        );
+    }
+}
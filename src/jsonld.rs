@@ -10,6 +10,7 @@ use crate::rdf::{
     BlankNodeLabel, DataSet, Graph, GraphLabel, IRIOrBlankNodeIdentifier, IRIRef, Lang, Literal,
     Object, Predicate, StringLiteral, Subject, Triple, LANG_STRING_IRI_STR,
 };
+use crate::revocation::ResourceLoader;
 
 use async_std::sync::RwLock;
 use futures::future::{BoxFuture, FutureExt};
@@ -117,6 +118,8 @@ pub fn is_iri(string: &str) -> bool {
 
 pub const CREDENTIALS_V1_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
 pub const CREDENTIALS_EXAMPLES_V1_CONTEXT: &str = "https://www.w3.org/2018/credentials/examples/v1";
+pub const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+pub const CREDENTIALS_EXAMPLES_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/examples/v2";
 pub const ODRL_CONTEXT: &str = "https://www.w3.org/ns/odrl.jsonld";
 pub const SECURITY_V1_CONTEXT: &str = "https://w3id.org/security/v1";
 pub const SECURITY_V2_CONTEXT: &str = "https://w3id.org/security/v2";
@@ -135,6 +138,8 @@ pub const LDS_JWS2020_V1_CONTEXT: &str =
     "https://w3c-ccg.github.io/lds-jws2020/contexts/lds-jws2020-v1.json";
 pub const W3ID_JWS2020_V1_CONTEXT: &str = "https://w3id.org/security/suites/jws-2020/v1";
 pub const W3ID_ED2020_V1_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+pub const W3ID_DATA_INTEGRITY_V1_CONTEXT: &str = "https://w3id.org/security/data-integrity/v1";
+pub const W3ID_DATA_INTEGRITY_V2_CONTEXT: &str = "https://w3id.org/security/data-integrity/v2";
 pub const BLOCKCHAIN2021_V1_CONTEXT: &str = "https://w3id.org/security/suites/blockchain-2021/v1";
 pub const CITIZENSHIP_V1_CONTEXT: &str = "https://w3id.org/citizenship/v1";
 pub const VACCINATION_V1_CONTEXT: &str = "https://w3id.org/vaccination/v1";
@@ -166,6 +171,18 @@ lazy_static! {
         let iri = Iri::new(CREDENTIALS_EXAMPLES_V1_CONTEXT).unwrap();
         RemoteDocument::new(doc, iri)
     };
+    pub static ref CREDENTIALS_V2_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
+        let jsonld = ssi_contexts::CREDENTIALS_V2;
+        let doc = json::parse(jsonld).unwrap();
+        let iri = Iri::new(CREDENTIALS_V2_CONTEXT).unwrap();
+        RemoteDocument::new(doc, iri)
+    };
+    pub static ref CREDENTIALS_EXAMPLES_V2_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
+        let jsonld = ssi_contexts::CREDENTIALS_EXAMPLES_V2;
+        let doc = json::parse(jsonld).unwrap();
+        let iri = Iri::new(CREDENTIALS_EXAMPLES_V2_CONTEXT).unwrap();
+        RemoteDocument::new(doc, iri)
+    };
     pub static ref ODRL_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
         let jsonld = ssi_contexts::ODRL;
         let doc = json::parse(jsonld).unwrap();
@@ -244,6 +261,18 @@ lazy_static! {
         let iri = Iri::new(W3ID_ED2020_V1_CONTEXT).unwrap();
         RemoteDocument::new(doc, iri)
     };
+    pub static ref W3ID_DATA_INTEGRITY_V1_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
+        let jsonld = ssi_contexts::W3ID_DATA_INTEGRITY_V1;
+        let doc = json::parse(jsonld).unwrap();
+        let iri = Iri::new(W3ID_DATA_INTEGRITY_V1_CONTEXT).unwrap();
+        RemoteDocument::new(doc, iri)
+    };
+    pub static ref W3ID_DATA_INTEGRITY_V2_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
+        let jsonld = ssi_contexts::W3ID_DATA_INTEGRITY_V2;
+        let doc = json::parse(jsonld).unwrap();
+        let iri = Iri::new(W3ID_DATA_INTEGRITY_V2_CONTEXT).unwrap();
+        RemoteDocument::new(doc, iri)
+    };
     pub static ref BLOCKCHAIN2021_V1_CONTEXT_DOCUMENT: RemoteDocument<JsonValue> = {
         let jsonld = ssi_contexts::BLOCKCHAIN2021_V1;
         let doc = json::parse(jsonld).unwrap();
@@ -352,6 +381,10 @@ impl Loader for StaticLoader {
                 CREDENTIALS_EXAMPLES_V1_CONTEXT => {
                     Ok(CREDENTIALS_EXAMPLES_V1_CONTEXT_DOCUMENT.clone())
                 }
+                CREDENTIALS_V2_CONTEXT => Ok(CREDENTIALS_V2_CONTEXT_DOCUMENT.clone()),
+                CREDENTIALS_EXAMPLES_V2_CONTEXT => {
+                    Ok(CREDENTIALS_EXAMPLES_V2_CONTEXT_DOCUMENT.clone())
+                }
                 ODRL_CONTEXT => Ok(ODRL_CONTEXT_DOCUMENT.clone()),
                 SECURITY_V1_CONTEXT => Ok(SECURITY_V1_CONTEXT_DOCUMENT.clone()),
                 SECURITY_V2_CONTEXT => Ok(SECURITY_V2_CONTEXT_DOCUMENT.clone()),
@@ -368,6 +401,12 @@ impl Loader for StaticLoader {
                 LDS_JWS2020_V1_CONTEXT => Ok(LDS_JWS2020_V1_CONTEXT_DOCUMENT.clone()),
                 W3ID_JWS2020_V1_CONTEXT => Ok(W3ID_JWS2020_V1_CONTEXT_DOCUMENT.clone()),
                 W3ID_ED2020_V1_CONTEXT => Ok(W3ID_ED2020_V1_CONTEXT_DOCUMENT.clone()),
+                W3ID_DATA_INTEGRITY_V1_CONTEXT => {
+                    Ok(W3ID_DATA_INTEGRITY_V1_CONTEXT_DOCUMENT.clone())
+                }
+                W3ID_DATA_INTEGRITY_V2_CONTEXT => {
+                    Ok(W3ID_DATA_INTEGRITY_V2_CONTEXT_DOCUMENT.clone())
+                }
                 BLOCKCHAIN2021_V1_CONTEXT => Ok(BLOCKCHAIN2021_V1_CONTEXT_DOCUMENT.clone()),
                 CITIZENSHIP_V1_CONTEXT => Ok(CITIZENSHIP_V1_CONTEXT_DOCUMENT.clone()),
                 VACCINATION_V1_CONTEXT => Ok(VACCINATION_V1_CONTEXT_DOCUMENT.clone()),
@@ -406,6 +445,8 @@ pub struct ContextLoader {
     // objects would go.  The Arc<RwLock<_>> is necessary because json_ld::Loader trait unfortunately
     // has a method that uses `&mut self`.
     context_map: Option<Arc<RwLock<ContextMap>>>,
+    // Loader of status list credentials referenced by `credentialStatus`.
+    resource_loader: Option<Arc<dyn ResourceLoader>>,
 }
 
 impl std::fmt::Debug for ContextLoader {
@@ -420,6 +461,7 @@ impl ContextLoader {
         Self {
             static_loader: None,
             context_map: None,
+            resource_loader: None,
         }
     }
     /// Using the builder pattern, the StaticLoader can be enabled so that contexts are checked
@@ -448,6 +490,16 @@ impl ContextLoader {
         self.context_map = Some(Arc::new(RwLock::new(context_map)));
         Ok(self)
     }
+    /// Using the builder pattern, the loader of status list credentials can be set.  It is used
+    /// to check credential statuses, instead of fetching the lists over HTTP(S).
+    pub fn with_resource_loader(mut self, resource_loader: impl ResourceLoader + 'static) -> Self {
+        self.resource_loader = Some(Arc::new(resource_loader));
+        self
+    }
+    /// The loader of status list credentials, if set.
+    pub fn resource_loader(&self) -> Option<&dyn ResourceLoader> {
+        self.resource_loader.as_deref()
+    }
 }
 
 /// The default ContextLoader only uses StaticLoader.
@@ -456,6 +508,7 @@ impl std::default::Default for ContextLoader {
         Self {
             static_loader: Some(StaticLoader),
             context_map: None,
+            resource_loader: None,
         }
     }
 }
@@ -0,0 +1,413 @@
+//! [JSONPath] queries on JSON values
+//!
+//! This module implements the subset of JSONPath used by [Presentation
+//! Exchange](crate::presentation_exchange) field constraints: child member and index selectors in
+//! dot or bracket notation, wildcards, array slices, and recursive descent. Filter and script
+//! expressions are not supported.
+//!
+//! ## Example
+//! ```
+//! use ssi::jsonpath;
+//! use serde_json::json;
+//!
+//! let value = json!({"credentialSubject": {"degree": {"type": "BachelorDegree"}}});
+//! let types = jsonpath::select("$.credentialSubject.degree.type", &value)?;
+//! assert_eq!(types, vec![&json!("BachelorDegree")]);
+//! # Ok::<(), ssi::jsonpath::JsonPathError>(())
+//! ```
+//!
+//! [JSONPath]: https://goessner.net/articles/JsonPath/
+
+use serde_json::Value;
+use std::convert::TryInto;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use thiserror::Error;
+
+/// Error parsing a JSONPath expression.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum JsonPathError {
+    #[error("JSONPath must start with '$'")]
+    MissingRoot,
+    #[error("Unexpected character in JSONPath at position {0}: {1:?}")]
+    UnexpectedChar(usize, char),
+    #[error("Unexpected end of JSONPath")]
+    UnexpectedEnd,
+    #[error("Invalid number in JSONPath: {0}")]
+    InvalidNumber(String),
+    #[error("Unsupported JSONPath expression: {0}")]
+    Unsupported(String),
+}
+
+/// Parsed JSONPath expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Wildcard,
+}
+
+/// Evaluate a JSONPath expression, returning the selected values in document order.
+pub fn select<'a>(path: &str, value: &'a Value) -> Result<Vec<&'a Value>, JsonPathError> {
+    Ok(JsonPath::from_str(path)?.select(value))
+}
+
+impl JsonPath {
+    /// Select the values matching this path.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut nodes = vec![value];
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for node in nodes {
+                match segment {
+                    Segment::Child(selectors) => select_children(node, selectors, &mut next),
+                    Segment::Descendant(selectors) => {
+                        let mut descendants = Vec::new();
+                        collect_descendants(node, &mut descendants);
+                        for descendant in descendants {
+                            select_children(descendant, selectors, &mut next);
+                        }
+                    }
+                }
+            }
+            nodes = next;
+        }
+        nodes
+    }
+}
+
+fn collect_descendants<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+    out.push(value);
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| collect_descendants(item, out)),
+        Value::Object(map) => map.values().for_each(|item| collect_descendants(item, out)),
+        _ => {}
+    }
+}
+
+fn select_children<'a>(value: &'a Value, selectors: &[Selector], out: &mut Vec<&'a Value>) {
+    for selector in selectors {
+        match (selector, value) {
+            (Selector::Name(name), Value::Object(map)) => out.extend(map.get(name)),
+            (Selector::Wildcard, Value::Object(map)) => out.extend(map.values()),
+            (Selector::Wildcard, Value::Array(items)) => out.extend(items),
+            (Selector::Index(i), Value::Array(items)) => {
+                out.extend(normalize_index(*i, items.len()).and_then(|i| items.get(i)))
+            }
+            (Selector::Slice(start, end, step), Value::Array(items)) => {
+                select_slice(items, *start, *end, step.unwrap_or(1), out)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn normalize_index(i: i64, len: usize) -> Option<usize> {
+    if i >= 0 {
+        Some(i as usize)
+    } else {
+        (len as i64 + i).try_into().ok()
+    }
+}
+
+fn select_slice<'a>(
+    items: &'a [Value],
+    start: Option<i64>,
+    end: Option<i64>,
+    step: i64,
+    out: &mut Vec<&'a Value>,
+) {
+    let len = items.len() as i64;
+    let bound = |i: i64| if i < 0 { (len + i).max(0) } else { i.min(len) };
+    if step > 0 {
+        let mut i = start.map_or(0, bound);
+        let end = end.map_or(len, bound);
+        while i < end {
+            out.push(&items[i as usize]);
+            i = match i.checked_add(step) {
+                Some(i) => i,
+                None => break,
+            };
+        }
+    } else if step < 0 {
+        let mut i = start.map_or(len - 1, |i| bound(i).min(len - 1));
+        let end = end.map_or(-1, bound);
+        while i > end {
+            out.push(&items[i as usize]);
+            i = match i.checked_add(step) {
+                Some(i) => i,
+                None => break,
+            };
+        }
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = JsonPathError;
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Parser {
+            chars: path.chars().peekable(),
+            position: 0,
+        }
+        .parse()
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonPathError> {
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(JsonPathError::UnexpectedChar(self.position - 1, c)),
+            None => Err(JsonPathError::UnexpectedEnd),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn parse(mut self) -> Result<JsonPath, JsonPathError> {
+        if self.next() != Some('$') {
+            return Err(JsonPathError::MissingRoot);
+        }
+        let mut segments = Vec::new();
+        while let Some(c) = self.next() {
+            let segment = match c {
+                '.' => {
+                    if self.chars.peek() == Some(&'.') {
+                        self.next();
+                        let selectors = if self.chars.peek() == Some(&'[') {
+                            self.next();
+                            self.parse_bracket()?
+                        } else {
+                            vec![self.parse_dot_member()?]
+                        };
+                        Segment::Descendant(selectors)
+                    } else {
+                        Segment::Child(vec![self.parse_dot_member()?])
+                    }
+                }
+                '[' => Segment::Child(self.parse_bracket()?),
+                c => return Err(JsonPathError::UnexpectedChar(self.position - 1, c)),
+            };
+            segments.push(segment);
+        }
+        Ok(JsonPath { segments })
+    }
+
+    fn parse_dot_member(&mut self) -> Result<Selector, JsonPathError> {
+        if self.chars.peek() == Some(&'*') {
+            self.next();
+            return Ok(Selector::Wildcard);
+        }
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '.' || c == '[' {
+                break;
+            }
+            if !(c.is_alphanumeric() || c == '_' || c == '-' || c == '$' || c == '@') {
+                return Err(JsonPathError::UnexpectedChar(self.position, c));
+            }
+            name.push(c);
+            self.next();
+        }
+        if name.is_empty() {
+            return match self.chars.peek() {
+                Some(&c) => Err(JsonPathError::UnexpectedChar(self.position, c)),
+                None => Err(JsonPathError::UnexpectedEnd),
+            };
+        }
+        Ok(Selector::Name(name))
+    }
+
+    fn parse_bracket(&mut self) -> Result<Vec<Selector>, JsonPathError> {
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let selector = match self.chars.peek() {
+                Some('\'') | Some('"') => Selector::Name(self.parse_string()?),
+                Some('*') => {
+                    self.next();
+                    Selector::Wildcard
+                }
+                Some('?') | Some('(') => {
+                    return Err(JsonPathError::Unsupported(
+                        "filter and script expressions".to_string(),
+                    ))
+                }
+                Some(_) => self.parse_index_or_slice()?,
+                None => return Err(JsonPathError::UnexpectedEnd),
+            };
+            selectors.push(selector);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(selectors),
+                Some(c) => return Err(JsonPathError::UnexpectedChar(self.position - 1, c)),
+                None => return Err(JsonPathError::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonPathError> {
+        let quote = self.next().ok_or(JsonPathError::UnexpectedEnd)?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some(c @ '\\') | Some(c @ '\'') | Some(c @ '"') | Some(c @ '/') => {
+                        string.push(c)
+                    }
+                    Some('b') => string.push('\u{08}'),
+                    Some('f') => string.push('\u{0C}'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or(JsonPathError::InvalidNumber(hex))?;
+                        string.push(c);
+                    }
+                    Some(c) => return Err(JsonPathError::UnexpectedChar(self.position - 1, c)),
+                    None => return Err(JsonPathError::UnexpectedEnd),
+                },
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+                None => return Err(JsonPathError::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn parse_integer(&mut self) -> Result<Option<i64>, JsonPathError> {
+        self.skip_whitespace();
+        let mut number = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_ascii_digit() || (c == '-' && number.is_empty())) {
+                break;
+            }
+            number.push(c);
+            self.next();
+        }
+        self.skip_whitespace();
+        if number.is_empty() {
+            return Ok(None);
+        }
+        number
+            .parse()
+            .map(Some)
+            .map_err(|_| JsonPathError::InvalidNumber(number))
+    }
+
+    fn parse_index_or_slice(&mut self) -> Result<Selector, JsonPathError> {
+        let start = self.parse_integer()?;
+        if self.chars.peek() != Some(&':') {
+            return match start {
+                Some(i) => Ok(Selector::Index(i)),
+                None => match self.chars.peek() {
+                    Some(&c) => Err(JsonPathError::UnexpectedChar(self.position, c)),
+                    None => Err(JsonPathError::UnexpectedEnd),
+                },
+            };
+        }
+        self.expect(':')?;
+        let end = self.parse_integer()?;
+        let step = if self.chars.peek() == Some(&':') {
+            self.next();
+            self.parse_integer()?
+        } else {
+            None
+        };
+        Ok(Selector::Slice(start, end, step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn select_paths() {
+        let value = json!({
+            "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+            "credentialSubject": {
+                "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+                "degree": {"type": "BachelorDegree", "name": "Bachelor of Science"}
+            },
+            "vc": {"credentialSubject": {"degree": {"type": "MasterDegree"}}}
+        });
+        let select = |path| select(path, &value).unwrap();
+        assert_eq!(select("$"), vec![&value]);
+        assert_eq!(select("$.type[0]"), vec![&json!("VerifiableCredential")]);
+        assert_eq!(
+            select("$.type[-1]"),
+            vec![&json!("UniversityDegreeCredential")]
+        );
+        assert_eq!(select("$.type[*]").len(), 2);
+        assert_eq!(select("$.type[0:1]"), vec![&json!("VerifiableCredential")]);
+        assert_eq!(
+            select("$.type[1::9223372036854775807]"),
+            vec![&json!("UniversityDegreeCredential")]
+        );
+        assert_eq!(
+            select("$.type[::-9223372036854775808]"),
+            vec![&json!("UniversityDegreeCredential")]
+        );
+        assert_eq!(
+            select("$['credentialSubject'][\"degree\"].type"),
+            vec![&json!("BachelorDegree")]
+        );
+        assert_eq!(
+            select("$..degree.type"),
+            vec![&json!("BachelorDegree"), &json!("MasterDegree")]
+        );
+        assert_eq!(select("$.credentialSubject.*").len(), 2);
+        assert!(select("$.issuer").is_empty());
+        assert!(select("$.type.name").is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            JsonPath::from_str("type").unwrap_err(),
+            JsonPathError::MissingRoot
+        );
+        assert_eq!(
+            JsonPath::from_str("$.type[0").unwrap_err(),
+            JsonPathError::UnexpectedEnd
+        );
+        assert!(matches!(
+            JsonPath::from_str("$.type[?(@ == 'x')]").unwrap_err(),
+            JsonPathError::Unsupported(_)
+        ));
+    }
+}
@@ -0,0 +1,1026 @@
+//! JSON Web Encryption (JWE)
+//!
+//! Encryption of a payload to one or more recipients, using key agreement over X25519, P-256 or
+//! secp256k1 keys:
+//!
+//! - `ECDH-ES` and `ECDH-ES+A256KW` ([RFC 7518 Section 4.6][ecdh-es]) for anonymous encryption,
+//! - `ECDH-1PU` and `ECDH-1PU+A256KW` ([draft-madden-jose-ecdh-1pu][ecdh-1pu]) for
+//!   authenticated encryption, using the sender's static key in addition to an ephemeral key.
+//!
+//! The content is encrypted with `A256GCM` (AES-256-GCM) or `XC20P` (XChaCha20-Poly1305). The
+//! direct key agreement algorithms (`ECDH-ES` and `ECDH-1PU`) allow a single recipient; the key
+//! wrapping algorithms encrypt the content encryption key for each recipient, with one ephemeral
+//! key shared by all recipients in the protected header.
+//!
+//! A [`JWE`] is serialized to the general JSON serialization with [serde], or to the compact
+//! serialization with [`JWE::to_compact`]. Recipient keys of a DID are the `keyAgreement`
+//! verification methods of its DID document ([`key_agreement_keys`],
+//! [`resolve_key_agreement_keys`]).
+//!
+//! [ecdh-es]: https://www.rfc-editor.org/rfc/rfc7518#section-4.6
+//! [ecdh-1pu]: https://datatracker.ietf.org/doc/html/draft-madden-jose-ecdh-1pu-04
+
+// RFC 7516 - JSON Web Encryption (JWE)
+// RFC 7518 - JSON Web Algorithms (JWA)
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use aes_gcm::aead::{AeadInPlace, NewAead};
+use rand_old::RngCore;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::did::Document;
+use crate::did_resolve::{easy_resolve, DIDResolver};
+use crate::error::Error;
+use crate::jwk::{Base64urlUInt, Params as JWKParams, JWK};
+
+/// Error from encrypting or decrypting a JWE.
+#[derive(Error, Debug)]
+pub enum JWEError {
+    #[error("Invalid JWE serialization")]
+    InvalidSerialization,
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    JSON(#[from] serde_json::Error),
+    #[error(transparent)]
+    Key(#[from] Error),
+    #[error("Missing key management algorithm")]
+    MissingAlgorithm,
+    #[error("Missing content encryption algorithm")]
+    MissingEncryptionAlgorithm,
+    #[error("Missing ephemeral public key")]
+    MissingEphemeralKey,
+    #[error("Missing sender key")]
+    MissingSenderKey,
+    #[error("Unexpected sender key for algorithm {0:?}")]
+    UnexpectedSenderKey(KeyManagementAlgorithm),
+    #[error("No recipients")]
+    MissingRecipients,
+    #[error("Algorithm {0:?} allows only one recipient")]
+    MultipleRecipients(KeyManagementAlgorithm),
+    #[error("Unsupported curve: {0}")]
+    UnsupportedCurve(String),
+    #[error("Keys must use the same curve")]
+    CurveMismatch,
+    #[error("Header parameter present in more than one header: {0}")]
+    DuplicateHeaderParameter(String),
+    #[error("Missing encrypted key")]
+    MissingEncryptedKey,
+    #[error("Unexpected encrypted key for direct key agreement")]
+    UnexpectedEncryptedKey,
+    #[error("Invalid content encryption key")]
+    KeyWrap,
+    #[error("Unable to encrypt content")]
+    Encryption,
+    #[error("Unable to decrypt content")]
+    Decryption,
+    #[error("No recipient matches the key")]
+    RecipientNotFound,
+    #[error("Key agreement key not found: {0}")]
+    KeyAgreementKeyNotFound(String),
+    #[error("Invalid critical header parameters")]
+    InvalidCriticalHeader,
+    #[error("Unsupported critical header parameter: {0}")]
+    UnknownCriticalHeader(String),
+}
+
+/// Key management algorithm (`alg`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
+pub enum KeyManagementAlgorithm {
+    /// Direct key agreement with an ephemeral key.
+    #[serde(rename = "ECDH-ES")]
+    ECDHES,
+    /// Key agreement with an ephemeral key, wrapping the content encryption key with A256KW.
+    #[serde(rename = "ECDH-ES+A256KW")]
+    ECDHESA256KW,
+    /// Direct key agreement with an ephemeral key and the sender's static key.
+    #[serde(rename = "ECDH-1PU")]
+    ECDH1PU,
+    /// Key agreement with an ephemeral key and the sender's static key, wrapping the content
+    /// encryption key with A256KW.
+    #[serde(rename = "ECDH-1PU+A256KW")]
+    ECDH1PUA256KW,
+}
+
+impl KeyManagementAlgorithm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::ECDHES => "ECDH-ES",
+            Self::ECDHESA256KW => "ECDH-ES+A256KW",
+            Self::ECDH1PU => "ECDH-1PU",
+            Self::ECDH1PUA256KW => "ECDH-1PU+A256KW",
+        }
+    }
+
+    fn is_key_wrapping(&self) -> bool {
+        matches!(self, Self::ECDHESA256KW | Self::ECDH1PUA256KW)
+    }
+
+    fn is_authenticated(&self) -> bool {
+        matches!(self, Self::ECDH1PU | Self::ECDH1PUA256KW)
+    }
+}
+
+/// Content encryption algorithm (`enc`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
+pub enum ContentEncryptionAlgorithm {
+    /// AES-256-GCM
+    A256GCM,
+    /// XChaCha20-Poly1305
+    XC20P,
+}
+
+impl ContentEncryptionAlgorithm {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::A256GCM => "A256GCM",
+            Self::XC20P => "XC20P",
+        }
+    }
+
+    fn iv_len(&self) -> usize {
+        match self {
+            Self::A256GCM => 12,
+            Self::XC20P => 24,
+        }
+    }
+}
+
+/// Length in bytes of the content encryption key, for both content encryption algorithms.
+const CEK_LEN: usize = 32;
+
+/// JOSE Header of a JWE.
+///
+/// The protected header, shared unprotected header and per-recipient header of a JWE are merged
+/// to get the parameters for a recipient.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Header {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "alg")]
+    pub algorithm: Option<KeyManagementAlgorithm>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "enc")]
+    pub encryption_algorithm: Option<ContentEncryptionAlgorithm>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "kid")]
+    pub key_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "skid")]
+    pub sender_key_id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "epk")]
+    pub ephemeral_public_key: Option<JWK>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "apu")]
+    pub agreement_party_u_info: Option<Base64urlUInt>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "apv")]
+    pub agreement_party_v_info: Option<Base64urlUInt>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "typ")]
+    pub type_: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "cty")]
+    pub content_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "crit")]
+    pub critical: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(flatten)]
+    pub additional_parameters: BTreeMap<String, serde_json::Value>,
+}
+
+impl Header {
+    /// Merge another header into this one. The headers must not share any parameter.
+    pub fn merge(&self, other: &Header) -> Result<Header, JWEError> {
+        let mut object = match serde_json::to_value(self)? {
+            serde_json::Value::Object(object) => object,
+            _ => return Err(JWEError::InvalidSerialization),
+        };
+        let other_object = match serde_json::to_value(other)? {
+            serde_json::Value::Object(object) => object,
+            _ => return Err(JWEError::InvalidSerialization),
+        };
+        for (name, value) in other_object {
+            if object.contains_key(&name) {
+                return Err(JWEError::DuplicateHeaderParameter(name));
+            }
+            object.insert(name, value);
+        }
+        Ok(serde_json::from_value(serde_json::Value::Object(object))?)
+    }
+}
+
+/// Public key of a recipient of a JWE, with its key ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecipientKey {
+    /// Key ID, e.g. the ID of a `keyAgreement` verification method, set as the recipient's `kid`.
+    pub key_id: Option<String>,
+    pub jwk: JWK,
+}
+
+/// Recipient of a JWE in the JSON serialization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Recipient {
+    /// Per-recipient unprotected header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<Header>,
+    /// Base64url-encoded encrypted key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_key: Option<String>,
+}
+
+/// JSON Web Encryption, in the general JSON serialization.
+///
+/// Deserializing also accepts the flattened JSON serialization.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(try_from = "JWEJson")]
+pub struct JWE {
+    /// Base64url-encoded protected header.
+    pub protected: String,
+    /// Shared unprotected header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unprotected: Option<Header>,
+    pub recipients: Vec<Recipient>,
+    /// Base64url-encoded additional authenticated data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aad: Option<String>,
+    /// Base64url-encoded initialization vector.
+    pub iv: String,
+    /// Base64url-encoded ciphertext.
+    pub ciphertext: String,
+    /// Base64url-encoded authentication tag.
+    pub tag: String,
+}
+
+/// General or flattened JSON serialization of a JWE.
+#[derive(Deserialize)]
+struct JWEJson {
+    protected: String,
+    unprotected: Option<Header>,
+    recipients: Option<Vec<Recipient>>,
+    header: Option<Header>,
+    encrypted_key: Option<String>,
+    aad: Option<String>,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+impl TryFrom<JWEJson> for JWE {
+    type Error = JWEError;
+    fn try_from(json: JWEJson) -> Result<Self, Self::Error> {
+        let recipients = match (json.recipients, json.header, json.encrypted_key) {
+            (Some(recipients), None, None) => recipients,
+            (None, header, encrypted_key) => vec![Recipient {
+                header,
+                encrypted_key,
+            }],
+            _ => return Err(JWEError::InvalidSerialization),
+        };
+        Ok(Self {
+            protected: json.protected,
+            unprotected: json.unprotected,
+            recipients,
+            aad: json.aad,
+            iv: json.iv,
+            ciphertext: json.ciphertext,
+            tag: json.tag,
+        })
+    }
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+fn base64_decode(data: &str) -> Result<Vec<u8>, JWEError> {
+    Ok(base64::decode_config(data, base64::URL_SAFE_NO_PAD)?)
+}
+
+impl JWE {
+    /// Get the protected header merged with the shared unprotected header.
+    pub fn header(&self) -> Result<Header, JWEError> {
+        let protected = self.protected_header()?;
+        match self.unprotected {
+            Some(ref unprotected) => protected.merge(unprotected),
+            None => Ok(protected),
+        }
+    }
+
+    fn protected_header(&self) -> Result<Header, JWEError> {
+        Ok(serde_json::from_slice(&base64_decode(&self.protected)?)?)
+    }
+
+    /// Serialize to the compact serialization. Only a JWE with a single recipient, and without
+    /// unprotected headers or additional authenticated data, can be serialized this way.
+    pub fn to_compact(&self) -> Result<String, JWEError> {
+        let recipient = match self.recipients.as_slice() {
+            [recipient] => recipient,
+            _ => return Err(JWEError::InvalidSerialization),
+        };
+        if self.unprotected.is_some() || recipient.header.is_some() || self.aad.is_some() {
+            return Err(JWEError::InvalidSerialization);
+        }
+        Ok([
+            &self.protected[..],
+            recipient.encrypted_key.as_deref().unwrap_or(""),
+            &self.iv,
+            &self.ciphertext,
+            &self.tag,
+        ]
+        .join("."))
+    }
+
+    /// Parse the compact serialization.
+    pub fn from_compact(jwe: &str) -> Result<Self, JWEError> {
+        let parts: Vec<&str> = jwe.split('.').collect();
+        let (protected, encrypted_key, iv, ciphertext, tag) = match parts.as_slice() {
+            [protected, encrypted_key, iv, ciphertext, tag] => {
+                (protected, encrypted_key, iv, ciphertext, tag)
+            }
+            _ => return Err(JWEError::InvalidSerialization),
+        };
+        Ok(Self {
+            protected: protected.to_string(),
+            unprotected: None,
+            recipients: vec![Recipient {
+                header: None,
+                encrypted_key: Some(encrypted_key.to_string()).filter(|key| !key.is_empty()),
+            }],
+            aad: None,
+            iv: iv.to_string(),
+            ciphertext: ciphertext.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+
+    /// Decrypt the payload with the private key of a recipient.
+    ///
+    /// If a key ID is given, only the recipients with that `kid` are tried; otherwise, every
+    /// recipient is tried. For `ECDH-1PU` algorithms, the public key of the sender (identified by
+    /// `skid` in the header) must be given.
+    ///
+    /// Critical header parameters (`crit`) are rejected, as no extension is supported; they must
+    /// anyway be in the protected header.
+    pub fn decrypt(
+        &self,
+        key: &JWK,
+        key_id: Option<&str>,
+        sender_key: Option<&JWK>,
+    ) -> Result<Vec<u8>, JWEError> {
+        check_critical_headers(&self.protected_header()?)?;
+        let unprotected_headers = self
+            .unprotected
+            .iter()
+            .chain(self.recipients.iter().flat_map(|r| r.header.as_ref()));
+        for header in unprotected_headers {
+            if header.critical.is_some() {
+                return Err(JWEError::InvalidCriticalHeader);
+            }
+        }
+        let shared_header = self.header()?;
+        let mut result = Err(JWEError::RecipientNotFound);
+        for recipient in &self.recipients {
+            let header = match recipient.header {
+                Some(ref header) => shared_header.merge(header)?,
+                None => shared_header.clone(),
+            };
+            if key_id.is_some() && header.key_id.as_deref() != key_id {
+                continue;
+            }
+            result = self.decrypt_recipient(&header, recipient, key, sender_key);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
+    }
+
+    fn decrypt_recipient(
+        &self,
+        header: &Header,
+        recipient: &Recipient,
+        key: &JWK,
+        sender_key: Option<&JWK>,
+    ) -> Result<Vec<u8>, JWEError> {
+        let alg = header.algorithm.ok_or(JWEError::MissingAlgorithm)?;
+        let enc = header
+            .encryption_algorithm
+            .ok_or(JWEError::MissingEncryptionAlgorithm)?;
+        let epk = header
+            .ephemeral_public_key
+            .as_ref()
+            .ok_or(JWEError::MissingEphemeralKey)?;
+        let mut z = ecdh(key, epk)?;
+        if alg.is_authenticated() {
+            let sender_key = sender_key.ok_or(JWEError::MissingSenderKey)?;
+            z.extend(ecdh(key, sender_key)?);
+        }
+        let iv = base64_decode(&self.iv)?;
+        let ciphertext = base64_decode(&self.ciphertext)?;
+        let tag = base64_decode(&self.tag)?;
+        let cek = match (alg.is_key_wrapping(), &recipient.encrypted_key) {
+            (true, Some(encrypted_key)) => {
+                // ECDH-1PU binds the key wrapping to the content with the authentication tag.
+                let cc_tag = Some(&tag[..]).filter(|_| alg.is_authenticated());
+                let kek = concat_kdf(&z, alg.as_str(), header, cc_tag)?;
+                aes_kw::KekAes256::try_from(&kek[..])
+                    .map_err(|_| JWEError::KeyWrap)?
+                    .unwrap_vec(&base64_decode(encrypted_key)?)
+                    .map_err(|_| JWEError::KeyWrap)?
+            }
+            (true, None) => return Err(JWEError::MissingEncryptedKey),
+            (false, None) => concat_kdf(&z, enc.as_str(), header, None)?,
+            (false, Some(_)) => return Err(JWEError::UnexpectedEncryptedKey),
+        };
+        let aad = self.additional_authenticated_data();
+        decrypt_content(enc, &cek, &iv, aad.as_bytes(), ciphertext, &tag)
+    }
+
+    /// Additional authenticated data for the content encryption: the encoded protected header,
+    /// followed by the encoded `aad` if any.
+    fn additional_authenticated_data(&self) -> String {
+        match self.aad {
+            Some(ref aad) => format!("{}.{}", self.protected, aad),
+            None => self.protected.clone(),
+        }
+    }
+}
+
+/// Check that the "crit" (critical) header parameters are recognized and supported. No extension
+/// parameters are supported, so any listed parameter is rejected.
+fn check_critical_headers(header: &Header) -> Result<(), JWEError> {
+    let names = match header.critical {
+        Some(ref names) => names,
+        None => return Ok(()),
+    };
+    match names.first().map(|name| name.as_str()) {
+        None
+        | Some(
+            "alg" | "enc" | "zip" | "jku" | "jwk" | "kid" | "x5u" | "x5c" | "x5t" | "x5t#S256"
+            | "typ" | "cty" | "crit" | "epk" | "apu" | "apv" | "skid",
+        ) => Err(JWEError::InvalidCriticalHeader),
+        Some(name) => Err(JWEError::UnknownCriticalHeader(name.to_string())),
+    }
+}
+
+/// Encrypt a payload to one or more recipients.
+///
+/// The header must have the key management algorithm (`alg`) and content encryption algorithm
+/// (`enc`), and may have other parameters, e.g. `typ`, `skid`, `apu` and `apv`; it is used as
+/// the protected header, with the ephemeral public key added. `ECDH-1PU` algorithms need the
+/// private key of the sender; if the header has a `skid` and no `apu`, `apu` is set to the
+/// `skid`. All the keys must use the same curve.
+pub fn encrypt(
+    payload: &[u8],
+    mut header: Header,
+    recipients: &[RecipientKey],
+    sender_key: Option<&JWK>,
+) -> Result<JWE, JWEError> {
+    let alg = header.algorithm.ok_or(JWEError::MissingAlgorithm)?;
+    let enc = header
+        .encryption_algorithm
+        .ok_or(JWEError::MissingEncryptionAlgorithm)?;
+    let first_recipient = recipients.first().ok_or(JWEError::MissingRecipients)?;
+    if !alg.is_key_wrapping() && recipients.len() > 1 {
+        return Err(JWEError::MultipleRecipients(alg));
+    }
+    let curve = curve(&first_recipient.jwk)?;
+    if recipients
+        .iter()
+        .any(|recipient| curve_opt(&recipient.jwk) != Some(curve))
+    {
+        return Err(JWEError::CurveMismatch);
+    }
+    let sender_key = match (alg.is_authenticated(), sender_key) {
+        (true, Some(sender_key)) => {
+            if curve_opt(sender_key) != Some(curve) {
+                return Err(JWEError::CurveMismatch);
+            }
+            if header.agreement_party_u_info.is_none() {
+                header.agreement_party_u_info = header
+                    .sender_key_id
+                    .as_ref()
+                    .map(|skid| Base64urlUInt(skid.as_bytes().to_vec()));
+            }
+            Some(sender_key)
+        }
+        (true, None) => return Err(JWEError::MissingSenderKey),
+        (false, Some(_)) => return Err(JWEError::UnexpectedSenderKey(alg)),
+        (false, None) => None,
+    };
+    let ephemeral_key = generate_ephemeral_key(curve)?;
+    header.ephemeral_public_key = Some(ephemeral_key.to_public());
+    if !alg.is_key_wrapping() {
+        header.key_id = first_recipient.key_id.clone();
+    }
+    let protected = base64_encode(serde_json::to_string(&header)?.as_bytes());
+
+    // Shared secret for each recipient
+    let mut zs = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let mut z = ecdh(&ephemeral_key, &recipient.jwk)?;
+        if let Some(sender_key) = sender_key {
+            z.extend(ecdh(sender_key, &recipient.jwk)?);
+        }
+        zs.push(z);
+    }
+
+    let cek = if alg.is_key_wrapping() {
+        let mut cek = vec![0u8; CEK_LEN];
+        rand_old::rngs::OsRng {}.fill_bytes(&mut cek);
+        cek
+    } else {
+        concat_kdf(&zs[0], enc.as_str(), &header, None)?
+    };
+    let mut iv = vec![0u8; enc.iv_len()];
+    rand_old::rngs::OsRng {}.fill_bytes(&mut iv);
+    let (ciphertext, tag) = encrypt_content(enc, &cek, &iv, protected.as_bytes(), payload)?;
+
+    let recipients = if alg.is_key_wrapping() {
+        let cc_tag = Some(&tag[..]).filter(|_| alg.is_authenticated());
+        recipients
+            .iter()
+            .zip(zs)
+            .map(|(recipient, z)| {
+                let kek = concat_kdf(&z, alg.as_str(), &header, cc_tag)?;
+                let encrypted_key = aes_kw::KekAes256::try_from(&kek[..])
+                    .map_err(|_| JWEError::KeyWrap)?
+                    .wrap_vec(&cek)
+                    .map_err(|_| JWEError::KeyWrap)?;
+                Ok(Recipient {
+                    header: recipient.key_id.as_ref().map(|key_id| Header {
+                        key_id: Some(key_id.to_string()),
+                        ..Default::default()
+                    }),
+                    encrypted_key: Some(base64_encode(&encrypted_key)),
+                })
+            })
+            .collect::<Result<Vec<Recipient>, JWEError>>()?
+    } else {
+        vec![Recipient::default()]
+    };
+    Ok(JWE {
+        protected,
+        unprotected: None,
+        recipients,
+        aad: None,
+        iv: base64_encode(&iv),
+        ciphertext: base64_encode(&ciphertext),
+        tag: base64_encode(&tag),
+    })
+}
+
+/// Get the key agreement keys of a DID document, with their verification method IDs as key IDs.
+pub fn key_agreement_keys(document: &Document) -> Result<Vec<RecipientKey>, JWEError> {
+    let keys = document
+        .get_key_agreement_keys()?
+        .into_iter()
+        .map(|mut jwk| RecipientKey {
+            key_id: jwk.key_id.take(),
+            jwk,
+        })
+        .collect();
+    Ok(keys)
+}
+
+/// Resolve the key agreement keys of a DID, or the key agreement key identified by a DID URL.
+pub async fn resolve_key_agreement_keys(
+    did_url: &str,
+    resolver: &dyn DIDResolver,
+) -> Result<Vec<RecipientKey>, JWEError> {
+    let (did, fragment) = match did_url.split_once('#') {
+        Some((did, fragment)) => (did, Some(fragment)),
+        None => (did_url, None),
+    };
+    let document = easy_resolve(did, resolver).await?;
+    let keys = key_agreement_keys(&document)?;
+    if fragment.is_none() {
+        if keys.is_empty() {
+            return Err(JWEError::KeyAgreementKeyNotFound(did_url.to_string()));
+        }
+        return Ok(keys);
+    }
+    let key = keys
+        .into_iter()
+        .find(|key| key.key_id.as_deref() == Some(did_url))
+        .ok_or_else(|| JWEError::KeyAgreementKeyNotFound(did_url.to_string()))?;
+    Ok(vec![key])
+}
+
+pub(crate) fn curve_opt(jwk: &JWK) -> Option<&str> {
+    match &jwk.params {
+        JWKParams::OKP(okp) => Some(&okp.curve),
+        JWKParams::EC(ec) => ec.curve.as_deref(),
+        _ => None,
+    }
+}
+
+fn curve(jwk: &JWK) -> Result<&str, JWEError> {
+    curve_opt(jwk).ok_or(JWEError::Key(Error::MissingCurve))
+}
+
+fn generate_ephemeral_key(curve: &str) -> Result<JWK, JWEError> {
+    Ok(match curve {
+        "X25519" => JWK::generate_x25519()?,
+        #[cfg(feature = "p256")]
+        "P-256" => JWK::generate_p256()?,
+        #[cfg(feature = "k256")]
+        "secp256k1" => JWK::generate_secp256k1()?,
+        _ => return Err(JWEError::UnsupportedCurve(curve.to_string())),
+    })
+}
+
+/// Compute the Diffie-Hellman shared secret of a private key and a public key.
+fn ecdh(private_key: &JWK, public_key: &JWK) -> Result<Vec<u8>, JWEError> {
+    match (&private_key.params, &public_key.params) {
+        (JWKParams::OKP(private_okp), JWKParams::OKP(public_okp)) => {
+            if private_okp.curve != "X25519" {
+                return Err(JWEError::UnsupportedCurve(private_okp.curve.to_string()));
+            }
+            if public_okp.curve != private_okp.curve {
+                return Err(JWEError::CurveMismatch);
+            }
+            Ok(private_key.x25519_diffie_hellman(public_key)?)
+        }
+        (JWKParams::EC(private_ec), JWKParams::EC(public_ec)) => {
+            let curve = private_ec.curve.as_deref().ok_or(Error::MissingCurve)?;
+            if public_ec.curve.as_deref() != Some(curve) {
+                return Err(JWEError::CurveMismatch);
+            }
+            match curve {
+                #[cfg(feature = "p256")]
+                "P-256" => {
+                    let secret = p256::SecretKey::try_from(private_ec)?;
+                    let public = p256::PublicKey::try_from(public_ec)?;
+                    let shared_secret = p256::elliptic_curve::ecdh::diffie_hellman(
+                        secret.to_secret_scalar(),
+                        public.as_affine(),
+                    );
+                    Ok(shared_secret.as_bytes().to_vec())
+                }
+                #[cfg(feature = "k256")]
+                "secp256k1" => {
+                    let secret = k256::SecretKey::try_from(private_ec)?;
+                    let public = k256::PublicKey::try_from(public_ec)?;
+                    let shared_secret = k256::elliptic_curve::ecdh::diffie_hellman(
+                        secret.to_secret_scalar(),
+                        public.as_affine(),
+                    );
+                    Ok(shared_secret.as_bytes().to_vec())
+                }
+                _ => Err(JWEError::UnsupportedCurve(curve.to_string())),
+            }
+        }
+        (JWKParams::OKP(_), _) | (JWKParams::EC(_), _) => Err(JWEError::CurveMismatch),
+        _ => Err(JWEError::Key(Error::UnsupportedKeyType)),
+    }
+}
+
+/// Concat KDF ([NIST SP 800-56A][kdf], [RFC 7518 Section 4.6.2][rfc]) with SHA-256, deriving a
+/// 256-bit key. For `ECDH-1PU` key wrapping, the authentication tag of the content encryption is
+/// appended to `SuppPubInfo`.
+///
+/// [kdf]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-56Ar2.pdf
+/// [rfc]: https://www.rfc-editor.org/rfc/rfc7518#section-4.6.2
+fn concat_kdf(
+    z: &[u8],
+    algorithm_id: &str,
+    header: &Header,
+    cc_tag: Option<&[u8]>,
+) -> Result<Vec<u8>, JWEError> {
+    fn length_prefixed(data: &[u8]) -> Vec<u8> {
+        [&(data.len() as u32).to_be_bytes(), data].concat()
+    }
+    let apu = header
+        .agreement_party_u_info
+        .as_ref()
+        .map(|apu| &apu.0[..])
+        .unwrap_or_default();
+    let apv = header
+        .agreement_party_v_info
+        .as_ref()
+        .map(|apv| &apv.0[..])
+        .unwrap_or_default();
+    let mut other_info = [
+        length_prefixed(algorithm_id.as_bytes()),
+        length_prefixed(apu),
+        length_prefixed(apv),
+        ((CEK_LEN * 8) as u32).to_be_bytes().to_vec(),
+    ]
+    .concat();
+    if let Some(cc_tag) = cc_tag {
+        other_info.extend(length_prefixed(cc_tag));
+    }
+    // A single round of SHA-256 gives the 256 bits of key.
+    let round: u32 = 1;
+    let digest = crate::hash::sha256(&[&round.to_be_bytes(), z, &other_info].concat())?;
+    Ok(digest.to_vec())
+}
+
+fn encrypt_content(
+    enc: ContentEncryptionAlgorithm,
+    cek: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), JWEError> {
+    let mut buffer = plaintext.to_vec();
+    let tag = match enc {
+        ContentEncryptionAlgorithm::A256GCM => aes_gcm::Aes256Gcm::new_from_slice(cek)
+            .map_err(|_| JWEError::Encryption)?
+            .encrypt_in_place_detached(aes_gcm::Nonce::from_slice(iv), aad, &mut buffer)
+            .map_err(|_| JWEError::Encryption)?
+            .to_vec(),
+        ContentEncryptionAlgorithm::XC20P => {
+            chacha20poly1305::XChaCha20Poly1305::new_from_slice(cek)
+                .map_err(|_| JWEError::Encryption)?
+                .encrypt_in_place_detached(
+                    chacha20poly1305::XNonce::from_slice(iv),
+                    aad,
+                    &mut buffer,
+                )
+                .map_err(|_| JWEError::Encryption)?
+                .to_vec()
+        }
+    };
+    Ok((buffer, tag))
+}
+
+fn decrypt_content(
+    enc: ContentEncryptionAlgorithm,
+    cek: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    mut buffer: Vec<u8>,
+    tag: &[u8],
+) -> Result<Vec<u8>, JWEError> {
+    if iv.len() != enc.iv_len() || tag.len() != 16 {
+        return Err(JWEError::Decryption);
+    }
+    match enc {
+        ContentEncryptionAlgorithm::A256GCM => aes_gcm::Aes256Gcm::new_from_slice(cek)
+            .map_err(|_| JWEError::Decryption)?
+            .decrypt_in_place_detached(
+                aes_gcm::Nonce::from_slice(iv),
+                aad,
+                &mut buffer,
+                aes_gcm::Tag::from_slice(tag),
+            )
+            .map_err(|_| JWEError::Decryption)?,
+        ContentEncryptionAlgorithm::XC20P => {
+            chacha20poly1305::XChaCha20Poly1305::new_from_slice(cek)
+                .map_err(|_| JWEError::Decryption)?
+                .decrypt_in_place_detached(
+                    chacha20poly1305::XNonce::from_slice(iv),
+                    aad,
+                    &mut buffer,
+                    chacha20poly1305::Tag::from_slice(tag),
+                )
+                .map_err(|_| JWEError::Decryption)?
+        }
+    }
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+
+    fn x25519_recipient(key_id: &str) -> (JWK, RecipientKey) {
+        let key = JWK::generate_x25519().unwrap();
+        let recipient = RecipientKey {
+            key_id: Some(key_id.to_string()),
+            jwk: key.to_public(),
+        };
+        (key, recipient)
+    }
+
+    #[test]
+    fn ecdh_es_compact() {
+        let (key, recipient) = x25519_recipient("did:example:bob#key-x25519-1");
+        for enc in [
+            ContentEncryptionAlgorithm::A256GCM,
+            ContentEncryptionAlgorithm::XC20P,
+        ] {
+            let header = Header {
+                algorithm: Some(KeyManagementAlgorithm::ECDHES),
+                encryption_algorithm: Some(enc),
+                ..Default::default()
+            };
+            let jwe = encrypt(b"hello", header, std::slice::from_ref(&recipient), None).unwrap();
+            let compact = jwe.to_compact().unwrap();
+            let jwe = JWE::from_compact(&compact).unwrap();
+            assert_eq!(
+                jwe.header().unwrap().key_id.as_deref(),
+                Some("did:example:bob#key-x25519-1")
+            );
+            assert_eq!(jwe.decrypt(&key, None, None).unwrap(), b"hello");
+
+            let other_key = JWK::generate_x25519().unwrap();
+            assert!(jwe.decrypt(&other_key, None, None).is_err());
+        }
+
+        // Direct key agreement has a single recipient.
+        let (_, recipient2) = x25519_recipient("did:example:bob#key-x25519-2");
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHES),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            ..Default::default()
+        };
+        assert!(encrypt(b"hello", header, &[recipient, recipient2], None).is_err());
+    }
+
+    #[test]
+    fn ecdh_es_key_wrap_multiple_recipients() {
+        let (key1, recipient1) = x25519_recipient("did:example:bob#key-x25519-1");
+        let (key2, recipient2) = x25519_recipient("did:example:bob#key-x25519-2");
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::XC20P),
+            type_: Some("application/didcomm-encrypted+json".to_string()),
+            ..Default::default()
+        };
+        let jwe = encrypt(b"hello", header, &[recipient1, recipient2], None).unwrap();
+        assert_eq!(jwe.recipients.len(), 2);
+        assert!(jwe.to_compact().is_err());
+        let json = serde_json::to_string(&jwe).unwrap();
+        let jwe: JWE = serde_json::from_str(&json).unwrap();
+        assert_eq!(jwe.decrypt(&key1, None, None).unwrap(), b"hello");
+        assert_eq!(
+            jwe.decrypt(&key2, Some("did:example:bob#key-x25519-2"), None)
+                .unwrap(),
+            b"hello"
+        );
+        assert!(jwe
+            .decrypt(&key2, Some("did:example:bob#key-x25519-1"), None)
+            .is_err());
+    }
+
+    #[test]
+    fn ecdh_1pu_key_wrap() {
+        let sender_key = JWK::generate_x25519().unwrap();
+        let (key1, recipient1) = x25519_recipient("did:example:bob#key-x25519-1");
+        let (key2, recipient2) = x25519_recipient("did:example:bob#key-x25519-2");
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDH1PUA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            sender_key_id: Some("did:example:alice#key-x25519-1".to_string()),
+            ..Default::default()
+        };
+        let recipients = [recipient1, recipient2];
+        assert!(encrypt(b"hello", header.clone(), &recipients, None).is_err());
+        let jwe = encrypt(b"hello", header, &recipients, Some(&sender_key)).unwrap();
+        let header = jwe.header().unwrap();
+        assert_eq!(
+            header.agreement_party_u_info,
+            Some(Base64urlUInt(b"did:example:alice#key-x25519-1".to_vec()))
+        );
+        let sender_public_key = sender_key.to_public();
+        for key in [&key1, &key2] {
+            assert_eq!(
+                jwe.decrypt(key, None, Some(&sender_public_key)).unwrap(),
+                b"hello"
+            );
+        }
+        // The sender key is authenticated.
+        assert!(jwe.decrypt(&key1, None, None).is_err());
+        let other_sender_key = JWK::generate_x25519().unwrap().to_public();
+        assert!(jwe.decrypt(&key1, None, Some(&other_sender_key)).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "p256", feature = "k256"))]
+    fn ecdh_nist_and_secp256k1() {
+        for key in [
+            JWK::generate_p256().unwrap(),
+            JWK::generate_secp256k1().unwrap(),
+        ] {
+            let recipient = RecipientKey {
+                key_id: None,
+                jwk: key.to_public(),
+            };
+            for alg in [
+                KeyManagementAlgorithm::ECDHES,
+                KeyManagementAlgorithm::ECDHESA256KW,
+            ] {
+                let header = Header {
+                    algorithm: Some(alg),
+                    encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+                    ..Default::default()
+                };
+                let jwe =
+                    encrypt(b"hello", header, std::slice::from_ref(&recipient), None).unwrap();
+                assert_eq!(jwe.decrypt(&key, None, None).unwrap(), b"hello");
+            }
+        }
+        // Keys must use the same curve.
+        let recipients = [
+            RecipientKey {
+                key_id: None,
+                jwk: JWK::generate_p256().unwrap().to_public(),
+            },
+            x25519_recipient("did:example:bob#key-x25519-1").1,
+        ];
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            ..Default::default()
+        };
+        assert!(encrypt(b"hello", header, &recipients, None).is_err());
+    }
+
+    #[test]
+    fn critical_headers() {
+        let (key, recipient) = x25519_recipient("did:example:bob#key-x25519-1");
+        let mut additional_parameters = BTreeMap::new();
+        additional_parameters.insert("exp".to_string(), serde_json::json!(1363284000));
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            critical: Some(vec!["exp".to_string()]),
+            additional_parameters,
+            ..Default::default()
+        };
+        let jwe = encrypt(b"hello", header, std::slice::from_ref(&recipient), None).unwrap();
+        assert!(matches!(
+            jwe.decrypt(&key, None, None),
+            Err(JWEError::UnknownCriticalHeader(name)) if name == "exp"
+        ));
+
+        // Critical header parameters must be protected.
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            ..Default::default()
+        };
+        let mut jwe = encrypt(b"hello", header, &[recipient], None).unwrap();
+        assert_eq!(jwe.decrypt(&key, None, None).unwrap(), b"hello");
+        jwe.unprotected = Some(Header {
+            critical: Some(vec!["exp".to_string()]),
+            ..Default::default()
+        });
+        assert!(matches!(
+            jwe.decrypt(&key, None, None),
+            Err(JWEError::InvalidCriticalHeader)
+        ));
+    }
+
+    #[test]
+    fn flattened_json() {
+        let (key, recipient) = x25519_recipient("did:example:bob#key-x25519-1");
+        let header = Header {
+            algorithm: Some(KeyManagementAlgorithm::ECDHESA256KW),
+            encryption_algorithm: Some(ContentEncryptionAlgorithm::A256GCM),
+            ..Default::default()
+        };
+        let jwe = encrypt(b"hello", header, &[recipient], None).unwrap();
+        let mut json = serde_json::to_value(&jwe).unwrap();
+        let object = json.as_object_mut().unwrap();
+        let recipient = object.remove("recipients").unwrap()[0].clone();
+        object.insert("header".to_string(), recipient["header"].clone());
+        object.insert(
+            "encrypted_key".to_string(),
+            recipient["encrypted_key"].clone(),
+        );
+        let flattened: JWE = serde_json::from_value(json).unwrap();
+        assert_eq!(flattened, jwe);
+        assert_eq!(flattened.decrypt(&key, None, None).unwrap(), b"hello");
+    }
+
+    #[async_std::test]
+    async fn resolve_key_agreement() {
+        let keys = resolve_key_agreement_keys("did:example:x25519", &DIDExample)
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(
+            keys[0].key_id.as_deref(),
+            Some("did:example:x25519#key-x25519")
+        );
+        let keys = resolve_key_agreement_keys("did:example:x25519#key-x25519", &DIDExample)
+            .await
+            .unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(
+            resolve_key_agreement_keys("did:example:x25519#key1", &DIDExample)
+                .await
+                .is_err()
+        );
+    }
+}
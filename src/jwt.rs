@@ -2,7 +2,8 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::error::Error;
-use crate::jwk::{Algorithm, JWK};
+use crate::jwk::{Algorithm, JWKSet, JWK};
+use crate::signer::Signer;
 
 // RFC 7519 - JSON Web Token (JWT)
 
@@ -15,6 +16,15 @@ pub fn encode_sign<Claims: Serialize>(
     crate::jws::encode_sign(algorithm, &payload, key)
 }
 
+pub async fn encode_sign_with_signer<Claims: Serialize>(
+    algorithm: Algorithm,
+    claims: &Claims,
+    signer: &dyn Signer,
+) -> Result<String, Error> {
+    let payload = serde_json::to_string(claims)?;
+    crate::jws::encode_sign_with_signer(algorithm, &payload, signer).await
+}
+
 pub fn encode_unsigned<Claims: Serialize>(claims: &Claims) -> Result<String, Error> {
     let payload = serde_json::to_string(claims)?;
     crate::jws::encode_unsigned(&payload)
@@ -26,6 +36,17 @@ pub fn decode_verify<Claims: DeserializeOwned>(jwt: &str, key: &JWK) -> Result<C
     Ok(claims)
 }
 
+/// Verify a JWT with a key of a JWK Set, selected using the header's key ID (`kid`), or else by
+/// algorithm.
+pub fn decode_verify_jwk_set<Claims: DeserializeOwned>(
+    jwt: &str,
+    jwk_set: &JWKSet,
+) -> Result<Claims, Error> {
+    let (_header, payload) = crate::jws::decode_verify_jwk_set(jwt, jwk_set)?;
+    let claims = serde_json::from_slice(&payload)?;
+    Ok(claims)
+}
+
 // for vc-test-suite
 pub fn decode_unverified<Claims: DeserializeOwned>(jwt: &str) -> Result<Claims, Error> {
     let (_header, payload) = crate::jws::decode_unverified(jwt)?;
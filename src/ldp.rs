@@ -1,6 +1,7 @@
 use std::collections::HashMap as Map;
 #[cfg(feature = "keccak-hash")]
 use std::convert::TryFrom;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard};
 
 use async_trait::async_trait;
 use chrono::prelude::*;
@@ -10,7 +11,11 @@ const SPSIG_PREFIX: [u8; 5] = [13, 115, 101, 19, 63];
 const P2SIG_PREFIX: [u8; 4] = [54, 240, 44, 52];
 
 // use crate::did::{VerificationMethod, VerificationMethodMap};
+use crate::bbs_ldp::{is_bls12381g2, BbsBlsSignature2020, BbsBlsSignatureProof2020};
 use crate::caip10::BlockchainAccountId;
+use crate::data_integrity::{
+    get_builtin_cryptosuite, Cryptosuite, DataIntegrityProof, DATA_INTEGRITY_PROOF_TYPE,
+};
 use crate::did::{Resource, VerificationMethodMap};
 use crate::did_resolve::{dereference, Content, DIDResolver, DereferencingInputMetadata};
 #[cfg(feature = "keccak-hash")]
@@ -22,6 +27,7 @@ use crate::jwk::Base64urlUInt;
 use crate::jwk::{Algorithm, Params as JWKParams, JWK};
 use crate::jws::Header;
 use crate::rdf::DataSet;
+use crate::signer::Signer;
 use crate::urdna2015;
 use crate::vc::{LinkedDataProofOptions, Proof, ProofPurpose, URI};
 use serde::{Deserialize, Serialize};
@@ -58,9 +64,58 @@ lazy_static! {
         let context_str = ssi_contexts::ALEOVM;
         serde_json::from_str(context_str).unwrap()
     };
+    /// JSON-LD context for EcdsaSecp384r1Signature2019, which the credentials and security
+    /// vocabulary contexts do not define. The term is defined like EcdsaSecp256r1Signature2019.
+    pub static ref ECDSA_SECP384R1_2019_CONTEXT: Value = serde_json::json!({
+        "EcdsaSecp384r1Signature2019": {
+            "@id": "https://w3id.org/security#EcdsaSecp384r1Signature2019",
+            "@context": {
+                "@version": 1.1,
+                "@protected": true,
+                "id": "@id",
+                "type": "@type",
+                "sec": "https://w3id.org/security#",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                "challenge": "sec:challenge",
+                "created": {"@id": "http://purl.org/dc/terms/created", "@type": "xsd:dateTime"},
+                "domain": "sec:domain",
+                "expires": {"@id": "sec:expiration", "@type": "xsd:dateTime"},
+                "jws": "sec:jws",
+                "nonce": "sec:nonce",
+                "proofPurpose": {
+                    "@id": "sec:proofPurpose",
+                    "@type": "@vocab",
+                    "@context": {
+                        "@version": 1.1,
+                        "@protected": true,
+                        "id": "@id",
+                        "type": "@type",
+                        "sec": "https://w3id.org/security#",
+                        "assertionMethod": {
+                            "@id": "sec:assertionMethod",
+                            "@type": "@id",
+                            "@container": "@set"
+                        },
+                        "authentication": {
+                            "@id": "sec:authenticationMethod",
+                            "@type": "@id",
+                            "@container": "@set"
+                        }
+                    }
+                },
+                "proofValue": "sec:proofValue",
+                "verificationMethod": {"@id": "sec:verificationMethod", "@type": "@id"}
+            }
+        }
+    });
+}
+
+/// Get a proof suite by proof type, from the [global](ProofSuiteRegistry::global) registry.
+pub fn get_proof_suite(proof_type: &str) -> Result<&'static (dyn ProofSuite + Sync), Error> {
+    ProofSuiteRegistry::read_global().get(proof_type)
 }
 
-pub fn get_proof_suite(proof_type: &str) -> Result<&(dyn ProofSuite + Sync), Error> {
+fn get_builtin_proof_suite(proof_type: &str) -> Result<&'static (dyn ProofSuite + Sync), Error> {
     Ok(match proof_type {
         "RsaSignature2018" => &RsaSignature2018,
         "Ed25519Signature2018" => &Ed25519Signature2018,
@@ -102,14 +157,21 @@ pub fn get_proof_suite(proof_type: &str) -> Result<&(dyn ProofSuite + Sync), Err
         }
         "JsonWebSignature2020" => &JsonWebSignature2020,
         "EcdsaSecp256r1Signature2019" => &EcdsaSecp256r1Signature2019,
+        "EcdsaSecp384r1Signature2019" => &EcdsaSecp384r1Signature2019,
+        "DataIntegrityProof" => &DataIntegrityProof,
+        "BbsBlsSignature2020" => &BbsBlsSignature2020,
+        "BbsBlsSignatureProof2020" => &BbsBlsSignatureProof2020,
         _ => return Err(Error::ProofTypeNotImplemented),
     })
 }
 
-fn pick_proof_suite<'a, 'b>(
+fn pick_builtin_proof_suite(
     jwk: &JWK,
-    verification_method: Option<&'a URI>,
-) -> Result<&'b (dyn ProofSuite + Sync), Error> {
+    verification_method: Option<&URI>,
+) -> Result<&'static (dyn ProofSuite + Sync), Error> {
+    if is_bls12381g2(jwk) {
+        return Ok(&BbsBlsSignature2020);
+    }
     let algorithm = jwk.get_algorithm().ok_or(Error::MissingAlgorithm)?;
     Ok(match algorithm {
         Algorithm::RS256 => &RsaSignature2018,
@@ -195,6 +257,143 @@ fn pick_proof_suite<'a, 'b>(
     })
 }
 
+/// Proof suites available for creating and verifying linked data proofs.
+///
+/// Suites are keyed by proof type, and Data Integrity cryptosuites by name. A proof type can also
+/// be registered as the default for keys of an algorithm, for when proof options do not specify
+/// one. Registered suites take precedence over the built-in ones, which remain available.
+///
+/// Credentials and presentations are only verified with a registered proof type if the IRI it
+/// expands to in JSON-LD is registered too, with [`register_type_iri`](Self::register_type_iri).
+///
+/// [`LinkedDataProofs`], and so credential, presentation and capability proofs, use the
+/// [global](Self::global) registry:
+///
+/// ```ignore
+/// ProofSuiteRegistry::global()
+///     .write()
+///     .unwrap()
+///     .register("ExampleSignature2023", &ExampleSignature2023);
+/// ```
+#[derive(Clone, Default)]
+pub struct ProofSuiteRegistry {
+    suites: Map<String, &'static (dyn ProofSuite + Sync)>,
+    cryptosuites: Map<String, &'static (dyn Cryptosuite + Sync)>,
+    algorithms: Map<Algorithm, String>,
+    type_iris: Map<String, Vec<String>>,
+}
+
+lazy_static! {
+    static ref PROOF_SUITE_REGISTRY: RwLock<ProofSuiteRegistry> =
+        RwLock::new(ProofSuiteRegistry::default());
+}
+
+impl ProofSuiteRegistry {
+    /// Registry used by [`LinkedDataProofs`], [`get_proof_suite`] and
+    /// [`get_cryptosuite`](crate::data_integrity::get_cryptosuite).
+    pub fn global() -> &'static RwLock<ProofSuiteRegistry> {
+        &PROOF_SUITE_REGISTRY
+    }
+
+    pub(crate) fn read_global() -> RwLockReadGuard<'static, ProofSuiteRegistry> {
+        // The registry is only written by inserting into maps, so it stays consistent even if a
+        // writer panicked.
+        PROOF_SUITE_REGISTRY
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Register a proof suite for a proof type, returning the suite it replaces, if any.
+    pub fn register(
+        &mut self,
+        proof_type: &str,
+        suite: &'static (dyn ProofSuite + Sync),
+    ) -> Option<&'static (dyn ProofSuite + Sync)> {
+        self.suites.insert(proof_type.to_string(), suite)
+    }
+
+    /// Register a Data Integrity cryptosuite under its name, returning the cryptosuite it
+    /// replaces, if any.
+    pub fn register_cryptosuite(
+        &mut self,
+        cryptosuite: &'static (dyn Cryptosuite + Sync),
+    ) -> Option<&'static (dyn Cryptosuite + Sync)> {
+        self.cryptosuites
+            .insert(cryptosuite.name().to_string(), cryptosuite)
+    }
+
+    /// Use a proof type by default for keys of an algorithm.
+    pub fn register_algorithm(&mut self, algorithm: Algorithm, proof_type: &str) {
+        self.algorithms.insert(algorithm, proof_type.to_string());
+    }
+
+    /// Register the IRI that a proof type expands to in JSON-LD.
+    pub fn register_type_iri(&mut self, proof_type: &str, iri: &str) {
+        self.type_iris
+            .entry(proof_type.to_string())
+            .or_default()
+            .push(iri.to_string());
+    }
+
+    pub(crate) fn has_type_iri(&self, proof_type: &str, iri: &str) -> bool {
+        self.type_iris
+            .get(proof_type)
+            .is_some_and(|iris| iris.iter().any(|type_iri| type_iri == iri))
+    }
+
+    /// Get a proof suite by proof type.
+    pub fn get(&self, proof_type: &str) -> Result<&'static (dyn ProofSuite + Sync), Error> {
+        match self.suites.get(proof_type) {
+            Some(suite) => Ok(*suite),
+            None => get_builtin_proof_suite(proof_type),
+        }
+    }
+
+    /// Get a Data Integrity cryptosuite by name.
+    pub fn get_cryptosuite(&self, name: &str) -> Result<&'static (dyn Cryptosuite + Sync), Error> {
+        match self.cryptosuites.get(name) {
+            Some(cryptosuite) => Ok(*cryptosuite),
+            None => get_builtin_cryptosuite(name),
+        }
+    }
+
+    /// Pick a proof suite for a key and verification method.
+    pub fn pick(
+        &self,
+        key: &JWK,
+        verification_method: Option<&URI>,
+    ) -> Result<&'static (dyn ProofSuite + Sync), Error> {
+        if let Some(proof_type) = key
+            .get_algorithm()
+            .and_then(|algorithm| self.algorithms.get(&algorithm))
+        {
+            return self.get(proof_type);
+        }
+        pick_builtin_proof_suite(key, verification_method)
+    }
+
+    /// Get the proof suite for proof options: by proof type if set, otherwise a Data Integrity
+    /// proof if a cryptosuite is set, otherwise by key.
+    fn for_options(
+        &self,
+        options: &LinkedDataProofOptions,
+        key: &JWK,
+    ) -> Result<&'static (dyn ProofSuite + Sync), Error> {
+        // Use type property if present
+        if let Some(ref type_) = options.type_ {
+            self.get(type_)
+        }
+        // A cryptosuite implies a Data Integrity proof.
+        else if options.cryptosuite.is_some() {
+            self.get(DATA_INTEGRITY_PROOF_TYPE)
+        }
+        // Otherwise pick proof type based on key and options.
+        else {
+            self.pick(key, options.verification_method.as_ref())
+        }
+    }
+}
+
 // Get current time to millisecond precision if possible
 pub fn now_ms() -> DateTime<Utc> {
     let datetime = Utc::now();
@@ -230,7 +429,7 @@ pub trait ProofSuite {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error>;
 
@@ -373,19 +572,14 @@ impl LinkedDataProofs {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        // Use type property if present
-        let suite = if let Some(ref type_) = options.type_ {
-            get_proof_suite(type_)?
-        }
-        // Otherwise pick proof type based on key and options.
-        else {
-            pick_proof_suite(key, options.verification_method.as_ref())?
-        };
+        let public_key = key.public_key();
+        let suite = ProofSuiteRegistry::read_global().for_options(options, &public_key)?;
         let mut options = options.clone();
-        ensure_or_pick_verification_relationship(&mut options, document, key, resolver).await?;
+        ensure_or_pick_verification_relationship(&mut options, document, &public_key, resolver)
+            .await?;
         suite
             .sign(document, &options, resolver, context_loader, key, extra_proof_properties)
             .await
@@ -401,14 +595,7 @@ impl LinkedDataProofs {
         public_key: &JWK,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<ProofPreparation, Error> {
-        // Use type property if present
-        let suite = if let Some(ref type_) = options.type_ {
-            get_proof_suite(type_)?
-        }
-        // Otherwise pick proof type based on key and options.
-        else {
-            pick_proof_suite(public_key, options.verification_method.as_ref())?
-        };
+        let suite = ProofSuiteRegistry::read_global().for_options(options, public_key)?;
         let mut options = options.clone();
         ensure_or_pick_verification_relationship(&mut options, document, public_key, resolver)
             .await?;
@@ -468,7 +655,7 @@ pub async fn resolve_vm(
     Ok(vm)
 }
 
-async fn to_jws_payload(
+pub(crate) async fn to_jws_payload(
     document: &(dyn LinkedDataDocument + Sync),
     proof: &Proof,
     context_loader: &mut ContextLoader,
@@ -494,12 +681,12 @@ async fn sign(
     options: &LinkedDataProofOptions,
     _resolver: &dyn DIDResolver,
     context_loader: &mut ContextLoader,
-    key: &JWK,
+    key: &dyn Signer,
     type_: &str,
     algorithm: Algorithm,
     extra_proof_properties: Option<Map<String, Value>>,
 ) -> Result<Proof, Error> {
-    if let Some(key_algorithm) = key.algorithm {
+    if let Some(key_algorithm) = key.public_key().algorithm {
         if key_algorithm != algorithm {
             return Err(Error::AlgorithmMismatch);
         }
@@ -513,12 +700,16 @@ async fn sign(
 async fn sign_proof(
     document: &(dyn LinkedDataDocument + Sync),
     mut proof: Proof,
-    key: &JWK,
+    key: &dyn Signer,
     algorithm: Algorithm,
     context_loader: &mut ContextLoader,
 ) -> Result<Proof, Error> {
     let message = to_jws_payload(document, &proof, context_loader).await?;
-    let jws = crate::jws::detached_sign_unencoded_payload(algorithm, &message, key)?;
+    let (header, signing_input) =
+        crate::jws::prepare_detached_unencoded_payload(algorithm, &message)?;
+    let sig = key.sign(algorithm, &signing_input).await?;
+    let sig_b64 = base64::encode_config(sig, base64::URL_SAFE_NO_PAD);
+    let jws = crate::jws::complete_sign_unencoded_payload(header, &sig_b64)?;
     proof.jws = Some(jws);
     Ok(proof)
 }
@@ -527,13 +718,13 @@ async fn sign_nojws(
     document: &(dyn LinkedDataDocument + Sync),
     options: &LinkedDataProofOptions,
     context_loader: &mut ContextLoader,
-    key: &JWK,
+    key: &dyn Signer,
     type_: &str,
     algorithm: Algorithm,
     context_uri: &str,
     extra_proof_properties: Option<Map<String, Value>>,
 ) -> Result<Proof, Error> {
-    if let Some(key_algorithm) = key.algorithm {
+    if let Some(key_algorithm) = key.public_key().algorithm {
         if key_algorithm != algorithm {
             return Err(Error::AlgorithmMismatch);
         }
@@ -545,7 +736,7 @@ async fn sign_nojws(
         proof.context = serde_json::json!([context_uri]);
     }
     let message = to_jws_payload(document, &proof, context_loader).await?;
-    let sig = crate::jws::sign_bytes(algorithm, &message, key)?;
+    let sig = key.sign(algorithm, &message).await?;
     let sig_multibase = multibase::encode(multibase::Base::Base58Btc, sig);
     proof.proof_value = Some(sig_multibase);
     Ok(proof)
@@ -677,7 +868,7 @@ impl ProofSuite for RsaSignature2018 {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         sign(
@@ -741,7 +932,7 @@ impl ProofSuite for Ed25519Signature2018 {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         sign(
@@ -805,7 +996,7 @@ impl ProofSuite for Ed25519Signature2020 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         sign_nojws(
@@ -871,7 +1062,7 @@ impl ProofSuite for EcdsaSecp256k1Signature2019 {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         sign(
@@ -935,10 +1126,10 @@ impl ProofSuite for EcdsaSecp256k1RecoverySignature2020 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        if let Some(key_algorithm) = key.algorithm {
+        if let Some(key_algorithm) = key.public_key().algorithm {
             if key_algorithm != Algorithm::ES256KR {
                 return Err(Error::AlgorithmMismatch);
             }
@@ -1034,15 +1225,15 @@ impl ProofSuite for Ed25519BLAKE2BDigestSize20Base58CheckEncodedSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        if let Some(key_algorithm) = key.algorithm {
+        if let Some(key_algorithm) = key.public_key().algorithm {
             if key_algorithm != Algorithm::EdBlake2b {
                 return Err(Error::AlgorithmMismatch);
             }
         }
-        let jwk_value = serde_json::to_value(key.to_public())?;
+        let jwk_value = serde_json::to_value(key.public_key())?;
         // This proof type must contain the public key, because the DID is based on the hash of the
         // public key, and the public key is not otherwise recoverable.
         let mut props = extra_proof_properties.clone();
@@ -1133,15 +1324,15 @@ impl ProofSuite for P256BLAKE2BDigestSize20Base58CheckEncodedSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        if let Some(key_algorithm) = key.algorithm {
+        if let Some(key_algorithm) = key.public_key().algorithm {
             if key_algorithm != Algorithm::ESBlake2b {
                 return Err(Error::AlgorithmMismatch);
             }
         }
-        let jwk_value = serde_json::to_value(key.to_public())?;
+        let jwk_value = serde_json::to_value(key.public_key())?;
         // This proof type must contain the public key, because the DID is based on the hash of the
         // public key, and the public key is not otherwise recoverable.
         let mut props = extra_proof_properties.clone();
@@ -1232,10 +1423,9 @@ impl ProofSuite for Eip712Signature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        use k256::ecdsa::signature::Signer;
         let mut proof = Proof {
             context: serde_json::json!([EIP712VM_CONTEXT.clone()]),
             ..Proof::new("Eip712Signature2021")
@@ -1244,14 +1434,7 @@ impl ProofSuite for Eip712Signature2021 {
         };
         let typed_data = TypedData::from_document_and_options(document, &proof, context_loader).await?;
         let bytes = typed_data.bytes()?;
-        let ec_params = match &key.params {
-            JWKParams::EC(ec) => ec,
-            _ => return Err(Error::KeyTypeNotImplemented),
-        };
-        let secret_key = k256::SecretKey::try_from(ec_params)?;
-        let signing_key = k256::ecdsa::SigningKey::from(secret_key);
-        let sig: k256::ecdsa::recoverable::Signature = signing_key.try_sign(&bytes)?;
-        let sig_bytes = &mut sig.as_ref().to_vec();
+        let sig_bytes = &mut key.sign(Algorithm::ESKeccakKR, &bytes).await?;
         // Recovery ID starts at 27 instead of 0.
         sig_bytes[64] += 27;
         let sig_hex = crate::keccak_hash::bytes_to_lowerhex(sig_bytes);
@@ -1356,10 +1539,9 @@ impl ProofSuite for EthereumEip712Signature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         _context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        use k256::ecdsa::signature::Signer;
         // TODO: conform to spec: no domain
         let mut props = extra_proof_properties.clone();
         if let Some(ref eip712_domain) = options.eip712_domain {
@@ -1376,14 +1558,7 @@ impl ProofSuite for EthereumEip712Signature2021 {
         };
         let typed_data = TypedData::from_document_and_options_json(document, &proof).await?;
         let bytes = typed_data.bytes()?;
-        let ec_params = match &key.params {
-            JWKParams::EC(ec) => ec,
-            _ => return Err(Error::KeyTypeNotImplemented),
-        };
-        let secret_key = k256::SecretKey::try_from(ec_params)?;
-        let signing_key = k256::ecdsa::SigningKey::from(secret_key);
-        let sig: k256::ecdsa::recoverable::Signature = signing_key.try_sign(&bytes)?;
-        let sig_bytes = &mut sig.as_ref().to_vec();
+        let sig_bytes = &mut key.sign(Algorithm::ESKeccakKR, &bytes).await?;
         // Recovery ID starts at 27 instead of 0.
         sig_bytes[64] += 27;
         let sig_hex = crate::keccak_hash::bytes_to_lowerhex(sig_bytes);
@@ -1493,10 +1668,9 @@ impl ProofSuite for EthereumPersonalSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        use k256::ecdsa::signature::Signer;
         let mut proof = Proof {
             context: serde_json::json!([EPSIG_CONTEXT.clone()]),
             ..Proof::new("EthereumPersonalSignature2021")
@@ -1505,14 +1679,7 @@ impl ProofSuite for EthereumPersonalSignature2021 {
         };
         let signing_string = string_from_document_and_options(document, &proof, context_loader).await?;
         let hash = crate::keccak_hash::prefix_personal_message(&signing_string);
-        let ec_params = match &key.params {
-            JWKParams::EC(ec) => ec,
-            _ => return Err(Error::KeyTypeNotImplemented),
-        };
-        let secret_key = k256::SecretKey::try_from(ec_params)?;
-        let signing_key = k256::ecdsa::SigningKey::from(secret_key);
-        let sig: k256::ecdsa::recoverable::Signature = signing_key.try_sign(&hash)?;
-        let sig_bytes = &mut sig.as_ref().to_vec();
+        let sig_bytes = &mut key.sign(Algorithm::ESKeccakKR, &hash).await?;
         // Recovery ID starts at 27 instead of 0.
         sig_bytes[64] += 27;
         let sig_hex = crate::keccak_hash::bytes_to_lowerhex(sig_bytes);
@@ -1662,11 +1829,11 @@ impl ProofSuite for TezosSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        let algorithm = key.get_algorithm().ok_or(Error::MissingAlgorithm)?;
-        let jwk_value = serde_json::to_value(key.to_public())?;
+        let algorithm = key.algorithm().ok_or(Error::MissingAlgorithm)?;
+        let jwk_value = serde_json::to_value(key.public_key())?;
         let mut props = extra_proof_properties.clone();
         props
             .get_or_insert(Map::new())
@@ -1678,7 +1845,7 @@ impl ProofSuite for TezosSignature2021 {
                 .with_properties(props)
         };
         let micheline = micheline_from_document_and_options(document, &proof, context_loader).await?;
-        let sig = crate::jws::sign_bytes(algorithm, &micheline, key)?;
+        let sig = key.sign(algorithm, &micheline).await?;
         let mut sig_prefixed = Vec::new();
         let prefix: &[u8] = match algorithm {
             Algorithm::EdBlake2b => &EDSIG_PREFIX,
@@ -1803,11 +1970,11 @@ impl ProofSuite for TezosJcsSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        let algorithm = key.get_algorithm().ok_or(Error::MissingAlgorithm)?;
-        let tzpk = crate::tzkey::jwk_to_tezos_key(&key.to_public())?;
+        let algorithm = key.algorithm().ok_or(Error::MissingAlgorithm)?;
+        let tzpk = crate::tzkey::jwk_to_tezos_key(&key.public_key())?;
         let pkmb = "z".to_string() + &tzpk;
         let mut props = extra_proof_properties.clone();
         props
@@ -1820,7 +1987,7 @@ impl ProofSuite for TezosJcsSignature2021 {
                 .with_properties(props)
         };
         let micheline = micheline_from_document_and_options(document, &proof, context_loader).await?;
-        let sig = crate::jws::sign_bytes(algorithm, &micheline, key)?;
+        let sig = key.sign(algorithm, &micheline).await?;
         let mut sig_prefixed = Vec::new();
         let prefix: &[u8] = match algorithm {
             Algorithm::EdBlake2b => &EDSIG_PREFIX,
@@ -1960,7 +2127,7 @@ impl ProofSuite for SolanaSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         let mut proof = Proof {
@@ -1972,7 +2139,7 @@ impl ProofSuite for SolanaSignature2021 {
         let message = to_jws_payload(document, &proof, context_loader).await?;
         let tx = crate::soltx::LocalSolanaTransaction::with_message(&message);
         let bytes = tx.to_bytes();
-        let sig = crate::jws::sign_bytes(Algorithm::EdDSA, &bytes, key)?;
+        let sig = key.sign(Algorithm::EdDSA, &bytes).await?;
         let sig_b58 = bs58::encode(&sig).into_string();
         proof.proof_value = Some(sig_b58);
         Ok(proof)
@@ -2105,7 +2272,7 @@ impl ProofSuite for AleoSignature2021 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         let has_context = document_has_context(document, "TODO:uploadAleoVMContextSomewhere")?;
@@ -2120,7 +2287,8 @@ impl ProofSuite for AleoSignature2021 {
                 .with_properties(extra_proof_properties)
         };
         let message = to_jws_payload(document, &proof, context_loader).await?;
-        let sig = crate::aleo::sign(&message, &key)?;
+        let private_key = key.private_key().ok_or(Error::MissingPrivateKey)?;
+        let sig = crate::aleo::sign(&message, private_key)?;
         let sig_mb = multibase::encode(multibase::Base::Base58Btc, sig);
         proof.proof_value = Some(sig_mb);
         Ok(proof)
@@ -2211,7 +2379,7 @@ impl ProofSuite for EcdsaSecp256r1Signature2019 {
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
         sign(
@@ -2265,8 +2433,87 @@ impl ProofSuite for EcdsaSecp256r1Signature2019 {
     }
 }
 
+/// EcdsaSecp384r1Signature2019 proof suite, signing with ES384. Requires the `openssl` feature.
+pub struct EcdsaSecp384r1Signature2019;
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ProofSuite for EcdsaSecp384r1Signature2019 {
+    async fn sign(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        key: &dyn Signer,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<Proof, Error> {
+        if let Some(key_algorithm) = key.public_key().algorithm {
+            if key_algorithm != Algorithm::ES384 {
+                return Err(Error::AlgorithmMismatch);
+            }
+        }
+        let proof = Proof {
+            context: ECDSA_SECP384R1_2019_CONTEXT.clone(),
+            ..Proof::new("EcdsaSecp384r1Signature2019")
+                .with_options(options)
+                .with_properties(extra_proof_properties)
+        };
+        sign_proof(document, proof, key, Algorithm::ES384, context_loader).await
+    }
+    async fn prepare(
+        &self,
+        document: &(dyn LinkedDataDocument + Sync),
+        options: &LinkedDataProofOptions,
+        _resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        _public_key: &JWK,
+        extra_proof_properties: Option<Map<String, Value>>,
+    ) -> Result<ProofPreparation, Error> {
+        let proof = Proof {
+            context: ECDSA_SECP384R1_2019_CONTEXT.clone(),
+            ..Proof::new("EcdsaSecp384r1Signature2019")
+                .with_options(options)
+                .with_properties(extra_proof_properties)
+        };
+        prepare_proof(document, proof, Algorithm::ES384, context_loader).await
+    }
+    async fn verify(
+        &self,
+        proof: &Proof,
+        document: &(dyn LinkedDataDocument + Sync),
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> Result<VerificationWarnings, Error> {
+        let jws = proof.jws.as_ref().ok_or(Error::MissingProofSignature)?;
+        let verification_method = proof
+            .verification_method
+            .as_ref()
+            .ok_or(Error::MissingVerificationMethod)?;
+        let (header_b64, signature_b64) = crate::jws::split_detached_jws(jws)?;
+        let message = to_jws_payload(document, proof, context_loader).await?;
+        let crate::jws::DecodedJWS {
+            header,
+            signing_input,
+            payload: _,
+            signature,
+        } = crate::jws::decode_jws_parts(header_b64, &message, signature_b64)?;
+        if header.algorithm != Algorithm::ES384 {
+            return Err(Error::AlgorithmMismatch);
+        }
+        let key = resolve_key(verification_method, resolver).await?;
+        crate::jws::verify_bytes_warnable(header.algorithm, &signing_input, &key, &signature)
+    }
+    async fn complete(
+        &self,
+        preparation: ProofPreparation,
+        signature: &str,
+    ) -> Result<Proof, Error> {
+        complete(preparation, signature).await
+    }
+}
+
 // Check if a linked data document has a given URI in its @context array.
-fn document_has_context(
+pub(crate) fn document_has_context(
     document: &(dyn LinkedDataDocument + Sync),
     context_uri: &str,
 ) -> Result<bool, Error> {
@@ -2286,11 +2533,11 @@ impl ProofSuite for JsonWebSignature2020 {
         options: &LinkedDataProofOptions,
         _resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
-        key: &JWK,
+        key: &dyn Signer,
         extra_proof_properties: Option<Map<String, Value>>,
     ) -> Result<Proof, Error> {
-        let algorithm = key.get_algorithm().ok_or(Error::MissingAlgorithm)?;
-        self.validate_key_and_algorithm(key, algorithm)?;
+        let algorithm = key.algorithm().ok_or(Error::MissingAlgorithm)?;
+        self.validate_key_and_algorithm(&key.public_key(), algorithm)?;
         let has_context = document_has_context(document, crate::jsonld::W3ID_JWS2020_V1_CONTEXT)?;
         let proof = Proof {
             context: if has_context {
@@ -2460,6 +2707,123 @@ mod tests {
         }
     }
 
+    struct ExampleSignature2099;
+
+    #[async_trait]
+    impl ProofSuite for ExampleSignature2099 {
+        async fn sign(
+            &self,
+            _document: &(dyn LinkedDataDocument + Sync),
+            options: &LinkedDataProofOptions,
+            _resolver: &dyn DIDResolver,
+            _context_loader: &mut ContextLoader,
+            _key: &dyn Signer,
+            _extra_proof_properties: Option<Map<String, Value>>,
+        ) -> Result<Proof, Error> {
+            Ok(Proof {
+                proof_value: Some("example".to_string()),
+                ..Proof::new("ExampleSignature2099").with_options(options)
+            })
+        }
+        async fn prepare(
+            &self,
+            _document: &(dyn LinkedDataDocument + Sync),
+            _options: &LinkedDataProofOptions,
+            _resolver: &dyn DIDResolver,
+            _context_loader: &mut ContextLoader,
+            _public_key: &JWK,
+            _extra_proof_properties: Option<Map<String, Value>>,
+        ) -> Result<ProofPreparation, Error> {
+            Err(Error::NotImplemented)
+        }
+        async fn verify(
+            &self,
+            proof: &Proof,
+            _document: &(dyn LinkedDataDocument + Sync),
+            _resolver: &dyn DIDResolver,
+            _context_loader: &mut ContextLoader,
+        ) -> Result<VerificationWarnings, Error> {
+            match proof.proof_value.as_deref() {
+                Some("example") => Ok(Default::default()),
+                _ => Err(Error::InvalidSignature),
+            }
+        }
+        async fn complete(
+            &self,
+            _preparation: ProofPreparation,
+            _signature: &str,
+        ) -> Result<Proof, Error> {
+            Err(Error::NotImplemented)
+        }
+    }
+
+    #[async_std::test]
+    async fn registered_proof_suite() {
+        let key = JWK::generate_ed25519().unwrap();
+        let issue_options = LinkedDataProofOptions {
+            type_: Some("ExampleSignature2099".to_string()),
+            ..Default::default()
+        };
+        let doc = ExampleDocument;
+        let resolver = DIDExample;
+        let mut context_loader = crate::jsonld::ContextLoader::default();
+        assert!(get_proof_suite("ExampleSignature2099").is_err());
+
+        ProofSuiteRegistry::global()
+            .write()
+            .unwrap()
+            .register("ExampleSignature2099", &ExampleSignature2099);
+        let mut proof = LinkedDataProofs::sign(
+            &doc,
+            &issue_options,
+            &resolver,
+            &mut context_loader,
+            &key,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(proof.type_, "ExampleSignature2099");
+        LinkedDataProofs::verify(&proof, &doc, &resolver, &mut context_loader)
+            .await
+            .unwrap();
+        proof.proof_value = Some("tampered".to_string());
+        LinkedDataProofs::verify(&proof, &doc, &resolver, &mut context_loader)
+            .await
+            .unwrap_err();
+
+        // Built-in suites are still available.
+        assert!(get_proof_suite("Ed25519Signature2018").is_ok());
+    }
+
+    #[async_std::test]
+    async fn registered_algorithm() {
+        let key = JWK::generate_ed25519().unwrap();
+        let mut registry = ProofSuiteRegistry::default();
+        let options = LinkedDataProofOptions::default();
+        let doc = ExampleDocument;
+        let resolver = DIDExample;
+        let mut context_loader = crate::jsonld::ContextLoader::default();
+
+        registry.register("ExampleSignature2099", &ExampleSignature2099);
+        let proof = registry
+            .pick(&key, None)
+            .unwrap()
+            .sign(&doc, &options, &resolver, &mut context_loader, &key, None)
+            .await
+            .unwrap();
+        assert_eq!(proof.type_, "Ed25519Signature2018");
+
+        registry.register_algorithm(Algorithm::EdDSA, "ExampleSignature2099");
+        let proof = registry
+            .pick(&key, None)
+            .unwrap()
+            .sign(&doc, &options, &resolver, &mut context_loader, &key, None)
+            .await
+            .unwrap();
+        assert_eq!(proof.type_, "ExampleSignature2099");
+    }
+
     #[cfg(feature = "secp256k1")]
     #[async_std::test]
     async fn eip712vm() {
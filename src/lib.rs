@@ -10,12 +10,14 @@
 //! `ring`                | Use the [ring](https://crates.io/crates/ring) crate for RSA, Ed25519, and SHA-256 functionality. **Conflicts with `rsa`, `ed25519-dalek`, and `sha` features.**
 //! `rsa`                 | Use the [rsa](https://crates.io/crates/rsa) crate for RSA functionality. **Conflicts with `ring` feature.**
 //! `http-did`            | Enable DID resolution tests using [hyper](https://crates.io/crates/hyper) and [tokio](https://crates.io/crates/tokio).
+//! `http-did-server`     | Enable the [DID Resolution HTTP(S) Binding server](did_resolve_server), using [hyper](https://crates.io/crates/hyper).
 //! `secp256k1`           | Enable Secp256k1 using the [k256](https://crates.io/crates/k256) crate.
 //! `secp256r1`           | Enable Secp256r1 using the [p256](https://crates.io/crates/p256) crate.
 //! `ripemd-160`          | Enable RIPEMD-160, for Bitcoin addresses, using the [ripemd160](https://crates.io/crates/ripemd160) crate.
 //! `keccak`              | Enable Keccak hash, for Ethereum addresses, using the [keccak-hash](https://crates.io/crates/keccak-hash) crate.
 //! `sha`                 | Enable SHA-256 using the [sha2](https://crates.io/crates/sha2) crate. **Conflicts with `ring` feature.**
 //! `ed25519-dalek`       | Enable Ed25519 using the [ed25519-dalek](https://crates.io/crates/ed25519-dalek) crate. **Conflicts with `ring` feature.**
+//! `openssl`             | Enable ES384 and ES512, P-384 and P-521 key generation, and [EcdsaSecp384r1Signature2019](ldp::EcdsaSecp384r1Signature2019), using the [openssl](https://crates.io/crates/openssl) crate. Without it, signing or verifying with these algorithms fails.
 //! `example-http-issuer` | Enable resolving example HTTPS Verifiable credential Issuer URL, for [VC Test Suite](https://github.com/w3c/vc-test-suite/).
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![doc(
@@ -27,17 +29,25 @@
 pub mod aleo;
 
 pub mod bbs;
+pub mod bbs_ldp;
 pub mod blakesig;
 pub mod caip10;
 pub mod caip2;
+pub mod data_integrity;
 pub mod der;
 pub mod did;
 pub mod did_resolve;
+#[cfg(feature = "http-did-server")]
+pub mod did_resolve_server;
+pub mod didcomm;
 #[cfg(feature = "keccak-hash")]
 pub mod eip712;
 pub mod error;
 pub mod hash;
+pub mod json_schema;
 pub mod jsonld;
+pub mod jsonpath;
+pub mod jwe;
 pub mod jwk;
 pub mod jws;
 pub mod jwt;
@@ -45,12 +55,17 @@ pub mod jwt;
 pub mod keccak_hash;
 pub mod ldp;
 pub mod one_or_many;
+pub mod policy;
+pub mod presentation_exchange;
 pub mod rdf;
 pub mod revocation;
 #[cfg(feature = "ripemd160")]
 pub mod ripemd;
+pub mod sd_jwt;
+pub mod signer;
 pub mod soltx;
 pub mod ssh;
+pub mod trust_store;
 pub mod tzkey;
 pub mod urdna2015;
 pub mod vc;
@@ -0,0 +1,742 @@
+//! Verification policies
+//!
+//! A [`VerificationPolicy`] restricts which credentials and presentations are accepted, on top of
+//! the verification of their proofs: trusted issuers, proof types and algorithms, checks that
+//! must pass, maximum credential age and required credential types. It is set in the
+//! [`policy`](crate::vc::LinkedDataProofOptions::policy) of the options passed to
+//! [`Credential::verify`], [`Presentation::verify`] and the JWT verification functions, and its
+//! violations are reported in the [`VerificationResult`] with the code [`ProblemCode::Policy`].
+//! For a presentation, the issuer, age and type restrictions apply to the presented credentials,
+//! which are verified first.
+//!
+//! ## Example
+//! ```
+//! use ssi::policy::{IssuerPattern, VerificationPolicy};
+//! use ssi::vc::{Check, LinkedDataProofOptions};
+//!
+//! let policy = VerificationPolicy {
+//!     trusted_issuers: Some(vec![
+//!         IssuerPattern::from("did:example:foo"),
+//!         IssuerPattern::from("did:web:*.example.org"),
+//!         IssuerPattern::from("did:key"),
+//!     ]),
+//!     required_checks: vec![Check::CredentialStatus],
+//!     max_age: Some(365 * 24 * 3600),
+//!     ..Default::default()
+//! };
+//! let options = LinkedDataProofOptions {
+//!     policy: Some(Box::new(policy)),
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use chrono::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::did_resolve::DIDResolver;
+use crate::jsonld::ContextLoader;
+use crate::jwk::Algorithm;
+use crate::jws::Header;
+use crate::vc::{
+    Check, Credential, CredentialOrJWT, DataModelVersion, LinkedDataProofOptions, Presentation,
+    ProblemCode, Proof, VerificationResult,
+};
+
+/// Pattern of accepted issuers in a [`VerificationPolicy`].
+///
+/// Parsed from a string: a string containing `*` is a [pattern](Self::Pattern), a string of the
+/// form `did:<method>` is a [DID method](Self::Method), and anything else is an [issuer
+/// ID](Self::Id).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum IssuerPattern {
+    /// Issuer ID, e.g. a DID, matched exactly.
+    Id(String),
+    /// DID method name, e.g. `key` for `did:key`, matching any DID of the method.
+    Method(String),
+    /// Pattern with `*` wildcards, e.g. `did:web:*.example.org`.
+    Pattern(WildcardPattern),
+}
+
+impl IssuerPattern {
+    /// Check if an issuer ID matches this pattern.
+    pub fn matches(&self, issuer: &str) -> bool {
+        match self {
+            Self::Id(id) => id == issuer,
+            Self::Method(method) => issuer
+                .strip_prefix("did:")
+                .and_then(|did| did.strip_prefix(method.as_str()))
+                .is_some_and(|method_specific_id| method_specific_id.starts_with(':')),
+            Self::Pattern(pattern) => pattern.matches(issuer),
+        }
+    }
+}
+
+/// Issuer ID pattern in which `*` matches any sequence of characters within a single segment of
+/// the ID: it does not match `:`, `/`, `?` or `#`. For example, `did:web:*.example.org` matches
+/// `did:web:issuer.example.org` but not `did:web:evil.com:issuer.example.org`.
+#[derive(Debug, Clone)]
+pub struct WildcardPattern {
+    pattern: String,
+    /// `None` if the pattern is too large to compile, in which case it matches nothing.
+    regex: Option<Regex>,
+}
+
+impl WildcardPattern {
+    pub fn new(pattern: &str) -> Self {
+        let regex = pattern
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<String>>()
+            .join("[^:/?#]*");
+        Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&format!("^{}$", regex)).ok(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Check if an issuer ID matches this pattern.
+    pub fn matches(&self, issuer: &str) -> bool {
+        self.regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(issuer))
+    }
+}
+
+impl PartialEq for WildcardPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for WildcardPattern {}
+
+impl Hash for WildcardPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pattern.hash(state);
+    }
+}
+
+impl fmt::Display for WildcardPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl FromStr for IssuerPattern {
+    type Err = std::convert::Infallible;
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(pattern))
+    }
+}
+
+impl From<&str> for IssuerPattern {
+    fn from(pattern: &str) -> Self {
+        if pattern.contains('*') {
+            return Self::Pattern(WildcardPattern::new(pattern));
+        }
+        match pattern.strip_prefix("did:") {
+            Some(method) if !method.is_empty() && !method.contains(':') => {
+                Self::Method(method.to_string())
+            }
+            _ => Self::Id(pattern.to_string()),
+        }
+    }
+}
+
+impl From<String> for IssuerPattern {
+    fn from(pattern: String) -> Self {
+        Self::from(pattern.as_str())
+    }
+}
+
+impl From<IssuerPattern> for String {
+    fn from(pattern: IssuerPattern) -> String {
+        pattern.to_string()
+    }
+}
+
+impl fmt::Display for IssuerPattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Id(id) => f.write_str(id),
+            Self::Method(method) => write!(f, "did:{}", method),
+            Self::Pattern(pattern) => pattern.fmt(f),
+        }
+    }
+}
+
+/// Restrictions on the credentials and presentations accepted by a verifier.
+///
+/// Each restriction is optional; the default policy accepts anything that verifies.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationPolicy {
+    /// Accepted credential issuers. If omitted, any issuer is accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trusted_issuers: Option<Vec<IssuerPattern>>,
+    /// Accepted proof types, e.g. `Ed25519Signature2020`. The cryptosuite of a Data Integrity
+    /// proof, e.g. `eddsa-rdfc-2022`, is also accepted in place of its proof type. If omitted,
+    /// any proof type is accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_types: Option<Vec<String>>,
+    /// Accepted signature algorithms of proofs and JWTs. If omitted, any algorithm is accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithms: Option<Vec<Algorithm>>,
+    /// Checks that must pass, in addition to the checks requested in the options.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_checks: Vec<Check>,
+    /// Maximum age of a credential, in seconds since its issuance date (`issuanceDate`, or
+    /// `validFrom` for the v2.0 data model).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+    /// Types that a credential must have. For a presentation, each type must be the type of one
+    /// of the presented credentials.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_types: Vec<String>,
+}
+
+impl VerificationPolicy {
+    /// Check if an issuer ID is trusted.
+    pub fn trusts_issuer(&self, issuer: &str) -> bool {
+        match self.trusted_issuers {
+            Some(ref patterns) => patterns.iter().any(|pattern| pattern.matches(issuer)),
+            None => true,
+        }
+    }
+
+    /// Check the issuer, types and age of a credential at the given time.
+    pub fn check_credential(
+        &self,
+        credential: &Credential,
+        time: DateTime<Utc>,
+    ) -> VerificationResult {
+        let mut result = self.check_issuer_and_age(credential, time);
+        for type_ in &self.required_types {
+            if !credential.type_.contains(type_) {
+                result = result.with_problem(
+                    ProblemCode::Policy,
+                    format!("Missing required credential type: {}", type_),
+                );
+            }
+        }
+        result
+    }
+
+    /// Check the issuers and ages of the credentials of a presentation at the given time, and
+    /// that they have the required types.
+    ///
+    /// The presented credentials are verified first, with the proof type and algorithm
+    /// restrictions of the policy, and only the credentials that verify count towards the
+    /// required types. If the policy has no issuer, age or type restriction, the credentials are
+    /// not verified.
+    pub async fn check_presentation(
+        &self,
+        presentation: &Presentation,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        time: DateTime<Utc>,
+    ) -> VerificationResult {
+        let mut result = VerificationResult::new();
+        if self.trusted_issuers.is_none()
+            && self.max_age.is_none()
+            && self.required_types.is_empty()
+        {
+            return result;
+        }
+        let options = LinkedDataProofOptions {
+            policy: Some(Box::new(Self {
+                proof_types: self.proof_types.clone(),
+                algorithms: self.algorithms.clone(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let mut credentials = Vec::new();
+        for (i, credential) in presentation
+            .verifiable_credential
+            .iter()
+            .flatten()
+            .enumerate()
+        {
+            let (credential, verification) = match credential {
+                CredentialOrJWT::Credential(credential) => {
+                    let verification = credential
+                        .verify(Some(options.clone()), resolver, context_loader)
+                        .await;
+                    (Some(credential.clone()), verification)
+                }
+                CredentialOrJWT::JWT(jwt) => {
+                    Credential::decode_verify_jwt(
+                        jwt,
+                        Some(options.clone()),
+                        resolver,
+                        context_loader,
+                    )
+                    .await
+                }
+            };
+            match credential {
+                Some(credential) if verification.errors.is_empty() => credentials.push(credential),
+                _ => {
+                    let errors: Vec<String> =
+                        verification.errors.iter().map(|e| e.to_string()).collect();
+                    result = result.with_problem(
+                        ProblemCode::Policy,
+                        format!("Unable to verify credential {}: {}", i, errors.join(", ")),
+                    );
+                }
+            }
+        }
+        for credential in &credentials {
+            result.append(&mut self.check_issuer_and_age(credential, time));
+        }
+        for type_ in &self.required_types {
+            if !credentials
+                .iter()
+                .any(|credential| credential.type_.contains(type_))
+            {
+                result = result.with_problem(
+                    ProblemCode::Policy,
+                    format!("Missing credential of required type: {}", type_),
+                );
+            }
+        }
+        result
+    }
+
+    fn check_issuer_and_age(
+        &self,
+        credential: &Credential,
+        time: DateTime<Utc>,
+    ) -> VerificationResult {
+        let mut result = VerificationResult::new();
+        if self.trusted_issuers.is_some() {
+            match credential.issuer {
+                Some(ref issuer) if self.trusts_issuer(issuer.get_id_ref()) => {}
+                Some(ref issuer) => {
+                    result = result.with_problem(
+                        ProblemCode::Policy,
+                        format!("Untrusted issuer: {}", issuer.get_id_ref()),
+                    )
+                }
+                None => result = result.with_problem(ProblemCode::Policy, "Missing issuer"),
+            }
+        }
+        if let Some(max_age) = self.max_age {
+            let issuance_date = match credential.context.version() {
+                DataModelVersion::V1 => &credential.issuance_date,
+                DataModelVersion::V2 => &credential.valid_from,
+            };
+            match issuance_date {
+                Some(issuance_date) => {
+                    let issuance_date = DateTime::<Utc>::from(issuance_date.clone());
+                    let age = (time - issuance_date).num_seconds();
+                    if u64::try_from(age).is_ok_and(|age| age > max_age) {
+                        result = result.with_problem(
+                            ProblemCode::Policy,
+                            format!(
+                                "Credential is older than the maximum age of {} seconds",
+                                max_age
+                            ),
+                        );
+                    }
+                }
+                None => {
+                    result = result.with_problem(
+                        ProblemCode::Policy,
+                        "Missing issuance date to check credential age",
+                    )
+                }
+            }
+        }
+        result
+    }
+
+    /// Check the type and algorithm of a linked data proof.
+    ///
+    /// The algorithm is read from the `jws` of the proof if it has one, and from the key of its
+    /// verification method otherwise.
+    pub async fn check_proof(
+        &self,
+        proof: &Proof,
+        resolver: &dyn DIDResolver,
+    ) -> VerificationResult {
+        if let Some(ref proof_types) = self.proof_types {
+            if !proof_types
+                .iter()
+                .any(|type_| type_ == &proof.type_ || Some(type_) == proof.cryptosuite.as_ref())
+            {
+                return VerificationResult::problem(
+                    ProblemCode::Policy,
+                    format!("Proof type not allowed: {}", proof.type_),
+                );
+            }
+        }
+        if self.algorithms.is_none() {
+            return VerificationResult::new();
+        }
+        let algorithm = match proof.jws {
+            Some(ref jws) => jws_algorithm(jws),
+            None => match proof.verification_method {
+                Some(ref vm) => crate::ldp::resolve_key(vm, resolver)
+                    .await
+                    .ok()
+                    .and_then(|key| key.get_algorithm()),
+                None => None,
+            },
+        };
+        match algorithm {
+            Some(algorithm) => self.check_algorithm(algorithm),
+            None => VerificationResult::problem(
+                ProblemCode::Policy,
+                "Unable to determine proof algorithm",
+            ),
+        }
+    }
+
+    /// Check a signature algorithm, e.g. of a JWT.
+    pub fn check_algorithm(&self, algorithm: Algorithm) -> VerificationResult {
+        match self.algorithms {
+            Some(ref algorithms) if !algorithms.contains(&algorithm) => {
+                VerificationResult::problem(
+                    ProblemCode::Policy,
+                    format!("Algorithm not allowed: {:?}", algorithm),
+                )
+            }
+            _ => VerificationResult::new(),
+        }
+    }
+
+    /// Check that the required checks are among the checks that passed.
+    pub fn check_required_checks(&self, checks: &[Check]) -> VerificationResult {
+        let mut result = VerificationResult::new();
+        for check in &self.required_checks {
+            if !checks.contains(check) {
+                result = result.with_problem(
+                    ProblemCode::Policy,
+                    format!(
+                        "Required check did not pass: {}",
+                        String::from(check.clone())
+                    ),
+                );
+            }
+        }
+        result
+    }
+}
+
+fn jws_algorithm(jws: &str) -> Option<Algorithm> {
+    let (header_b64, _, _) = crate::jws::split_jws(jws).ok()?;
+    let header_json = base64::decode_config(header_b64, base64::URL_SAFE_NO_PAD).ok()?;
+    let header: Header = serde_json::from_slice(&header_json).ok()?;
+    Some(header.algorithm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+    use crate::jwk::JWK;
+    use crate::one_or_many::OneOrMany;
+    use crate::vc::{ProofPurpose, URI};
+
+    const JWK_JSON: &str = include_str!("../tests/rsa2048-2020-08-25.json");
+
+    async fn issue_credential() -> Credential {
+        let mut vc: Credential = serde_json::from_value(serde_json::json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:foo",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }))
+        .unwrap();
+        let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        vc
+    }
+
+    #[async_std::test]
+    async fn verify_credential() {
+        let vc = issue_credential().await;
+        let verify = |policy: VerificationPolicy| {
+            let vc = vc.clone();
+            async move {
+                let options = LinkedDataProofOptions {
+                    policy: Some(Box::new(policy)),
+                    ..Default::default()
+                };
+                let mut context_loader = ContextLoader::default();
+                vc.verify(Some(options), &DIDExample, &mut context_loader)
+                    .await
+            }
+        };
+
+        let result = verify(VerificationPolicy {
+            trusted_issuers: Some(vec![IssuerPattern::from("did:example")]),
+            proof_types: Some(vec![vc
+                .proof
+                .as_ref()
+                .unwrap()
+                .first()
+                .unwrap()
+                .type_
+                .clone()]),
+            algorithms: Some(vec![Algorithm::PS256]),
+            required_checks: vec![Check::Proof],
+            required_types: vec!["VerifiableCredential".to_string()],
+            max_age: Some(u64::MAX),
+        })
+        .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        let result = verify(VerificationPolicy {
+            trusted_issuers: Some(vec![IssuerPattern::from("did:key")]),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(result.errors, vec!["Untrusted issuer: did:example:foo"]);
+        assert_eq!(result.errors[0].code, ProblemCode::Policy);
+
+        let result = verify(VerificationPolicy {
+            max_age: Some(3600),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(
+            result.errors,
+            vec!["Credential is older than the maximum age of 3600 seconds"]
+        );
+        assert!(result.checks.contains(&Check::Proof));
+
+        let result = verify(VerificationPolicy {
+            required_types: vec!["AlumniCredential".to_string()],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(
+            result.errors,
+            vec!["Missing required credential type: AlumniCredential"]
+        );
+        assert!(result.checks.contains(&Check::Proof));
+
+        // Proofs not allowed by the policy are not verified.
+        let result = verify(VerificationPolicy {
+            proof_types: Some(vec!["Ed25519Signature2020".to_string()]),
+            ..Default::default()
+        })
+        .await;
+        assert!(!result.checks.contains(&Check::Proof));
+        assert_eq!(result.errors[0].proof_index, Some(0));
+        assert_eq!(result.errors[0].code, ProblemCode::Policy);
+        let result = verify(VerificationPolicy {
+            algorithms: Some(vec![Algorithm::EdDSA]),
+            ..Default::default()
+        })
+        .await;
+        assert!(!result.checks.contains(&Check::Proof));
+        assert_eq!(result.errors, vec!["Algorithm not allowed: PS256"]);
+
+        // Required checks are performed.
+        let result = verify(VerificationPolicy {
+            required_checks: vec![Check::CredentialStatus],
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(
+            result.errors,
+            vec![
+                "Missing credentialStatus",
+                "Required check did not pass: credentialStatus"
+            ]
+        );
+    }
+
+    #[async_std::test]
+    async fn verify_presentation() {
+        let vc = issue_credential().await;
+        let mut unsigned_vc = vc.clone();
+        unsigned_vc.proof = None;
+        let holder_key: JWK =
+            serde_json::from_str(include_str!("../tests/ed25519-2020-10-18.json")).unwrap();
+        let present = |credential: CredentialOrJWT| {
+            let holder_key = holder_key.clone();
+            async move {
+                let mut vp = Presentation {
+                    holder: Some(URI::String("did:example:foo".to_string())),
+                    verifiable_credential: Some(OneOrMany::One(credential)),
+                    ..Default::default()
+                };
+                let options = LinkedDataProofOptions {
+                    verification_method: Some(URI::String("did:example:foo#key2".to_string())),
+                    proof_purpose: Some(ProofPurpose::Authentication),
+                    ..Default::default()
+                };
+                let mut context_loader = ContextLoader::default();
+                let proof = vp
+                    .generate_proof(&holder_key, &options, &DIDExample, &mut context_loader)
+                    .await
+                    .unwrap();
+                vp.add_proof(proof);
+                vp
+            }
+        };
+        let verify = |vp: Presentation, policy: VerificationPolicy| async move {
+            let options = LinkedDataProofOptions {
+                proof_purpose: Some(ProofPurpose::Authentication),
+                policy: Some(Box::new(policy)),
+                ..Default::default()
+            };
+            let mut context_loader = ContextLoader::default();
+            vp.verify(Some(options), &DIDExample, &mut context_loader)
+                .await
+        };
+        let policy = VerificationPolicy {
+            trusted_issuers: Some(vec![IssuerPattern::from("did:example:foo")]),
+            required_types: vec!["VerifiableCredential".to_string()],
+            required_checks: vec![Check::Proof],
+            ..Default::default()
+        };
+
+        let vp = present(CredentialOrJWT::Credential(vc.clone())).await;
+        let result = verify(vp.clone(), policy.clone()).await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert!(result.checks.contains(&Check::Proof));
+
+        // Presented credentials must have the required types.
+        let result = verify(
+            vp.clone(),
+            VerificationPolicy {
+                required_types: vec!["AlumniCredential".to_string()],
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            result.errors,
+            vec!["Missing credential of required type: AlumniCredential"]
+        );
+
+        // Presented credentials must not be too old.
+        let result = verify(
+            vp.clone(),
+            VerificationPolicy {
+                max_age: Some(3600),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(
+            result.errors,
+            vec!["Credential is older than the maximum age of 3600 seconds"]
+        );
+
+        // Presented credentials are verified with the proof restrictions of the policy.
+        let result = verify(
+            vp.clone(),
+            VerificationPolicy {
+                algorithms: Some(vec![Algorithm::EdDSA]),
+                required_types: vec!["VerifiableCredential".to_string()],
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0]
+            .to_string()
+            .starts_with("Unable to verify credential 0"));
+
+        // Required checks apply to presentations.
+        let result = verify(
+            vp,
+            VerificationPolicy {
+                required_checks: vec![Check::CredentialStatus],
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(result.errors[0].code, ProblemCode::InapplicableCheck);
+
+        // Unsigned credentials do not satisfy the policy, even from a trusted issuer.
+        let vp = present(CredentialOrJWT::Credential(unsigned_vc.clone())).await;
+        let result = verify(vp, policy.clone()).await;
+        assert!(result.checks.contains(&Check::Proof));
+        assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+        assert!(result.errors[0]
+            .to_string()
+            .starts_with("Unable to verify credential 0"));
+        assert_eq!(
+            result.errors[1],
+            "Missing credential of required type: VerifiableCredential"
+        );
+        let options = LinkedDataProofOptions {
+            checks: None,
+            created: None,
+            ..Default::default()
+        };
+        let jwt = unsigned_vc
+            .generate_jwt(None, &options, &DIDExample)
+            .await
+            .unwrap();
+        let vp = present(CredentialOrJWT::JWT(jwt)).await;
+        let result = verify(vp, policy).await;
+        assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+    }
+
+    #[test]
+    fn issuer_patterns() {
+        let id = IssuerPattern::from("did:example:foo");
+        assert_eq!(id, IssuerPattern::Id("did:example:foo".to_string()));
+        assert!(id.matches("did:example:foo"));
+        assert!(!id.matches("did:example:foobar"));
+
+        let method = IssuerPattern::from("did:key");
+        assert_eq!(method, IssuerPattern::Method("key".to_string()));
+        assert!(method.matches("did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"));
+        assert!(!method.matches("did:keyx:z6Mk"));
+        assert!(!method.matches("did:example:foo"));
+
+        let pattern = IssuerPattern::from("did:web:*.example.org");
+        assert!(pattern.matches("did:web:issuer.example.org"));
+        assert!(!pattern.matches("did:web:example.org"));
+        assert!(!pattern.matches("did:web:issuer.example.org.evil"));
+        // The wildcard does not span DID path segments or URL components.
+        assert!(!pattern.matches("did:web:evil.com:x.example.org"));
+        assert!(!pattern.matches("did:web:evil.com:path:to:x.example.org"));
+        let url_pattern = IssuerPattern::from("https://*.example.org/issuer");
+        assert!(url_pattern.matches("https://a.example.org/issuer"));
+        assert!(!url_pattern.matches("https://evil.com/x.example.org/issuer"));
+        assert!(!url_pattern.matches("https://evil.com?x.example.org/issuer"));
+        assert!(!url_pattern.matches("https://evil.com#x.example.org/issuer"));
+        assert_eq!(pattern, IssuerPattern::from("did:web:*.example.org"));
+
+        let patterns: Vec<IssuerPattern> =
+            serde_json::from_str(r#"["did:example:foo", "did:key", "did:web:*"]"#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&patterns).unwrap(),
+            r#"["did:example:foo","did:key","did:web:*"]"#
+        );
+    }
+}
@@ -0,0 +1,905 @@
+//! [DIF Presentation Exchange v2][pe]
+//!
+//! A verifier describes the credentials it requires with a [`PresentationDefinition`]. A holder
+//! [selects](PresentationDefinition::select) credentials satisfying the definition's input
+//! descriptors and submission requirements, and
+//! [creates](PresentationDefinition::create_presentation) a [`Presentation`] with a
+//! [`PresentationSubmission`] mapping the credentials to the input descriptors. The verifier then
+//! [verifies](PresentationDefinition::verify_submission) that the submitted presentation satisfies
+//! the definition.
+//!
+//! Field constraints use the [JSONPath subset](crate::jsonpath) and [JSON Schema
+//! subset](crate::json_schema) implemented in this crate. Credentials are submitted in the
+//! `ldp_vc` format; submissions of `jwt_vc` credentials can be verified.
+//!
+//! Checking a submission only evaluates the submitted credentials against the definition. It
+//! does not verify their proofs: JWT credentials are decoded without verifying their signature,
+//! and [`Presentation::verify`] verifies the proof of the presentation but not the credentials
+//! embedded in it. Verify each submitted credential, e.g. with [`Credential::verify`], before
+//! relying on it.
+//!
+//! [pe]: https://identity.foundation/presentation-exchange/spec/v2.0.0/
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::json_schema::{self, JsonSchemaError};
+use crate::jsonld::PRESENTATION_SUBMISSION_V1_CONTEXT;
+use crate::jsonpath::{JsonPath, JsonPathError};
+use crate::one_or_many::OneOrMany;
+use crate::vc::{
+    Context, Contexts, Credential, CredentialOrJWT, Presentation, DEFAULT_CONTEXT, URI,
+};
+
+/// Presentation type indicating a presentation contains a presentation submission.
+pub const PRESENTATION_SUBMISSION_TYPE: &str = "PresentationSubmission";
+
+/// Presentation property containing the presentation submission.
+pub const PRESENTATION_SUBMISSION_PROPERTY: &str = "presentation_submission";
+
+/// Error from evaluating a [`PresentationDefinition`].
+#[derive(Error, Debug)]
+pub enum PresentationExchangeError {
+    #[error("Invalid field path: {0}")]
+    JsonPath(#[from] JsonPathError),
+    #[error("Invalid field filter: {0}")]
+    Filter(#[from] JsonSchemaError),
+    #[error("No credential satisfies input descriptor {0}")]
+    UnsatisfiedInputDescriptor(String),
+    #[error("Submission requirement not satisfied: {0}")]
+    UnsatisfiedSubmissionRequirement(String),
+    #[error("Invalid submission requirement: {0}")]
+    InvalidSubmissionRequirement(String),
+    #[error("Input descriptor {0} requires limited disclosure")]
+    LimitDisclosureRequired(String),
+    #[error("Missing presentation submission")]
+    MissingPresentationSubmission,
+    #[error("Presentation submission is for definition {0}, expected {1}")]
+    DefinitionIdMismatch(String, String),
+    #[error("Unknown input descriptor in presentation submission: {0}")]
+    UnknownInputDescriptor(String),
+    #[error("Descriptor map path does not select a single credential: {0}")]
+    InvalidDescriptorPath(String),
+    #[error("Unsupported format in descriptor map: {0}")]
+    UnsupportedFormat(String),
+    #[error("Credential at {0} does not satisfy input descriptor {1}")]
+    InputDescriptorMismatch(String, String),
+}
+
+/// [Presentation Definition](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-definition)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PresentationDefinition {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Map<String, Value>>,
+    pub input_descriptors: Vec<InputDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_requirements: Option<Vec<SubmissionRequirement>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame: Option<Map<String, Value>>,
+}
+
+/// [Input Descriptor](https://identity.foundation/presentation-exchange/spec/v2.0.0/#input-descriptor-object)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputDescriptor {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Constraints>,
+}
+
+/// Constraints of an [`InputDescriptor`].
+///
+/// Properties of the Presentation Exchange features that are not evaluated here, such as
+/// `statuses` or `same_subject`, are kept in `property_set`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Constraints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_disclosure: Option<Requirement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<Field>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_is_issuer: Option<Requirement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten)]
+    pub property_set: Option<Map<String, Value>>,
+}
+
+/// Whether a constraint is required or only preferred.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Requirement {
+    Required,
+    Preferred,
+}
+
+/// [Field](https://identity.foundation/presentation-exchange/spec/v2.0.0/#input-descriptor-object)
+/// constraint of an [`InputDescriptor`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Field {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// JSONPath expressions, tried in order.
+    pub path: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// JSON Schema that the value at the path must conform to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predicate: Option<Requirement>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intent_to_retain: Option<bool>,
+}
+
+/// [Submission Requirement](https://identity.foundation/presentation-exchange/spec/v2.0.0/#submission-requirements)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SubmissionRequirement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    pub rule: SubmissionRequirementRule,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    /// Group of input descriptors the requirement applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Nested submission requirements, instead of a group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_nested: Option<Vec<SubmissionRequirement>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionRequirementRule {
+    All,
+    Pick,
+}
+
+/// [Presentation Submission](https://identity.foundation/presentation-exchange/spec/v2.0.0/#presentation-submission)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PresentationSubmission {
+    pub id: String,
+    pub definition_id: String,
+    pub descriptor_map: Vec<DescriptorMapEntry>,
+}
+
+/// Entry of a [`PresentationSubmission`] descriptor map, locating the credential submitted for an
+/// input descriptor.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DescriptorMapEntry {
+    /// Input descriptor ID.
+    pub id: String,
+    /// Claim format of the credential, e.g. `ldp_vc` or `jwt_vc`.
+    pub format: String,
+    /// JSONPath locating the credential in the presentation.
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_nested: Option<Box<DescriptorMapEntry>>,
+}
+
+impl Field {
+    /// Check if a credential, in JSON, satisfies this field: the value at one of the paths must
+    /// conform to the filter, if any.
+    pub fn is_satisfied_by(&self, credential: &Value) -> Result<bool, PresentationExchangeError> {
+        for path in &self.path {
+            let path: JsonPath = path.parse()?;
+            for value in path.select(credential) {
+                match self.filter {
+                    None => return Ok(true),
+                    Some(ref filter) => {
+                        if json_schema::is_valid(filter, value)? {
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+}
+
+fn subject_is_issuer(credential: &Value) -> bool {
+    let issuer = match credential.get("issuer") {
+        Some(Value::String(issuer)) => issuer,
+        Some(Value::Object(issuer)) => match issuer.get("id") {
+            Some(Value::String(issuer)) => issuer,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let subjects = match credential.get("credentialSubject") {
+        Some(Value::Array(subjects)) => subjects.iter().collect(),
+        Some(subject) => vec![subject],
+        None => return false,
+    };
+    subjects
+        .iter()
+        .all(|subject| subject.get("id") == Some(&Value::String(issuer.to_string())))
+}
+
+impl InputDescriptor {
+    /// Check if a credential, in JSON, satisfies the constraints of this input descriptor.
+    pub fn is_satisfied_by(&self, credential: &Value) -> Result<bool, PresentationExchangeError> {
+        let constraints = match self.constraints {
+            Some(ref constraints) => constraints,
+            None => return Ok(true),
+        };
+        for field in constraints.fields.iter().flatten() {
+            if field.optional != Some(true) && !field.is_satisfied_by(credential)? {
+                return Ok(false);
+            }
+        }
+        if constraints.subject_is_issuer == Some(Requirement::Required)
+            && !subject_is_issuer(credential)
+        {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn is_in_group(&self, group: &str) -> bool {
+        self.group
+            .iter()
+            .flatten()
+            .any(|descriptor_group| descriptor_group == group)
+    }
+}
+
+impl SubmissionRequirement {
+    fn description(&self) -> String {
+        match (&self.name, &self.from) {
+            (Some(name), _) => name.to_string(),
+            (None, Some(from)) => format!("from group {}", from),
+            (None, None) => "nested requirement".to_string(),
+        }
+    }
+
+    /// Select input descriptors fulfilling this requirement, given the input descriptors that
+    /// can be fulfilled. If `exact`, picking more than required is an error; otherwise the first
+    /// fulfillable descriptors are selected.
+    fn select<'a>(
+        &self,
+        definition: &'a PresentationDefinition,
+        fulfilled: &BTreeSet<&str>,
+        exact: bool,
+    ) -> Result<BTreeSet<&'a str>, PresentationExchangeError> {
+        let unsatisfied =
+            || PresentationExchangeError::UnsatisfiedSubmissionRequirement(self.description());
+        // Each option is a set of input descriptors that can fulfill part of the requirement.
+        let (options, total): (Vec<BTreeSet<&str>>, usize) = match (&self.from, &self.from_nested) {
+            (Some(group), None) => {
+                let descriptors: Vec<&str> = definition
+                    .input_descriptors
+                    .iter()
+                    .filter(|descriptor| descriptor.is_in_group(group))
+                    .map(|descriptor| descriptor.id.as_str())
+                    .collect();
+                let options = descriptors
+                    .iter()
+                    .filter(|id| fulfilled.contains(*id))
+                    .map(|id| std::iter::once(*id).collect())
+                    .collect();
+                (options, descriptors.len())
+            }
+            (None, Some(nested)) => {
+                let options = nested
+                    .iter()
+                    .filter_map(|requirement| {
+                        match requirement.select(definition, fulfilled, exact) {
+                            Err(PresentationExchangeError::UnsatisfiedSubmissionRequirement(_)) => {
+                                None
+                            }
+                            result => Some(result),
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                (options, nested.len())
+            }
+            _ => {
+                return Err(PresentationExchangeError::InvalidSubmissionRequirement(
+                    "exactly one of from and from_nested must be present".to_string(),
+                ))
+            }
+        };
+        let selected = match self.rule {
+            SubmissionRequirementRule::All => {
+                if options.len() < total {
+                    return Err(unsatisfied());
+                }
+                options.len()
+            }
+            SubmissionRequirementRule::Pick => {
+                let (min, max) = match self.count {
+                    Some(count) => (count, count),
+                    None => (self.min.unwrap_or(0), self.max.unwrap_or(options.len())),
+                };
+                if options.len() < min || (exact && options.len() > max) {
+                    return Err(unsatisfied());
+                }
+                options.len().min(max)
+            }
+        };
+        Ok(options.into_iter().take(selected).flatten().collect())
+    }
+}
+
+impl PresentationDefinition {
+    /// Get an input descriptor by ID.
+    pub fn input_descriptor(&self, id: &str) -> Option<&InputDescriptor> {
+        self.input_descriptors
+            .iter()
+            .find(|descriptor| descriptor.id == id)
+    }
+
+    /// Select the input descriptors to fulfill, given those that can be fulfilled.
+    fn select_input_descriptors(
+        &self,
+        fulfilled: &BTreeSet<&str>,
+        exact: bool,
+    ) -> Result<BTreeSet<&str>, PresentationExchangeError> {
+        match self.submission_requirements {
+            None => {
+                // Without submission requirements, every input descriptor must be fulfilled.
+                for descriptor in &self.input_descriptors {
+                    if !fulfilled.contains(descriptor.id.as_str()) {
+                        return Err(PresentationExchangeError::UnsatisfiedInputDescriptor(
+                            descriptor.id.to_string(),
+                        ));
+                    }
+                }
+                Ok(self
+                    .input_descriptors
+                    .iter()
+                    .map(|descriptor| descriptor.id.as_str())
+                    .collect())
+            }
+            Some(ref requirements) => {
+                let mut selected = BTreeSet::new();
+                for requirement in requirements {
+                    selected.append(&mut requirement.select(self, fulfilled, exact)?);
+                }
+                Ok(selected)
+            }
+        }
+    }
+
+    /// Find the credentials satisfying each input descriptor, as indices into `credentials`.
+    pub fn match_credentials(
+        &self,
+        credentials: &[Credential],
+    ) -> Result<Vec<(&InputDescriptor, Vec<usize>)>, Error> {
+        let credentials = credentials
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<Value>, _>>()?;
+        let mut matches = Vec::new();
+        for descriptor in &self.input_descriptors {
+            let mut indices = Vec::new();
+            for (i, credential) in credentials.iter().enumerate() {
+                if descriptor.is_satisfied_by(credential)? {
+                    indices.push(i);
+                }
+            }
+            matches.push((descriptor, indices));
+        }
+        Ok(matches)
+    }
+
+    /// Select credentials to fulfill this definition. Returns the IDs of the input descriptors
+    /// to submit, each with the index in `credentials` of the first credential satisfying it.
+    pub fn select(&self, credentials: &[Credential]) -> Result<Vec<(String, usize)>, Error> {
+        let matches = self.match_credentials(credentials)?;
+        let fulfilled = matches
+            .iter()
+            .filter(|(_, indices)| !indices.is_empty())
+            .map(|(descriptor, _)| descriptor.id.as_str())
+            .collect();
+        let selected = self.select_input_descriptors(&fulfilled, false)?;
+        let mut selection = Vec::new();
+        for (descriptor, indices) in matches {
+            if !selected.contains(descriptor.id.as_str()) {
+                continue;
+            }
+            if let Some(Requirement::Required) = descriptor
+                .constraints
+                .as_ref()
+                .and_then(|constraints| constraints.limit_disclosure)
+            {
+                return Err(PresentationExchangeError::LimitDisclosureRequired(
+                    descriptor.id.to_string(),
+                ))?;
+            }
+            selection.push((descriptor.id.to_string(), indices[0]));
+        }
+        Ok(selection)
+    }
+
+    /// Create an unsigned presentation of credentials fulfilling this definition, with a
+    /// presentation submission. Each selected credential is included once, even if it satisfies
+    /// several input descriptors.
+    pub fn create_presentation(
+        &self,
+        credentials: &[Credential],
+        holder: Option<URI>,
+    ) -> Result<Presentation, Error> {
+        let selection = self.select(credentials)?;
+        let mut included: Vec<usize> = Vec::new();
+        let mut descriptor_map = Vec::new();
+        for (descriptor_id, credential_index) in selection {
+            let position = match included.iter().position(|i| *i == credential_index) {
+                Some(position) => position,
+                None => {
+                    included.push(credential_index);
+                    included.len() - 1
+                }
+            };
+            descriptor_map.push(DescriptorMapEntry {
+                id: descriptor_id,
+                format: "ldp_vc".to_string(),
+                path: format!("$.verifiableCredential[{}]", position),
+                path_nested: None,
+            });
+        }
+        let submission = PresentationSubmission {
+            id: format!("urn:uuid:{}", random_uuid()),
+            definition_id: self.id.clone(),
+            descriptor_map,
+        };
+        let mut property_set = std::collections::HashMap::new();
+        property_set.insert(
+            PRESENTATION_SUBMISSION_PROPERTY.to_string(),
+            serde_json::to_value(submission)?,
+        );
+        Ok(Presentation {
+            context: Contexts::Many(vec![
+                Context::URI(URI::String(DEFAULT_CONTEXT.to_string())),
+                Context::URI(URI::String(PRESENTATION_SUBMISSION_V1_CONTEXT.to_string())),
+            ]),
+            id: None,
+            type_: OneOrMany::Many(vec![
+                "VerifiablePresentation".to_string(),
+                PRESENTATION_SUBMISSION_TYPE.to_string(),
+            ]),
+            verifiable_credential: Some(OneOrMany::Many(
+                included
+                    .into_iter()
+                    .map(|i| CredentialOrJWT::Credential(credentials[i].clone()))
+                    .collect(),
+            )),
+            proof: None,
+            holder,
+            property_set: Some(property_set),
+        })
+    }
+
+    /// Verify that a presentation submission satisfies this definition: every entry of its
+    /// descriptor map must locate a credential satisfying the input descriptor, and the
+    /// submitted input descriptors must fulfill the submission requirements.
+    ///
+    /// This does not verify the proofs of the presentation or of its credentials. Embedded
+    /// credentials are not verified by [`Presentation::verify`] either, and must be verified
+    /// separately.
+    pub fn verify_submission(&self, presentation: &Presentation) -> Result<(), Error> {
+        let submission = PresentationSubmission::from_presentation(presentation)?;
+        if submission.definition_id != self.id {
+            return Err(PresentationExchangeError::DefinitionIdMismatch(
+                submission.definition_id,
+                self.id.clone(),
+            ))?;
+        }
+        let presentation = serde_json::to_value(presentation)?;
+        let mut submitted = BTreeSet::new();
+        for entry in &submission.descriptor_map {
+            let descriptor = self.input_descriptor(&entry.id).ok_or_else(|| {
+                PresentationExchangeError::UnknownInputDescriptor(entry.id.to_string())
+            })?;
+            let credential = entry.select_credential(&presentation)?;
+            if !descriptor.is_satisfied_by(&credential)? {
+                return Err(PresentationExchangeError::InputDescriptorMismatch(
+                    entry.path.to_string(),
+                    entry.id.to_string(),
+                ))?;
+            }
+            submitted.insert(descriptor.id.as_str());
+        }
+        self.select_input_descriptors(&submitted, true)?;
+        Ok(())
+    }
+}
+
+impl PresentationSubmission {
+    /// Get the presentation submission of a presentation.
+    pub fn from_presentation(presentation: &Presentation) -> Result<Self, Error> {
+        let submission = presentation
+            .property_set
+            .as_ref()
+            .and_then(|property_set| property_set.get(PRESENTATION_SUBMISSION_PROPERTY))
+            .ok_or(PresentationExchangeError::MissingPresentationSubmission)?;
+        Ok(serde_json::from_value(submission.clone())?)
+    }
+}
+
+impl DescriptorMapEntry {
+    /// Locate the credential for this entry in a presentation, following nested paths. JWT
+    /// credentials and presentations are decoded, without verification, to their JWT claims.
+    ///
+    /// Entries in a credential format must locate an element of the `verifiableCredential` of
+    /// the enclosing presentation, and entries in a presentation format must have a nested path.
+    fn select_credential(&self, presentation: &Value) -> Result<Value, Error> {
+        self.select_credential_in(presentation, false)
+    }
+
+    fn select_credential_in(&self, value: &Value, jwt_claims: bool) -> Result<Value, Error> {
+        let invalid_path =
+            || PresentationExchangeError::InvalidDescriptorPath(self.path.to_string());
+        let path: JsonPath = self.path.parse().map_err(PresentationExchangeError::from)?;
+        let selected = match path.select(value).as_slice() {
+            [selected] => *selected,
+            _ => return Err(invalid_path())?,
+        };
+        let format = self.format.as_str();
+        let credential_format =
+            matches!(format, "ldp_vc" | "jwt_vc" | "jwt_vc_json" | "ldp" | "jwt");
+        if credential_format {
+            // The claims of a JWT presentation hold the presentation in the vp claim.
+            let presentation = match jwt_claims {
+                true => value.get("vp").ok_or_else(invalid_path)?,
+                false => value,
+            };
+            if !is_credential_of(presentation, selected) {
+                return Err(invalid_path())?;
+            }
+        } else if self.path_nested.is_none() {
+            return Err(invalid_path())?;
+        }
+        let (selected, jwt_claims) = match (format, selected) {
+            ("ldp_vc" | "ldp_vp" | "ldp", selected @ Value::Object(_)) => (selected.clone(), false),
+            ("jwt_vc" | "jwt_vc_json" | "jwt_vp" | "jwt_vp_json" | "jwt", Value::String(jwt)) => {
+                (crate::jwt::decode_unverified(jwt)?, true)
+            }
+            (format, _) => {
+                return Err(PresentationExchangeError::UnsupportedFormat(
+                    format.to_string(),
+                ))?
+            }
+        };
+        match self.path_nested {
+            Some(ref nested) if credential_format => Err(
+                PresentationExchangeError::InvalidDescriptorPath(nested.path.to_string()),
+            )?,
+            Some(ref nested) => nested.select_credential_in(&selected, jwt_claims),
+            None => Ok(selected),
+        }
+    }
+}
+
+/// Whether a value is an element of the `verifiableCredential` property of a presentation.
+fn is_credential_of(presentation: &Value, value: &Value) -> bool {
+    match presentation.get("verifiableCredential") {
+        Some(Value::Array(credentials)) => credentials.iter().any(|c| std::ptr::eq(c, value)),
+        Some(credential) => std::ptr::eq(credential, value),
+        None => false,
+    }
+}
+
+fn random_uuid() -> String {
+    let mut bytes: [u8; 16] = rand_old::random();
+    // Version 4, variant 1
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+    use crate::jwk::JWK;
+    use crate::vc::LinkedDataProofOptions;
+    use serde_json::json;
+
+    fn credentials() -> Vec<Credential> {
+        let degree = Credential::from_json_unsigned(
+            r#"{
+                "@context": [
+                    "https://www.w3.org/2018/credentials/v1",
+                    "https://www.w3.org/2018/credentials/examples/v1"
+                ],
+                "id": "http://example.edu/credentials/1872",
+                "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+                "issuer": "did:example:foo",
+                "issuanceDate": "2010-01-01T19:23:24Z",
+                "credentialSubject": {
+                    "id": "did:example:bar",
+                    "degree": {
+                        "type": "BachelorDegree",
+                        "name": "Bachelor of Science and Arts"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let alumni = Credential::from_json_unsigned(
+            r#"{
+                "@context": [
+                    "https://www.w3.org/2018/credentials/v1",
+                    "https://www.w3.org/2018/credentials/examples/v1"
+                ],
+                "id": "http://example.edu/credentials/58473",
+                "type": ["VerifiableCredential", "AlumniCredential"],
+                "issuer": "did:example:foo",
+                "issuanceDate": "2010-01-01T19:23:24Z",
+                "credentialSubject": {
+                    "id": "did:example:bar",
+                    "alumniOf": "Example University"
+                }
+            }"#,
+        )
+        .unwrap();
+        vec![degree, alumni]
+    }
+
+    fn definition() -> PresentationDefinition {
+        serde_json::from_value(json!({
+            "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+            "input_descriptors": [
+                {
+                    "id": "degree",
+                    "group": ["A"],
+                    "constraints": {
+                        "fields": [
+                            {
+                                "path": ["$.credentialSubject.degree.type", "$.vc.credentialSubject.degree.type"],
+                                "filter": {"type": "string", "const": "BachelorDegree"}
+                            },
+                            {
+                                "path": ["$.credentialSubject.gpa"],
+                                "optional": true
+                            }
+                        ]
+                    }
+                },
+                {
+                    "id": "alumni",
+                    "group": ["A"],
+                    "constraints": {
+                        "fields": [
+                            {
+                                "path": ["$.type"],
+                                "filter": {"type": "array", "contains": {"const": "AlumniCredential"}}
+                            }
+                        ]
+                    }
+                },
+                {
+                    "id": "employment",
+                    "group": ["B"],
+                    "constraints": {
+                        "fields": [
+                            {
+                                "path": ["$.type"],
+                                "filter": {"type": "array", "contains": {"const": "EmploymentCredential"}}
+                            }
+                        ]
+                    }
+                }
+            ],
+            "submission_requirements": [
+                {"name": "Education", "rule": "pick", "count": 1, "from": "A"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn select_credentials() {
+        let definition = definition();
+        let credentials = credentials();
+        let matches = definition.match_credentials(&credentials).unwrap();
+        let matches: Vec<(&str, Vec<usize>)> = matches
+            .into_iter()
+            .map(|(descriptor, indices)| (descriptor.id.as_str(), indices))
+            .collect();
+        assert_eq!(
+            matches,
+            vec![
+                ("degree", vec![0]),
+                ("alumni", vec![1]),
+                ("employment", vec![])
+            ]
+        );
+        assert_eq!(
+            definition.select(&credentials).unwrap(),
+            vec![("degree".to_string(), 0)]
+        );
+
+        // Without submission requirements, every input descriptor must be fulfilled.
+        let definition = PresentationDefinition {
+            submission_requirements: None,
+            ..definition
+        };
+        match definition.select(&credentials).unwrap_err() {
+            Error::PresentationExchange(PresentationExchangeError::UnsatisfiedInputDescriptor(
+                id,
+            )) => assert_eq!(id, "employment"),
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+
+    #[test]
+    fn nested_submission_requirements() {
+        let mut definition = definition();
+        definition.submission_requirements = Some(
+            serde_json::from_value(json!([{
+                "rule": "pick",
+                "min": 1,
+                "from_nested": [
+                    {"rule": "all", "from": "A"},
+                    {"rule": "all", "from": "B"}
+                ]
+            }]))
+            .unwrap(),
+        );
+        let selection = definition.select(&credentials()).unwrap();
+        assert_eq!(
+            selection,
+            vec![("degree".to_string(), 0), ("alumni".to_string(), 1)]
+        );
+        definition.submission_requirements =
+            Some(serde_json::from_value(json!([{"rule": "all", "from": "B"}])).unwrap());
+        definition.select(&credentials()).unwrap_err();
+    }
+
+    #[async_std::test]
+    async fn create_verify_presentation() {
+        let definition = definition();
+        let credentials = credentials();
+        let mut vp = definition
+            .create_presentation(
+                &credentials,
+                Some(URI::String("did:example:bar".to_string())),
+            )
+            .unwrap();
+        vp.validate_unsigned().unwrap();
+        let submission = PresentationSubmission::from_presentation(&vp).unwrap();
+        assert_eq!(submission.definition_id, definition.id);
+        assert_eq!(
+            submission.descriptor_map,
+            vec![DescriptorMapEntry {
+                id: "degree".to_string(),
+                format: "ldp_vc".to_string(),
+                path: "$.verifiableCredential[0]".to_string(),
+                path_nested: None,
+            }]
+        );
+        definition.verify_submission(&vp).unwrap();
+
+        // The presentation submission is covered by the proof.
+        let key: JWK =
+            serde_json::from_str(include_str!("../tests/ed25519-2020-10-18.json")).unwrap();
+        let options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key2".to_string())),
+            proof_purpose: Some(crate::vc::ProofPurpose::Authentication),
+            ..Default::default()
+        };
+        let mut context_loader = crate::jsonld::ContextLoader::default();
+        vp.holder = Some(URI::String("did:example:foo".to_string()));
+        let proof = vp
+            .generate_proof(&key, &options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vp.add_proof(proof);
+        let vp: Presentation = serde_json::from_str(&serde_json::to_string(&vp).unwrap()).unwrap();
+        let result = vp.verify(None, &DIDExample, &mut context_loader).await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        definition.verify_submission(&vp).unwrap();
+
+        // Submitting a credential for the wrong input descriptor fails.
+        let mut tampered = vp.clone();
+        tampered.property_set.as_mut().unwrap().insert(
+            PRESENTATION_SUBMISSION_PROPERTY.to_string(),
+            json!({
+                "id": submission.id,
+                "definition_id": definition.id,
+                "descriptor_map": [
+                    {"id": "alumni", "format": "ldp_vc", "path": "$.verifiableCredential[0]"}
+                ]
+            }),
+        );
+        definition.verify_submission(&tampered).unwrap_err();
+
+        // Submitting more than the requirement allows fails.
+        let mut tampered = definition.create_presentation(&credentials, None).unwrap();
+        tampered.verifiable_credential = Some(OneOrMany::Many(
+            credentials
+                .iter()
+                .cloned()
+                .map(CredentialOrJWT::Credential)
+                .collect(),
+        ));
+        tampered.property_set.as_mut().unwrap().insert(
+            PRESENTATION_SUBMISSION_PROPERTY.to_string(),
+            json!({
+                "id": submission.id,
+                "definition_id": definition.id,
+                "descriptor_map": [
+                    {"id": "degree", "format": "ldp_vc", "path": "$.verifiableCredential[0]"},
+                    {"id": "alumni", "format": "ldp_vc", "path": "$.verifiableCredential[1]"}
+                ]
+            }),
+        );
+        definition.verify_submission(&tampered).unwrap_err();
+
+        // Credential paths must locate an embedded credential.
+        let submit = |descriptor_map: Value| {
+            let mut submitted = vp.clone();
+            submitted.property_set.as_mut().unwrap().insert(
+                "degree".to_string(),
+                serde_json::to_value(&credentials[0]).unwrap(),
+            );
+            submitted.property_set.as_mut().unwrap().insert(
+                PRESENTATION_SUBMISSION_PROPERTY.to_string(),
+                json!({
+                    "id": submission.id,
+                    "definition_id": definition.id,
+                    "descriptor_map": descriptor_map
+                }),
+            );
+            definition.verify_submission(&submitted)
+        };
+        for entry in [
+            json!({"id": "degree", "format": "ldp_vc", "path": "$"}),
+            json!({"id": "degree", "format": "ldp_vc", "path": "$.degree"}),
+            json!({"id": "degree", "format": "ldp_vp", "path": "$.verifiableCredential[0]"}),
+            json!({
+                "id": "degree", "format": "ldp_vp", "path": "$",
+                "path_nested": {"id": "degree", "format": "ldp_vc", "path": "$.degree"}
+            }),
+        ] {
+            assert!(matches!(
+                submit(json!([entry])),
+                Err(Error::PresentationExchange(
+                    PresentationExchangeError::InvalidDescriptorPath(_)
+                ))
+            ));
+        }
+        submit(json!([{
+            "id": "degree", "format": "ldp_vp", "path": "$",
+            "path_nested": {"id": "degree", "format": "ldp_vc", "path": "$.verifiableCredential[0]"}
+        }]))
+        .unwrap();
+    }
+}
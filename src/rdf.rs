@@ -620,6 +620,55 @@ impl<'a> GraphRef<'a> {
         Ok(())
     }
 
+    /// Given a string, assert that a statement exists with the given subject, predicate and string
+    /// literal as object, either plain or of the given datatype, and remove it; or given no
+    /// string, assert that no statement with given subject and predicate exists.
+    pub fn match_typed_string_property(
+        &mut self,
+        subject: &Subject,
+        predicate_iri: &str,
+        datatype_iri: &str,
+        object_opt_expected: Option<&str>,
+    ) -> Result<(), Error> {
+        let triple_opt = self.take(
+            Some(subject),
+            Some(&Predicate::IRIRef(IRIRef(predicate_iri.to_string()))),
+            None,
+        );
+        match (object_opt_expected, triple_opt) {
+            (None, None) => {}
+            (None, Some(triple)) => {
+                return Err(Error::UnexpectedTriple(triple.clone()));
+            }
+            (Some(object_expected), None) => {
+                return Err(Error::ExpectedObjectForPredicate(
+                    predicate_iri.to_string(),
+                    object_expected.to_string(),
+                ));
+            }
+            (Some(object_expected), Some(triple)) => {
+                let object = match &triple.object {
+                    Object::Literal(Literal::String {
+                        string: StringLiteral(string_literal),
+                    }) => string_literal,
+                    Object::Literal(Literal::Typed {
+                        string: StringLiteral(string_literal),
+                        type_: IRIRef(type_iri),
+                    }) if type_iri == datatype_iri => string_literal,
+                    _ => return Err(Error::UnexpectedTriple(triple.clone())),
+                };
+                if object != object_expected {
+                    return Err(Error::ObjectMismatch(
+                        predicate_iri.to_string(),
+                        object_expected.to_string(),
+                        object.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Given a date-time, assert that a statement exists with the given subject, predicate and
     /// object, and remove it; or given no object, assert that no statement with given subject and
     /// predicate exists.
@@ -1,14 +1,28 @@
-use crate::did_resolve::DIDResolver;
-use crate::jsonld::{ContextLoader, REVOCATION_LIST_2020_V1_CONTEXT, STATUS_LIST_2021_V1_CONTEXT};
+use crate::did::Resource;
+use crate::did_resolve::{dereference, Content, DIDResolver, DereferencingInputMetadata};
+use crate::jsonld::{
+    ContextLoader, CREDENTIALS_V2_CONTEXT, REVOCATION_LIST_2020_V1_CONTEXT,
+    STATUS_LIST_2021_V1_CONTEXT,
+};
 use crate::one_or_many::OneOrMany;
-use crate::vc::{Credential, CredentialStatus, Issuer, VerificationResult, URI};
+use crate::signer::Signer;
+use crate::vc::{
+    Credential, CredentialStatus, Issuer, LinkedDataProofOptions, ProblemCode, VCDateTime,
+    VerificationResult, URI,
+};
 use async_trait::async_trait;
-use bitvec::prelude::Lsb0;
+use bitvec::prelude::{Lsb0, Msb0};
 use bitvec::slice::BitSlice;
 use bitvec::vec::BitVec;
+use chrono::{DateTime, Duration, Utc};
 use core::convert::TryFrom;
+use rand_old::seq::IteratorRandom;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::sync::Mutex;
 use thiserror::Error;
 
 #[allow(clippy::upper_case_acronyms)]
@@ -60,6 +74,63 @@ pub struct StatusList2021Entry {
     pub status_list_credential: URL,
 }
 
+/// Bitstring Status List entry, for use in a Verifiable Credential's credentialStatus property.
+/// <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistentry>
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitstringStatusListEntry {
+    /// URL for status information of the verifiable credential - but not the URL of the status
+    /// list.
+    pub id: URI,
+    /// Status purpose, e.g. "revocation", "suspension" or "message".
+    ///
+    /// It must be one of the purposes of the status list.
+    pub status_purpose: String,
+    /// Index of this credential's status in the status list credential
+    pub status_list_index: RevocationListIndex,
+    /// URL to a [BitstringStatusListCredential]
+    pub status_list_credential: URL,
+    /// Size of the status in bits. Default is 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_size: Option<usize>,
+    /// Messages for the values of the status
+    #[serde(rename = "statusMessage")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_messages: Option<Vec<StatusMessage>>,
+    /// URLs of documentation about the values of the status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_reference: Option<OneOrMany<URL>>,
+}
+
+/// Message for a value of a multi-bit status, in a [BitstringStatusListEntry].
+/// <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistentry>
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StatusMessage {
+    /// Status value, as a hexadecimal string prefixed by "0x", e.g. "0x1"
+    pub status: String,
+    /// Message for the status value
+    pub message: String,
+}
+
+/// Status of a credential, read from a status list when [checking][CredentialStatus::check] a
+/// credential's status.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusValue {
+    /// Status purpose, e.g. "revocation"
+    pub purpose: String,
+    /// Value of the status. For a status of one bit, 1 means that the status is set, e.g. that
+    /// the credential is revoked.
+    pub value: u64,
+    /// Message for the value, from the status entry's `statusMessage`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// URL of the status list credential
+    pub status_list_credential: URL,
+    /// Index of the status in the status list
+    pub status_list_index: usize,
+}
+
 /// Integer identifying a bit position of the revocation status of a verifiable credential in a
 /// revocation list, e.g. in a [RevocationList2020].
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -105,6 +176,25 @@ pub struct StatusList2021Credential {
     pub more_properties: Value,
 }
 
+/// [Credential subject](https://www.w3.org/TR/vc-data-model/#credential-subject) of a [BitstringStatusListCredential]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum BitstringStatusListSubject {
+    BitstringStatusList(BitstringStatusList),
+}
+
+/// Verifiable Credential of type BitstringStatusListCredential.
+/// <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistcredential>
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitstringStatusListCredential {
+    pub id: URI,
+    pub issuer: Issuer,
+    pub credential_subject: BitstringStatusListSubject,
+    #[serde(flatten)]
+    pub more_properties: Value,
+}
+
 /// Credential subject of type RevocationList2020, expected to be used in a Verifiable Credential of type [RevocationList2020Credential]
 /// <https://w3c-ccg.github.io/vc-status-rl-2020/#revocationlist2020credential>
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -124,9 +214,33 @@ pub struct StatusList2021 {
     pub more_properties: Value,
 }
 
+/// Credential subject of type BitstringStatusList, expected to be used in a Verifiable Credential of type [BitstringStatusListCredential]
+///
+/// The list contains statuses of a fixed size in bits, given by the `statusSize` of the
+/// [entries](BitstringStatusListEntry) referring to it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BitstringStatusList {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<URI>,
+    /// Purposes of the statuses in the list
+    pub status_purpose: OneOrMany<String>,
+    pub encoded_list: MultibaseEncodedList,
+    /// Time in milliseconds for which the list may be cached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
+    #[serde(flatten)]
+    pub more_properties: Value,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct EncodedList(pub String);
 
+/// [Multibase](https://www.w3.org/TR/controller-document/#multibase-0)-encoded GZIP-compressed
+/// bitstring of a [BitstringStatusList]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MultibaseEncodedList(pub String);
+
 #[deprecated(note = "Use RevocationList2020Subject or StatusList2021Subject instead")]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type")]
@@ -163,6 +277,23 @@ pub enum SetStatusError {
     ListTooLarge(usize),
     #[error("Revocation list bitstring is too small: {0}. Minimum: {1}")]
     ListTooSmall(usize, usize),
+    #[error("Invalid status size: {0}")]
+    StatusSize(usize),
+    #[error("Status value {0} does not fit in {1} bits")]
+    ValueTooLarge(u64, usize),
+}
+
+/// Error resulting from attempting to get a status from a [BitstringStatusList]
+#[derive(Error, Debug)]
+pub enum GetStatusError {
+    #[error("Decode list: {0}")]
+    Decode(#[from] DecodeListError),
+    #[error("Out of bounds: status index {0} but list length is {1}")]
+    OutOfBounds(usize, usize),
+    #[error("Invalid status size: {0}")]
+    StatusSize(usize),
+    #[error("List too small: {0}. Minimum: {1}")]
+    ListTooSmall(usize, usize),
 }
 
 impl RevocationList2020 {
@@ -194,6 +325,10 @@ impl RevocationList2020 {
 pub enum NewStatusListError {
     #[error("Unable to encode list")]
     EncodedList(#[source] NewEncodedListError),
+    #[error("Invalid status size: {0}")]
+    StatusSize(usize),
+    #[error("Status list of {0} statuses of {1} bits is too large")]
+    ListTooLarge(usize, usize),
 }
 
 impl StatusList2021 {
@@ -229,6 +364,101 @@ impl StatusList2021 {
     }
 }
 
+/// Maximum size of a status in a [BitstringStatusList], in bits
+pub const MAX_STATUS_SIZE: usize = 64;
+
+impl BitstringStatusList {
+    /// Construct a new [BitstringStatusList] of `len` statuses of `status_size` bits, all set to
+    /// zero.
+    pub fn new(
+        status_purpose: OneOrMany<String>,
+        len: usize,
+        status_size: usize,
+    ) -> Result<Self, NewStatusListError> {
+        if status_size == 0 || status_size > MAX_STATUS_SIZE {
+            return Err(NewStatusListError::StatusSize(status_size));
+        }
+        let byte_len = len
+            .checked_mul(status_size)
+            .ok_or(NewStatusListError::ListTooLarge(len, status_size))?
+            .div_ceil(8);
+        let encoded_list = MultibaseEncodedList::try_from(&List(vec![0; byte_len]))
+            .map_err(|e| NewStatusListError::EncodedList(NewEncodedListError::Encode(e)))?;
+        Ok(BitstringStatusList {
+            id: None,
+            status_purpose,
+            encoded_list,
+            ttl: None,
+            more_properties: serde_json::Value::Null,
+        })
+    }
+
+    /// Get the status at a given index in the list, for statuses of `status_size` bits.
+    pub fn get_status(&self, index: usize, status_size: usize) -> Result<u64, GetStatusError> {
+        if status_size == 0 || status_size > MAX_STATUS_SIZE {
+            return Err(GetStatusError::StatusSize(status_size));
+        }
+        let list = List::try_from(&self.encoded_list)?;
+        let bitstring_len = list.0.len().saturating_mul(8);
+        if bitstring_len < MIN_BITSTRING_LENGTH {
+            return Err(GetStatusError::ListTooSmall(
+                bitstring_len,
+                MIN_BITSTRING_LENGTH,
+            ));
+        }
+        let len = bitstring_len / status_size;
+        let start = match index.checked_mul(status_size) {
+            Some(start) if index < len => start,
+            _ => return Err(GetStatusError::OutOfBounds(index, len)),
+        };
+        let bitstring = BitSlice::<Msb0, u8>::from_slice(&list.0[..])
+            .map_err(|_| GetStatusError::OutOfBounds(index, len))?;
+        Ok((start..start + status_size).fold(0, |value, i| value << 1 | u64::from(bitstring[i])))
+    }
+
+    /// Set the status at a given index in the list, for statuses of `status_size` bits.
+    ///
+    /// The first bit of the bitstring is the most significant bit of its first byte.
+    pub fn set_status(
+        &mut self,
+        index: usize,
+        status_size: usize,
+        value: u64,
+    ) -> Result<(), SetStatusError> {
+        if status_size == 0 || status_size > MAX_STATUS_SIZE {
+            return Err(SetStatusError::StatusSize(status_size));
+        }
+        if status_size < MAX_STATUS_SIZE && value >> status_size != 0 {
+            return Err(SetStatusError::ValueTooLarge(value, status_size));
+        }
+        let mut list = List::try_from(&self.encoded_list)?;
+        let bitstring_len = list.0.len().saturating_mul(8);
+        let mut bitstring = BitVec::<Msb0, u8>::try_from_vec(list.0)
+            .map_err(|_| SetStatusError::ListTooLarge(bitstring_len))?;
+        if bitstring_len < MIN_BITSTRING_LENGTH {
+            return Err(SetStatusError::ListTooSmall(
+                bitstring_len,
+                MIN_BITSTRING_LENGTH,
+            ));
+        }
+        let start = match index.checked_mul(status_size) {
+            Some(start) if start.checked_add(status_size) <= Some(bitstring_len) => start,
+            _ => {
+                return Err(SetStatusError::OutOfBounds(
+                    index,
+                    bitstring_len / status_size,
+                ))
+            }
+        };
+        for i in 0..status_size {
+            bitstring.set(start + i, value >> (status_size - 1 - i) & 1 == 1);
+        }
+        list.0 = bitstring.into_vec();
+        self.encoded_list = MultibaseEncodedList::try_from(&list)?;
+        Ok(())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ListIterDecodeError {
     #[error("Unable to reference indexes: {0}")]
@@ -259,6 +489,8 @@ pub enum DecodeListError {
     Build(#[from] base64::DecodeError),
     #[error("Decompression: {0}")]
     Decompress(#[from] std::io::Error),
+    #[error("Multibase: {0}")]
+    Multibase(#[from] multibase::Error),
 }
 
 #[derive(Error, Debug)]
@@ -325,6 +557,31 @@ impl TryFrom<&List> for EncodedList {
     }
 }
 
+impl TryFrom<&MultibaseEncodedList> for List {
+    type Error = DecodeListError;
+    fn try_from(encoded_list: &MultibaseEncodedList) -> Result<Self, Self::Error> {
+        let (_base, bytes) = multibase::decode(&encoded_list.0)?;
+        let mut data = Vec::new();
+        use flate2::bufread::GzDecoder;
+        use std::io::Read;
+        GzDecoder::new(bytes.as_slice()).read_to_end(&mut data)?;
+        Ok(Self(data))
+    }
+}
+
+impl TryFrom<&List> for MultibaseEncodedList {
+    type Error = EncodeListError;
+    fn try_from(list: &List) -> Result<Self, Self::Error> {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        let mut e = GzEncoder::new(Vec::new(), Compression::default());
+        e.write_all(&list.0)?;
+        let bytes = e.finish()?;
+        let string = multibase::encode(multibase::Base::Base64Url, bytes);
+        Ok(MultibaseEncodedList(string))
+    }
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl CredentialStatus for RevocationList2020Status {
@@ -340,7 +597,10 @@ impl CredentialStatus for RevocationList2020Status {
         let issuer_id = match &credential.issuer {
             Some(issuer) => issuer.get_id().clone(),
             None => {
-                return result.with_error("Credential is missing issuer".to_string());
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Credential is missing issuer",
+                );
             }
         };
         if !credential
@@ -348,63 +608,94 @@ impl CredentialStatus for RevocationList2020Status {
             .contains_uri(REVOCATION_LIST_2020_V1_CONTEXT)
         {
             // TODO: support JSON-LD credentials defining the terms elsewhere.
-            return result.with_error(format!(
-                "Missing expected context URI {} for credential using RevocationList2020",
-                REVOCATION_LIST_2020_V1_CONTEXT
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Missing expected context URI {} for credential using RevocationList2020",
+                    REVOCATION_LIST_2020_V1_CONTEXT
+                ),
+            );
         }
         if self.id == URI::String(self.revocation_list_credential.clone()) {
-            return result.with_error(format!(
-                "Expected revocationListCredential to be different from status id: {}",
-                self.id
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Expected revocationListCredential to be different from status id: {}",
+                    self.id
+                ),
+            );
         }
         // Check the revocation list URL before attempting to load it.
         // Revocation List 2020 does not specify an expected URL scheme (URI scheme), but
-        // examples and test vectors use https.
+        // examples and test vectors use https. The list credential may also be embedded in a
+        // data URL or referenced by a DID URL.
         match self.revocation_list_credential.split_once(':') {
-            Some(("https", _)) => (),
+            Some(("https", _)) | Some(("data", _)) | Some(("did", _)) => (),
             // TODO: an option to allow HTTP?
-            // TODO: load from DID URLs?
-            Some((_scheme, _)) => return result.with_error(format!("Invalid schema: {}", self.id)),
-            _ => return result.with_error(format!("Invalid rsrc: {}", self.id)),
+            Some((_scheme, _)) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid schema: {}", self.id),
+                )
+            }
+            _ => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid rsrc: {}", self.id),
+                )
+            }
         }
-        let revocation_list_credential =
-            match load_credential(&self.revocation_list_credential).await {
-                Ok(credential) => credential,
-                Err(e) => {
-                    return result
-                        .with_error(format!("Unable to fetch revocation list credential: {}", e));
-                }
-            };
+        let revocation_list_credential = match load_status_list_credential(
+            &self.revocation_list_credential,
+            resolver,
+            context_loader,
+        )
+        .await
+        {
+            Ok(credential) => credential,
+            Err(e) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to fetch revocation list credential: {}", e),
+                );
+            }
+        };
         let list_issuer_id = match &revocation_list_credential.issuer {
             Some(issuer) => issuer.get_id().clone(),
             None => {
-                return result
-                    .with_error("Revocation list credential is missing issuer".to_string());
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Revocation list credential is missing issuer",
+                );
             }
         };
         if issuer_id != list_issuer_id {
-            return result.with_error(format!(
-                "Revocation list issuer mismatch. Credential: {}, Revocation list: {}",
-                issuer_id, list_issuer_id
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Revocation list issuer mismatch. Credential: {}, Revocation list: {}",
+                    issuer_id, list_issuer_id
+                ),
+            );
         }
 
         match revocation_list_credential.validate() {
             Err(e) => {
-                return result.with_error(format!("Invalid list credential: {}", e));
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid list credential: {}", e),
+                );
             }
             Ok(()) => {}
         }
         let vc_result = revocation_list_credential.verify(None, resolver, context_loader).await;
-        for warning in vc_result.warnings {
-            result
-                .warnings
-                .push(format!("Revocation list: {}", warning));
+        for mut warning in vc_result.warnings {
+            warning.message = format!("Revocation list: {}", warning.message);
+            result.warnings.push(warning);
         }
-        for error in vc_result.errors {
-            result.errors.push(format!("Revocation list: {}", error));
+        for mut error in vc_result.errors {
+            error.message = format!("Revocation list: {}", error.message);
+            result.errors.push(error);
         }
         if !result.errors.is_empty() {
             return result;
@@ -415,44 +706,66 @@ impl CredentialStatus for RevocationList2020Status {
             match RevocationList2020Credential::try_from(revocation_list_credential) {
                 Ok(credential) => credential,
                 Err(e) => {
-                    return result
-                        .with_error(format!("Unable to parse revocation list credential: {}", e));
+                    return result.with_problem(
+                        ProblemCode::CredentialStatus,
+                        format!("Unable to parse revocation list credential: {}", e),
+                    );
                 }
             };
-        if revocation_list_credential.id != URI::String(self.revocation_list_credential.to_string())
+        // A credential embedded in a data URL cannot have the URL as its id.
+        if !self.revocation_list_credential.starts_with("data:")
+            && revocation_list_credential.id
+                != URI::String(self.revocation_list_credential.to_string())
         {
-            return result.with_error(format!(
-                "Revocation list credential id mismatch. revocationListCredential: {}, id: {}",
-                self.revocation_list_credential, revocation_list_credential.id
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Revocation list credential id mismatch. revocationListCredential: {}, id: {}",
+                    self.revocation_list_credential, revocation_list_credential.id
+                ),
+            );
         }
         let RevocationList2020Subject::RevocationList2020(revocation_list) =
             revocation_list_credential.credential_subject;
 
         let list = match List::try_from(&revocation_list.encoded_list) {
             Ok(list) => list,
-            Err(e) => return result.with_error(format!("Unable to decode revocation list: {}", e)),
+            Err(e) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to decode revocation list: {}", e),
+                )
+            }
         };
         let credential_index = self.revocation_list_index.0;
         use bitvec::prelude::*;
         let bitstring = match BitVec::<Lsb0, u8>::try_from_vec(list.0) {
             Ok(bitstring) => bitstring,
             Err(list) => {
-                return result.with_error(format!(
-                    "Revocation list is too large for bitvec: {}",
-                    list.len()
-                ))
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Revocation list is too large for bitvec: {}", list.len()),
+                )
             }
         };
         let revoked = match bitstring.get(credential_index) {
             Some(bitref) => *bitref,
             None => {
-                return result
-                    .with_error("Credential index in revocation list is invalid.".to_string());
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Credential index in revocation list is invalid.",
+                );
             }
         };
+        result.statuses.push(StatusValue {
+            purpose: "revocation".to_string(),
+            value: u64::from(revoked),
+            message: None,
+            status_list_credential: self.revocation_list_credential.clone(),
+            status_list_index: credential_index,
+        });
         if revoked {
-            return result.with_error("Credential is revoked.".to_string());
+            return result.with_problem(ProblemCode::Revoked, "Credential is revoked.");
         }
         result
     }
@@ -473,66 +786,102 @@ impl CredentialStatus for StatusList2021Entry {
         let issuer_id = match &credential.issuer {
             Some(issuer) => issuer.get_id().clone(),
             None => {
-                return result.with_error("Credential is missing issuer".to_string());
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Credential is missing issuer",
+                );
             }
         };
         if !credential.context.contains_uri(STATUS_LIST_2021_V1_CONTEXT) {
             // TODO: support JSON-LD credentials defining the terms elsewhere.
-            return result.with_error(format!(
-                "Missing expected context URI {} for credential using StatusList2021",
-                STATUS_LIST_2021_V1_CONTEXT
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Missing expected context URI {} for credential using StatusList2021",
+                    STATUS_LIST_2021_V1_CONTEXT
+                ),
+            );
         }
         if self.id == URI::String(self.status_list_credential.clone()) {
-            return result.with_error(format!(
-                "Expected statusListCredential to be different from status id: {}",
-                self.id
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Expected statusListCredential to be different from status id: {}",
+                    self.id
+                ),
+            );
         }
         // Check the status list URL before attempting to load it.
         // Status List 2021 does not specify an expected URL scheme (URI scheme), but
-        // examples and test vectors use https.
+        // examples and test vectors use https. The list credential may also be embedded in a
+        // data URL or referenced by a DID URL.
         match self.status_list_credential.split_once(':') {
-            Some(("https", _)) => (),
+            Some(("https", _)) | Some(("data", _)) | Some(("did", _)) => (),
             // TODO: an option to allow HTTP?
-            // TODO: load from DID URLs?
-            Some((_scheme, _)) => return result.with_error(format!("Invalid schema: {}", self.id)),
-            _ => return result.with_error(format!("Invalid rsrc: {}", self.id)),
+            Some((_scheme, _)) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid schema: {}", self.id),
+                )
+            }
+            _ => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid rsrc: {}", self.id),
+                )
+            }
         }
-        let status_list_credential = match load_credential(&self.status_list_credential).await {
+        let status_list_credential = match load_status_list_credential(
+            &self.status_list_credential,
+            resolver,
+            context_loader,
+        )
+        .await
+        {
             Ok(credential) => credential,
             Err(e) => {
-                return result.with_error(format!(
-                    "Unable to fetch status list credential: {}",
-                    e.to_string()
-                ));
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to fetch status list credential: {}", e.to_string()),
+                );
             }
         };
         let list_issuer_id = match &status_list_credential.issuer {
             Some(issuer) => issuer.get_id().clone(),
             None => {
-                return result.with_error(format!("Status list credential is missing issuer"));
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Status list credential is missing issuer"),
+                );
             }
         };
         if issuer_id != list_issuer_id {
-            return result.with_error(format!(
-                "Status list issuer mismatch. Credential: {}, Status list: {}",
-                issuer_id, list_issuer_id
-            ));
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Status list issuer mismatch. Credential: {}, Status list: {}",
+                    issuer_id, list_issuer_id
+                ),
+            );
         }
 
         match status_list_credential.validate() {
             Err(e) => {
-                return result.with_error(format!("Invalid list credential: {}", e.to_string()));
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid list credential: {}", e.to_string()),
+                );
             }
             Ok(()) => {}
         }
         let vc_result = status_list_credential.verify(None, resolver, context_loader).await;
-        for warning in vc_result.warnings {
-            result.warnings.push(format!("Status list: {}", warning));
+        for mut warning in vc_result.warnings {
+            warning.message = format!("Status list: {}", warning.message);
+            result.warnings.push(warning);
         }
-        for error in vc_result.errors {
-            result.errors.push(format!("Status list: {}", error));
+        for mut error in vc_result.errors {
+            error.message = format!("Status list: {}", error.message);
+            result.errors.push(error);
             return result;
         }
         // Note: vc_result.checks is not checked here. It is assumed that default checks passed.
@@ -541,17 +890,23 @@ impl CredentialStatus for StatusList2021Entry {
             match StatusList2021Credential::try_from(status_list_credential) {
                 Ok(credential) => credential,
                 Err(e) => {
-                    return result.with_error(format!(
-                        "Unable to parse status list credential: {}",
-                        e.to_string()
-                    ));
+                    return result.with_problem(
+                        ProblemCode::CredentialStatus,
+                        format!("Unable to parse status list credential: {}", e.to_string()),
+                    );
                 }
             };
-        if status_list_credential.id != URI::String(self.status_list_credential.to_string()) {
-            return result.with_error(format!(
-                "Status list credential id mismatch. statusListCredential: {}, id: {}",
-                self.status_list_credential, status_list_credential.id
-            ));
+        // A credential embedded in a data URL cannot have the URL as its id.
+        if !self.status_list_credential.starts_with("data:")
+            && status_list_credential.id != URI::String(self.status_list_credential.to_string())
+        {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Status list credential id mismatch. statusListCredential: {}, id: {}",
+                    self.status_list_credential, status_list_credential.id
+                ),
+            );
         }
         let StatusList2021Subject::StatusList2021(status_list) =
             status_list_credential.credential_subject;
@@ -559,8 +914,10 @@ impl CredentialStatus for StatusList2021Entry {
         let list = match List::try_from(&status_list.encoded_list) {
             Ok(list) => list,
             Err(e) => {
-                return result
-                    .with_error(format!("Unable to decode status list: {}", e.to_string()))
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to decode status list: {}", e.to_string()),
+                )
             }
         };
         let credential_index = self.status_list_index.0;
@@ -568,21 +925,223 @@ impl CredentialStatus for StatusList2021Entry {
         let bitstring = match BitVec::<Lsb0, u8>::try_from_vec(list.0) {
             Ok(bitstring) => bitstring,
             Err(list) => {
-                return result.with_error(format!(
-                    "Revocation list is too large for bitvec: {}",
-                    list.len()
-                ))
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Revocation list is too large for bitvec: {}", list.len()),
+                )
             }
         };
         let revoked = match bitstring.get(credential_index) {
             Some(bitref) => *bitref,
             None => {
-                return result
-                    .with_error("Credential index in revocation list is invalid.".to_string());
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Credential index in revocation list is invalid.",
+                );
             }
         };
+        result.statuses.push(StatusValue {
+            purpose: self.status_purpose.clone(),
+            value: u64::from(revoked),
+            message: None,
+            status_list_credential: self.status_list_credential.clone(),
+            status_list_index: credential_index,
+        });
         if revoked {
-            return result.with_error("Credential is revoked.".to_string());
+            if self.status_purpose == "suspension" {
+                return result.with_problem(ProblemCode::Suspended, "Credential is suspended.");
+            }
+            return result.with_problem(ProblemCode::Revoked, "Credential is revoked.");
+        }
+        result
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl CredentialStatus for BitstringStatusListEntry {
+    /// Validate a credential's status according to [Bitstring Status List](https://www.w3.org/TR/vc-bitstring-status-list/#validate-algorithm).
+    ///
+    /// The status is added to the [statuses](VerificationResult::statuses) of the result. A
+    /// non-zero status is an error if the purpose is "revocation" or "suspension".
+    async fn check(
+        &self,
+        credential: &Credential,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> VerificationResult {
+        let mut result = VerificationResult::new();
+        let issuer_id = match &credential.issuer {
+            Some(issuer) => issuer.get_id(),
+            None => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Credential is missing issuer",
+                );
+            }
+        };
+        if !credential.context.contains_uri(CREDENTIALS_V2_CONTEXT) {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Missing expected context URI {} for credential using BitstringStatusList",
+                    CREDENTIALS_V2_CONTEXT
+                ),
+            );
+        }
+        if self.id == URI::String(self.status_list_credential.clone()) {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Expected statusListCredential to be different from status id: {}",
+                    self.id
+                ),
+            );
+        }
+        let status_size = self.status_size.unwrap_or(1);
+        if status_size == 0 || status_size > MAX_STATUS_SIZE {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!("Invalid statusSize: {}", status_size),
+            );
+        }
+        match self.status_list_credential.split_once(':') {
+            Some(("https", _)) | Some(("data", _)) | Some(("did", _)) => (),
+            Some((_scheme, _)) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid schema: {}", self.id),
+                )
+            }
+            _ => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Invalid rsrc: {}", self.id),
+                )
+            }
+        }
+        let status_list_credential = match load_status_list_credential(
+            &self.status_list_credential,
+            resolver,
+            context_loader,
+        )
+        .await
+        {
+            Ok(credential) => credential,
+            Err(e) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to fetch status list credential: {}", e),
+                );
+            }
+        };
+        let list_issuer_id = match &status_list_credential.issuer {
+            Some(issuer) => issuer.get_id(),
+            None => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    "Status list credential is missing issuer",
+                );
+            }
+        };
+        if issuer_id != list_issuer_id {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Status list issuer mismatch. Credential: {}, Status list: {}",
+                    issuer_id, list_issuer_id
+                ),
+            );
+        }
+        if let Err(e) = status_list_credential.validate() {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!("Invalid list credential: {}", e),
+            );
+        }
+        let vc_result = status_list_credential
+            .verify(None, resolver, context_loader)
+            .await;
+        for mut warning in vc_result.warnings {
+            warning.message = format!("Status list: {}", warning.message);
+            result.warnings.push(warning);
+        }
+        if let Some(mut error) = vc_result.errors.into_iter().next() {
+            error.message = format!("Status list: {}", error.message);
+            result.errors.push(error);
+            return result;
+        }
+        let status_list_credential =
+            match BitstringStatusListCredential::try_from(status_list_credential) {
+                Ok(credential) => credential,
+                Err(e) => {
+                    return result.with_problem(
+                        ProblemCode::CredentialStatus,
+                        format!("Unable to parse status list credential: {}", e),
+                    );
+                }
+            };
+        // A credential embedded in a data URL cannot have the URL as its id.
+        if !self.status_list_credential.starts_with("data:")
+            && status_list_credential.id != URI::String(self.status_list_credential.to_string())
+        {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Status list credential id mismatch. statusListCredential: {}, id: {}",
+                    self.status_list_credential, status_list_credential.id
+                ),
+            );
+        }
+        let BitstringStatusListSubject::BitstringStatusList(status_list) =
+            status_list_credential.credential_subject;
+        if !status_list.status_purpose.contains(&self.status_purpose) {
+            return result.with_problem(
+                ProblemCode::CredentialStatus,
+                format!(
+                    "Status purpose {} is not a purpose of the status list",
+                    self.status_purpose
+                ),
+            );
+        }
+        let credential_index = self.status_list_index.0;
+        let value = match status_list.get_status(credential_index, status_size) {
+            Ok(value) => value,
+            Err(e) => {
+                return result.with_problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to get status from status list: {}", e),
+                )
+            }
+        };
+        let message = self
+            .status_messages
+            .iter()
+            .flatten()
+            .find_map(|status_message| {
+                let status = status_message.status.strip_prefix("0x")?;
+                match u64::from_str_radix(status, 16) {
+                    Ok(status) if status == value => Some(status_message.message.clone()),
+                    _ => None,
+                }
+            });
+        result.statuses.push(StatusValue {
+            purpose: self.status_purpose.clone(),
+            value,
+            message,
+            status_list_credential: self.status_list_credential.clone(),
+            status_list_index: credential_index,
+        });
+        if value != 0 {
+            match self.status_purpose.as_str() {
+                "revocation" => {
+                    return result.with_problem(ProblemCode::Revoked, "Credential is revoked.")
+                }
+                "suspension" => {
+                    return result.with_problem(ProblemCode::Suspended, "Credential is suspended.")
+                }
+                _ => {}
+            }
         }
         result
     }
@@ -613,16 +1172,98 @@ pub enum LoadResourceError {
     /// Unable to convert content-length header value.
     #[error("Unable to convert content-length header value")]
     ContentLengthConversion(#[source] std::num::TryFromIntError),
+    #[error("Unable to read file: {0}")]
+    Read(#[source] std::io::Error),
+    #[error("Invalid data URL: {0}")]
+    DataURL(String),
+    #[error("Unable to dereference DID URL: {0}")]
+    Dereference(String),
+}
+
+/// Resource loaded by a [ResourceLoader]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedResource {
+    pub data: Vec<u8>,
+    /// Value of the `Cache-Control` HTTP header, if any
+    pub cache_control: Option<String>,
+    /// Value of the `ETag` HTTP header, if any
+    pub etag: Option<String>,
+}
+
+impl From<Vec<u8>> for LoadedResource {
+    fn from(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            ..Default::default()
+        }
+    }
+}
+
+/// Loader of resources referenced by credential statuses, i.e. status list credentials.
+///
+/// A loader can be set on the [ContextLoader] using
+/// [`with_resource_loader`](ContextLoader::with_resource_loader). Otherwise,
+/// [HttpResourceLoader] is used.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ResourceLoader: Send + Sync {
+    /// Load the resource at the given URL.
+    async fn load(&self, url: &str) -> Result<LoadedResource, LoadResourceError>;
+
+    /// Load the resource at the given URL, unless its entity tag is still `etag`, in which case
+    /// `None` is returned.
+    ///
+    /// The default implementation always loads the resource.
+    async fn load_if_none_match(
+        &self,
+        url: &str,
+        _etag: &str,
+    ) -> Result<Option<LoadedResource>, LoadResourceError> {
+        Ok(Some(self.load(url).await?))
+    }
+}
+
+/// [ResourceLoader] fetching resources over HTTP(S).
+///
+/// The size of loaded resources must not be greater than [`MAX_RESPONSE_LENGTH`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpResourceLoader;
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ResourceLoader for HttpResourceLoader {
+    async fn load(&self, url: &str) -> Result<LoadedResource, LoadResourceError> {
+        load_resource(url, None)
+            .await?
+            .ok_or_else(|| LoadResourceError::HTTP("Unexpected 304 Not Modified".to_string()))
+    }
+
+    async fn load_if_none_match(
+        &self,
+        url: &str,
+        etag: &str,
+    ) -> Result<Option<LoadedResource>, LoadResourceError> {
+        load_resource(url, Some(etag)).await
+    }
 }
 
-async fn load_resource(url: &str) -> Result<Vec<u8>, LoadResourceError> {
+async fn load_resource(
+    url: &str,
+    etag: Option<&str>,
+) -> Result<Option<LoadedResource>, LoadResourceError> {
     #[cfg(test)]
     match url {
         crate::vc::tests::EXAMPLE_REVOCATION_2020_LIST_URL => {
-            return Ok(crate::vc::tests::EXAMPLE_REVOCATION_2020_LIST.to_vec());
+            return Ok(Some(
+                crate::vc::tests::EXAMPLE_REVOCATION_2020_LIST
+                    .to_vec()
+                    .into(),
+            ));
         }
         crate::vc::tests::EXAMPLE_STATUS_LIST_2021_URL => {
-            return Ok(crate::vc::tests::EXAMPLE_STATUS_LIST_2021.to_vec());
+            return Ok(Some(
+                crate::vc::tests::EXAMPLE_STATUS_LIST_2021.to_vec().into(),
+            ));
         }
         _ => {}
     }
@@ -636,18 +1277,28 @@ async fn load_resource(url: &str) -> Result<Vec<u8>, LoadResourceError> {
         .build()
         .map_err(LoadResourceError::Build)?;
     let accept = "application/json".to_string();
-    let resp = client
-        .get(url)
-        .header("Accept", accept)
-        .send()
-        .await
-        .map_err(LoadResourceError::Request)?;
+    let mut request = client.get(url).header("Accept", accept);
+    if let Some(etag) = etag {
+        request = request.header("If-None-Match", etag);
+    }
+    let resp = request.send().await.map_err(LoadResourceError::Request)?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
     if let Err(err) = resp.error_for_status_ref() {
         if err.status() == Some(reqwest::StatusCode::NOT_FOUND) {
             return Err(LoadResourceError::NotFound);
         }
         return Err(LoadResourceError::HTTP(err.to_string()));
     }
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    let cache_control = header(reqwest::header::CACHE_CONTROL);
+    let etag = header(reqwest::header::ETAG);
     #[allow(unused_variables)]
     let content_length_opt = if let Some(content_length) = resp.content_length() {
         let len =
@@ -681,7 +1332,11 @@ async fn load_resource(url: &str) -> Result<Vec<u8>, LoadResourceError> {
                 max: MAX_RESPONSE_LENGTH,
             });
         }
-        Ok(bytes)
+        Ok(Some(LoadedResource {
+            data: bytes,
+            cache_control,
+            etag,
+        }))
     }
     #[cfg(not(target_arch = "wasm32"))]
     {
@@ -706,10 +1361,258 @@ async fn load_resource(url: &str) -> Result<Vec<u8>, LoadResourceError> {
             }
             bytes.append(&mut chunk.to_vec());
         }
+        Ok(Some(LoadedResource {
+            data: bytes,
+            cache_control,
+            etag,
+        }))
+    }
+}
+
+/// [ResourceLoader] of resources held in memory, e.g. for testing or offline use.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryResourceLoader {
+    resources: HashMap<String, Vec<u8>>,
+}
+
+impl MemoryResourceLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a resource, returning the resource previously added with the same URL, if any.
+    pub fn insert(&mut self, url: &str, data: Vec<u8>) -> Option<Vec<u8>> {
+        self.resources.insert(url.to_string(), data)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ResourceLoader for MemoryResourceLoader {
+    async fn load(&self, url: &str) -> Result<LoadedResource, LoadResourceError> {
+        match self.resources.get(url) {
+            Some(data) => Ok(data.clone().into()),
+            None => Err(LoadResourceError::NotFound),
+        }
+    }
+}
+
+/// [ResourceLoader] of resources stored in files, e.g. for offline use. The files are read each
+/// time a resource is loaded.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Default)]
+pub struct FileResourceLoader {
+    paths: HashMap<String, PathBuf>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileResourceLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the path of the file containing a resource.
+    pub fn insert(&mut self, url: &str, path: impl Into<PathBuf>) -> Option<PathBuf> {
+        self.paths.insert(url.to_string(), path.into())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl ResourceLoader for FileResourceLoader {
+    async fn load(&self, url: &str) -> Result<LoadedResource, LoadResourceError> {
+        let path = self.paths.get(url).ok_or(LoadResourceError::NotFound)?;
+        let path = path.clone();
+        let data = async_std::task::spawn_blocking(move || std::fs::read(path))
+            .await
+            .map_err(LoadResourceError::Read)?;
+        if data.len() > MAX_RESPONSE_LENGTH {
+            return Err(LoadResourceError::TooLarge {
+                size: data.len(),
+                max: MAX_RESPONSE_LENGTH,
+            });
+        }
+        Ok(data.into())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedResource {
+    resource: LoadedResource,
+    expires: DateTime<Utc>,
+}
+
+/// Largest `max-age` honored by [CachingResourceLoader], in seconds.
+const MAX_DELTA_SECONDS: i64 = 1 << 31;
+
+/// [ResourceLoader] caching the resources loaded by another loader, according to their
+/// `Cache-Control` and `ETag` headers.
+///
+/// A resource is reused without revalidation until its `max-age` has elapsed. After that, or if
+/// it has `no-cache`, it is revalidated with its entity tag if it has one, and loaded again
+/// otherwise. Resources with `no-store`, or with neither `max-age` nor an entity tag, are not
+/// cached.
+#[derive(Debug, Default)]
+pub struct CachingResourceLoader<L: ResourceLoader> {
+    loader: L,
+    cache: Mutex<HashMap<String, CachedResource>>,
+}
+
+impl<L: ResourceLoader> CachingResourceLoader<L> {
+    pub fn new(loader: L) -> Self {
+        Self {
+            loader,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remove all cached resources.
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.clear();
+        }
+    }
+
+    fn store(&self, url: &str, resource: &LoadedResource, now: DateTime<Utc>) {
+        let mut max_age = 0;
+        let mut no_cache = false;
+        let mut no_store = false;
+        for directive in resource.cache_control.iter().flat_map(|cc| cc.split(',')) {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.split_once('=') {
+                Some(("max-age", value)) => {
+                    // Overly large values are clamped as per RFC 9111 section 1.2.2.
+                    let value = value.trim_matches('"');
+                    max_age = if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+                        value.parse().map_or(MAX_DELTA_SECONDS, |seconds: i64| {
+                            seconds.min(MAX_DELTA_SECONDS)
+                        })
+                    } else {
+                        0
+                    }
+                }
+                _ if directive == "no-store" => no_store = true,
+                _ if directive == "no-cache" => no_cache = true,
+                _ => {}
+            }
+        }
+        // no-cache requires revalidation whatever the max-age, in any order of the directives.
+        if no_cache {
+            max_age = 0;
+        }
+        let mut cache = match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(_) => return,
+        };
+        let expires = match now.checked_add_signed(Duration::seconds(max_age)) {
+            Some(expires) if !no_store && (max_age > 0 || resource.etag.is_some()) => expires,
+            _ => {
+                cache.remove(url);
+                return;
+            }
+        };
+        cache.insert(
+            url.to_string(),
+            CachedResource {
+                resource: resource.clone(),
+                expires,
+            },
+        );
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<L: ResourceLoader> ResourceLoader for CachingResourceLoader<L> {
+    async fn load(&self, url: &str) -> Result<LoadedResource, LoadResourceError> {
+        let cached = match self.cache.lock() {
+            Ok(cache) => cache.get(url).cloned(),
+            Err(_) => None,
+        };
+        let now = Utc::now();
+        let resource = match cached {
+            Some(cached) if cached.expires > now => return Ok(cached.resource),
+            Some(CachedResource {
+                resource: cached @ LoadedResource { etag: Some(_), .. },
+                ..
+            }) => {
+                let etag = cached.etag.as_deref().unwrap_or_default();
+                match self.loader.load_if_none_match(url, etag).await? {
+                    Some(resource) => resource,
+                    None => cached,
+                }
+            }
+            _ => self.loader.load(url).await?,
+        };
+        self.store(url, &resource, now);
+        Ok(resource)
+    }
+}
+
+/// Decode the data of a [`data:` URL](https://www.rfc-editor.org/rfc/rfc2397).
+fn decode_data_url(url: &str) -> Result<Vec<u8>, LoadResourceError> {
+    let (header, data) = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(|| LoadResourceError::DataURL("Missing data".to_string()))?;
+    let mut bytes = Vec::with_capacity(data.len());
+    let mut chars = data.bytes();
+    while let Some(c) = chars.next() {
+        if c != b'%' {
+            bytes.push(c);
+            continue;
+        }
+        let hex = [chars.next(), chars.next()];
+        let byte = match hex {
+            [Some(h), Some(l)] => std::str::from_utf8(&[h, l])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        bytes.push(
+            byte.ok_or_else(|| LoadResourceError::DataURL("Invalid percent-encoding".to_string()))?,
+        );
+    }
+    if header.ends_with(";base64") {
+        base64::decode(&bytes).map_err(|e| LoadResourceError::DataURL(e.to_string()))
+    } else {
         Ok(bytes)
     }
 }
 
+/// Load a resource referenced by a credential status. `data:` URLs are decoded, DID URLs are
+/// dereferenced, and other URLs are loaded using `loader`.
+pub async fn load_status_resource(
+    url: &str,
+    resolver: &dyn DIDResolver,
+    loader: &dyn ResourceLoader,
+) -> Result<Vec<u8>, LoadResourceError> {
+    if url.starts_with("data:") {
+        return decode_data_url(url);
+    }
+    if !url.starts_with("did:") {
+        return Ok(loader.load(url).await?.data);
+    }
+    let (deref_meta, content, _) =
+        dereference(resolver, url, &DereferencingInputMetadata::default()).await;
+    if let Some(error) = deref_meta.error {
+        return Err(LoadResourceError::Dereference(error));
+    }
+    match content {
+        // Service endpoint
+        Content::URL(url) if !url.starts_with("did:") => {
+            Ok(Box::pin(load_status_resource(&url, resolver, loader)).await?)
+        }
+        Content::Data(data) => Ok(data),
+        Content::Object(Resource::Object(object)) => {
+            serde_json::to_vec(&object).map_err(|e| LoadResourceError::Dereference(e.to_string()))
+        }
+        _ => Err(LoadResourceError::Dereference(
+            "Unexpected content".to_string(),
+        )),
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum LoadCredentialError {
     #[error("Unable to load resource: {0}")]
@@ -723,7 +1626,24 @@ pub enum LoadCredentialError {
 ///
 /// The size of the loaded credential must not be greater than [`MAX_RESPONSE_LENGTH`].
 pub async fn load_credential(url: &str) -> Result<Credential, LoadCredentialError> {
-    let data = load_resource(url).await?;
+    let data = HttpResourceLoader.load(url).await?.data;
+    // TODO: support JWT-VC
+    let credential: Credential = serde_json::from_slice(&data)?;
+    Ok(credential)
+}
+
+/// Load a status list credential using [load_status_resource], with the context loader's
+/// [ResourceLoader], or [HttpResourceLoader] if it has none.
+/// The resulting verifiable credential is not yet validated or verified.
+pub async fn load_status_list_credential(
+    url: &str,
+    resolver: &dyn DIDResolver,
+    context_loader: &ContextLoader,
+) -> Result<Credential, LoadCredentialError> {
+    let loader = context_loader
+        .resource_loader()
+        .unwrap_or(&HttpResourceLoader);
+    let data = load_status_resource(url, resolver, loader).await?;
     // TODO: support JWT-VC
     let credential: Credential = serde_json::from_slice(&data)?;
     Ok(credential)
@@ -831,6 +1751,419 @@ impl TryFrom<StatusList2021Credential> for Credential {
     }
 }
 
+/// Convert Credential to a [BitstringStatusListCredential], while [validating](https://www.w3.org/TR/vc-bitstring-status-list/#validate-algorithm) it.
+///
+/// Note: this is a lossy operation. Only known BitstringStatusListCredential fields are preserved.
+impl TryFrom<Credential> for BitstringStatusListCredential {
+    type Error = CredentialConversionError;
+    fn try_from(credential: Credential) -> Result<Self, Self::Error> {
+        if !credential.context.contains_uri(CREDENTIALS_V2_CONTEXT) {
+            return Err(CredentialConversionError::MissingContext(
+                CREDENTIALS_V2_CONTEXT,
+            ));
+        }
+        if !credential
+            .type_
+            .contains(&"BitstringStatusListCredential".to_string())
+        {
+            return Err(CredentialConversionError::MissingType(
+                "BitstringStatusListCredential",
+                credential.type_,
+            ));
+        }
+        let credential =
+            serde_json::to_value(credential).map_err(CredentialConversionError::ToValue)?;
+        let credential =
+            serde_json::from_value(credential).map_err(CredentialConversionError::FromValue)?;
+        Ok(credential)
+    }
+}
+
+impl TryFrom<BitstringStatusListCredential> for Credential {
+    type Error = CredentialConversionError;
+    fn try_from(credential: BitstringStatusListCredential) -> Result<Self, Self::Error> {
+        let mut credential =
+            serde_json::to_value(credential).map_err(CredentialConversionError::ToValue)?;
+        use serde_json::json;
+        credential["@context"] = json!([CREDENTIALS_V2_CONTEXT]);
+        credential["type"] = json!(["VerifiableCredential", "BitstringStatusListCredential"]);
+        let credential =
+            serde_json::from_value(credential).map_err(CredentialConversionError::FromValue)?;
+        Ok(credential)
+    }
+}
+
+/// State of the status lists managed by a [StatusListManager], as kept in a [StatusListStore].
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusListManagerState {
+    /// Status lists, in order of creation. New indexes are allocated from the last one.
+    pub lists: Vec<ManagedStatusList>,
+}
+
+/// Status list managed by a [StatusListManager]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedStatusList {
+    /// URL of the [StatusList2021Credential] publishing the list
+    pub url: URL,
+    pub status_list: StatusList2021,
+    /// Length of the list, in bits
+    pub length: usize,
+    /// Indexes allocated in the list, by credential ID
+    pub allocations: BTreeMap<String, usize>,
+}
+
+/// Number of random indexes tried when allocating an index, before falling back to choosing among
+/// all the free indexes of the list.
+const ALLOCATION_ATTEMPTS: usize = 64;
+
+impl ManagedStatusList {
+    /// Choose a free index at random, or `None` if the list is full.
+    ///
+    /// Random indexes are tried until a free one is found, so that allocating does not take time
+    /// proportional to the list length unless the list is nearly full.
+    fn choose_free_index(&self, rng: &mut impl rand_old::Rng) -> Option<usize> {
+        if self.allocations.len() >= self.length {
+            return None;
+        }
+        let used: HashSet<usize> = self.allocations.values().copied().collect();
+        for _ in 0..ALLOCATION_ATTEMPTS {
+            let index = rng.gen_range(0, self.length);
+            if !used.contains(&index) {
+                return Some(index);
+            }
+        }
+        (0..self.length).filter(|i| !used.contains(i)).choose(rng)
+    }
+
+    fn entry(&self, index: usize, status_purpose: &str) -> StatusList2021Entry {
+        StatusList2021Entry {
+            id: URI::String(format!("{}#{}", self.url, index)),
+            status_purpose: status_purpose.to_string(),
+            status_list_index: RevocationListIndex(index),
+            status_list_credential: self.url.clone(),
+        }
+    }
+}
+
+/// Error resulting from using a [StatusListManager] or its [StatusListStore]
+#[derive(Error, Debug)]
+pub enum StatusListManagerError {
+    #[error("Unable to access status list store: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Unable to (de)serialize status list store: {0}")]
+    JSON(#[from] serde_json::Error),
+    #[error("Status list store: {0}")]
+    Store(String),
+    #[error("No status allocated for credential: {0}")]
+    UnknownCredential(String),
+    #[error("Unknown status list: {0}")]
+    UnknownList(String),
+    #[error("Unable to create status list: {0}")]
+    NewStatusList(#[from] NewStatusListError),
+    #[error("Unable to set status: {0}")]
+    SetStatus(#[from] SetStatusError),
+    #[error("Unable to convert status list credential: {0}")]
+    Conversion(#[from] CredentialConversionError),
+    #[error("Unable to sign status list credential: {0}")]
+    Proof(#[from] crate::error::Error),
+}
+
+/// Storage for the state of a [StatusListManager]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait StatusListStore: Send + Sync {
+    /// Load the state, or the default (empty) state if none was saved yet.
+    async fn load(&self) -> Result<StatusListManagerState, StatusListManagerError>;
+    /// Save the state, replacing the previously saved state.
+    async fn save(&self, state: &StatusListManagerState) -> Result<(), StatusListManagerError>;
+}
+
+/// [StatusListStore] keeping the state in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStatusListStore {
+    state: Mutex<StatusListManagerState>,
+}
+
+impl MemoryStatusListStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl StatusListStore for MemoryStatusListStore {
+    async fn load(&self) -> Result<StatusListManagerState, StatusListManagerError> {
+        let state = self
+            .state
+            .lock()
+            .map_err(|e| StatusListManagerError::Store(e.to_string()))?;
+        Ok(state.clone())
+    }
+
+    async fn save(&self, state: &StatusListManagerState) -> Result<(), StatusListManagerError> {
+        let mut stored = self
+            .state
+            .lock()
+            .map_err(|e| StatusListManagerError::Store(e.to_string()))?;
+        *stored = state.clone();
+        Ok(())
+    }
+}
+
+/// [StatusListStore] keeping the state in a JSON file. The file is read and written on each
+/// operation, and created when the state is first saved. Saving writes a temporary file next to
+/// it and renames it over the target, so that a crash never leaves a partially written state.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileStatusListStore {
+    path: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStatusListStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl StatusListStore for FileStatusListStore {
+    async fn load(&self) -> Result<StatusListManagerState, StatusListManagerError> {
+        let path = self.path.clone();
+        let read = async_std::task::spawn_blocking(move || std::fs::read_to_string(path)).await;
+        let json = match read {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(StatusListManagerState::default())
+            }
+            Err(e) => return Err(e.into()),
+        };
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    async fn save(&self, state: &StatusListManagerState) -> Result<(), StatusListManagerError> {
+        let json = serde_json::to_string(state)?;
+        let path = self.path.clone();
+        async_std::task::spawn_blocking(move || write_file_atomic(&path, json.as_bytes())).await?;
+        Ok(())
+    }
+}
+
+/// Replace the file at `path` with `contents`: write and sync a temporary file in the same
+/// directory, then rename it over the target.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_file_atomic(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let file_name = path.file_name().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Path has no file name")
+    })?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    let result = std::fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| std::fs::rename(&tmp_path, path));
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    // Persist the rename itself.
+    #[cfg(unix)]
+    {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => std::path::Path::new("."),
+        };
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Issuer-side manager of [StatusList2021] lists.
+///
+/// The manager allocates status list indexes to credentials, sets and clears their statuses, and
+/// issues the [StatusList2021Credential]s to publish. Indexes are allocated at random among the
+/// free indexes of the current list, so that neighbouring indexes do not correlate with issuance
+/// order. When the current list is full, a new list is started at the next URL.
+///
+/// Only [StatusList2021] lists, with one-bit statuses, are managed: multi-bit
+/// [BitstringStatusList]s, e.g. for status messages, must be maintained by the caller.
+///
+/// Each operation loads and saves the whole state from the [StatusListStore]; operations on a
+/// store shared between managers must not run concurrently.
+pub struct StatusListManager<S: StatusListStore> {
+    store: S,
+    issuer: URI,
+    base_url: URL,
+    status_purpose: String,
+    list_length: usize,
+}
+
+impl<S: StatusListStore> StatusListManager<S> {
+    /// Construct a manager of lists issued by `issuer` for the given status purpose, e.g.
+    /// "revocation" or "suspension".
+    ///
+    /// List credential URLs are made by appending a sequence number, starting at 1, to
+    /// `base_url`.
+    pub fn new(
+        store: S,
+        issuer: URI,
+        base_url: impl Into<URL>,
+        status_purpose: impl Into<String>,
+    ) -> Self {
+        Self {
+            store,
+            issuer,
+            base_url: base_url.into(),
+            status_purpose: status_purpose.into(),
+            list_length: MIN_BITSTRING_LENGTH,
+        }
+    }
+
+    /// Set the length, in bits, of new lists. Defaults to [MIN_BITSTRING_LENGTH].
+    pub fn with_list_length(mut self, list_length: usize) -> Self {
+        self.list_length = list_length;
+        self
+    }
+
+    /// Add a new list to the state, returning its URL.
+    fn new_list(&self, state: &mut StatusListManagerState) -> Result<URL, StatusListManagerError> {
+        let mut status_list = StatusList2021::new(self.list_length)?;
+        status_list.more_properties = serde_json::json!({
+            "statusPurpose": self.status_purpose,
+        });
+        let url = format!("{}{}", self.base_url, state.lists.len() + 1);
+        state.lists.push(ManagedStatusList {
+            url: url.clone(),
+            status_list,
+            length: self.list_length,
+            allocations: BTreeMap::new(),
+        });
+        Ok(url)
+    }
+
+    /// Allocate a status list index to a credential, returning the status entry to use as the
+    /// credential's `credentialStatus`. The status is initially cleared.
+    ///
+    /// If an index is already allocated to the credential, its entry is returned.
+    pub async fn allocate(
+        &self,
+        credential_id: &str,
+    ) -> Result<StatusList2021Entry, StatusListManagerError> {
+        let mut state = self.store.load().await?;
+        for list in &state.lists {
+            if let Some(index) = list.allocations.get(credential_id) {
+                return Ok(list.entry(*index, &self.status_purpose));
+            }
+        }
+        loop {
+            if let Some(list) = state.lists.last_mut() {
+                if let Some(index) = list.choose_free_index(&mut rand_old::rngs::OsRng {}) {
+                    list.allocations.insert(credential_id.to_string(), index);
+                    let entry = list.entry(index, &self.status_purpose);
+                    self.store.save(&state).await?;
+                    return Ok(entry);
+                }
+            }
+            self.new_list(&mut state)?;
+        }
+    }
+
+    /// Start a new list: subsequent indexes are allocated from it.
+    ///
+    /// Returns the URL of the new list credential.
+    pub async fn rotate(&self) -> Result<URL, StatusListManagerError> {
+        let mut state = self.store.load().await?;
+        let url = self.new_list(&mut state)?;
+        self.store.save(&state).await?;
+        Ok(url)
+    }
+
+    /// Get the status entry allocated to a credential, if any.
+    pub async fn entry(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<StatusList2021Entry>, StatusListManagerError> {
+        let state = self.store.load().await?;
+        Ok(state.lists.iter().find_map(|list| {
+            list.allocations
+                .get(credential_id)
+                .map(|index| list.entry(*index, &self.status_purpose))
+        }))
+    }
+
+    /// Set the status of a credential, e.g. to revoke or suspend it.
+    ///
+    /// The list credential must then be [re-issued](Self::issue_list_credential) for the change
+    /// to be published.
+    pub async fn set_status(
+        &self,
+        credential_id: &str,
+        status: bool,
+    ) -> Result<(), StatusListManagerError> {
+        let mut state = self.store.load().await?;
+        let list = state
+            .lists
+            .iter_mut()
+            .find(|list| list.allocations.contains_key(credential_id))
+            .ok_or_else(|| StatusListManagerError::UnknownCredential(credential_id.to_string()))?;
+        let index = list.allocations[credential_id];
+        list.status_list.set_status(index, status)?;
+        self.store.save(&state).await?;
+        Ok(())
+    }
+
+    /// Clear the status of a credential, e.g. to lift its suspension.
+    pub async fn clear_status(&self, credential_id: &str) -> Result<(), StatusListManagerError> {
+        self.set_status(credential_id, false).await
+    }
+
+    /// Get the URLs of the managed list credentials, in order of creation.
+    pub async fn list_urls(&self) -> Result<Vec<URL>, StatusListManagerError> {
+        let state = self.store.load().await?;
+        Ok(state.lists.into_iter().map(|list| list.url).collect())
+    }
+
+    /// Issue the [StatusList2021Credential] for a list, signed using
+    /// [Credential::generate_proof], with the current time as issuance date.
+    pub async fn issue_list_credential(
+        &self,
+        url: &str,
+        signer: &dyn Signer,
+        options: &LinkedDataProofOptions,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> Result<Credential, StatusListManagerError> {
+        let state = self.store.load().await?;
+        let list = state
+            .lists
+            .into_iter()
+            .find(|list| list.url == url)
+            .ok_or_else(|| StatusListManagerError::UnknownList(url.to_string()))?;
+        let list_credential = StatusList2021Credential {
+            id: URI::String(list.url),
+            issuer: Issuer::URI(self.issuer.clone()),
+            credential_subject: StatusList2021Subject::StatusList2021(list.status_list),
+            more_properties: Value::Null,
+        };
+        let mut credential = Credential::try_from(list_credential)?;
+        credential.issuance_date = Some(VCDateTime::from(crate::ldp::now_ms()));
+        let proof = credential
+            .generate_proof(signer, options, resolver, context_loader)
+            .await?;
+        credential.add_proof(proof);
+        Ok(credential)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -858,4 +2191,340 @@ mod tests {
             .collect::<Vec<usize>>();
         assert_eq!(revoked_indexes, vec![1, 5]);
     }
+
+    #[test]
+    fn bitstring_status() {
+        let purposes = OneOrMany::Many(vec!["revocation".to_string(), "message".to_string()]);
+        let mut sl = BitstringStatusList::new(purposes, MIN_BITSTRING_LENGTH, 1).unwrap();
+        assert!(sl.encoded_list.0.starts_with('u'));
+        sl.set_status(1, 1, 1).unwrap();
+        // The first index is the most significant bit of the first byte.
+        let list = List::try_from(&sl.encoded_list).unwrap();
+        assert_eq!(list.0[0], 0b0100_0000);
+        assert_eq!(sl.get_status(0, 1).unwrap(), 0);
+        assert_eq!(sl.get_status(1, 1).unwrap(), 1);
+        sl.set_status(1, 1, 0).unwrap();
+        assert_eq!(sl.get_status(1, 1).unwrap(), 0);
+
+        let purposes = OneOrMany::One("message".to_string());
+        let mut sl = BitstringStatusList::new(purposes, MIN_BITSTRING_LENGTH, 4).unwrap();
+        sl.set_status(3, 4, 0xa).unwrap();
+        sl.set_status(4, 4, 0xf).unwrap();
+        assert_eq!(sl.get_status(2, 4).unwrap(), 0);
+        assert_eq!(sl.get_status(3, 4).unwrap(), 0xa);
+        assert_eq!(sl.get_status(4, 4).unwrap(), 0xf);
+        assert!(matches!(
+            sl.set_status(5, 4, 0x10),
+            Err(SetStatusError::ValueTooLarge(0x10, 4))
+        ));
+        assert!(matches!(
+            sl.get_status(MIN_BITSTRING_LENGTH, 4),
+            Err(GetStatusError::OutOfBounds(..))
+        ));
+        // Indexes whose bit offset overflows are out of bounds, rather than wrapping around.
+        let overflowing_index = usize::MAX / 4 + 1;
+        assert!(matches!(
+            sl.set_status(overflowing_index, 4, 0x1),
+            Err(SetStatusError::OutOfBounds(..))
+        ));
+        assert!(matches!(
+            sl.get_status(overflowing_index, 4),
+            Err(GetStatusError::OutOfBounds(..))
+        ));
+        assert_eq!(sl.get_status(0, 4).unwrap(), 0);
+        assert!(matches!(
+            BitstringStatusList::new(OneOrMany::One("message".to_string()), usize::MAX, 2),
+            Err(NewStatusListError::ListTooLarge(usize::MAX, 2))
+        ));
+        // Lists shorter than the minimum length are rejected.
+        let sl = BitstringStatusList::new(OneOrMany::One("message".to_string()), 8, 1).unwrap();
+        assert!(matches!(
+            sl.get_status(0, 1),
+            Err(GetStatusError::ListTooSmall(8, MIN_BITSTRING_LENGTH))
+        ));
+    }
+
+    #[async_std::test]
+    async fn status_list_manager() {
+        let key_str = include_str!("../tests/rsa2048-2020-08-25.json");
+        let key: crate::jwk::JWK = serde_json::from_str(key_str).unwrap();
+        let manager = StatusListManager::new(
+            MemoryStatusListStore::new(),
+            URI::String("did:example:12345".to_string()),
+            "https://example.com/credentials/status/",
+            "revocation",
+        );
+        let entry1 = manager.allocate("urn:uuid:1").await.unwrap();
+        let entry2 = manager.allocate("urn:uuid:2").await.unwrap();
+        assert_eq!(
+            entry1.status_list_credential,
+            "https://example.com/credentials/status/1"
+        );
+        assert_ne!(entry1.status_list_index.0, entry2.status_list_index.0);
+        assert_eq!(
+            entry1.id,
+            URI::String(format!(
+                "https://example.com/credentials/status/1#{}",
+                entry1.status_list_index.0
+            ))
+        );
+        // Allocation is idempotent.
+        let entry = manager.allocate("urn:uuid:1").await.unwrap();
+        assert_eq!(entry.status_list_index.0, entry1.status_list_index.0);
+        assert!(matches!(
+            manager.set_status("urn:uuid:3", true).await,
+            Err(StatusListManagerError::UnknownCredential(_))
+        ));
+        manager.set_status("urn:uuid:2", true).await.unwrap();
+
+        let url = manager.rotate().await.unwrap();
+        assert_eq!(url, "https://example.com/credentials/status/2");
+        let entry3 = manager.allocate("urn:uuid:3").await.unwrap();
+        assert_eq!(entry3.status_list_credential, url);
+        assert_eq!(manager.list_urls().await.unwrap().len(), 2);
+
+        let resolver = &crate::did::example::DIDExample;
+        let mut context_loader = ContextLoader::default();
+        let options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:12345#key1".to_string())),
+            ..Default::default()
+        };
+        let vc = manager
+            .issue_list_credential(
+                &entry1.status_list_credential,
+                &key,
+                &options,
+                resolver,
+                &mut context_loader,
+            )
+            .await
+            .unwrap();
+        let result = vc.verify(None, resolver, &mut context_loader).await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let vc = StatusList2021Credential::try_from(vc).unwrap();
+        let StatusList2021Subject::StatusList2021(status_list) = vc.credential_subject;
+        assert_eq!(status_list.more_properties["statusPurpose"], "revocation");
+        let revoked = List::try_from(&status_list.encoded_list)
+            .unwrap()
+            .iter_revoked_indexes()
+            .unwrap()
+            .collect::<Vec<usize>>();
+        assert_eq!(revoked, vec![entry2.status_list_index.0]);
+
+        manager.clear_status("urn:uuid:2").await.unwrap();
+        let state = manager.store.load().await.unwrap();
+        let list = List::try_from(&state.lists[0].status_list.encoded_list).unwrap();
+        assert_eq!(list.iter_revoked_indexes().unwrap().count(), 0);
+    }
+
+    #[async_std::test]
+    async fn status_list_manager_full_list() {
+        let manager = StatusListManager::new(
+            MemoryStatusListStore::new(),
+            URI::String("did:example:12345".to_string()),
+            "https://example.com/credentials/status/",
+            "revocation",
+        )
+        .with_list_length(8);
+        // Managers can be used from multi-threaded executors.
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send(&manager.allocate("urn:uuid:0"));
+        let mut indexes = Vec::new();
+        for i in 0..8 {
+            let entry = manager.allocate(&format!("urn:uuid:{}", i)).await.unwrap();
+            assert_eq!(
+                entry.status_list_credential,
+                "https://example.com/credentials/status/1"
+            );
+            indexes.push(entry.status_list_index.0);
+        }
+        indexes.sort_unstable();
+        assert_eq!(indexes, (0..8).collect::<Vec<usize>>());
+        let entry = manager.allocate("urn:uuid:8").await.unwrap();
+        assert_eq!(
+            entry.status_list_credential,
+            "https://example.com/credentials/status/2"
+        );
+    }
+
+    #[async_std::test]
+    async fn file_status_list_store() {
+        let path =
+            std::env::temp_dir().join(format!("ssi-status-lists-{}.json", std::process::id()));
+        let store = FileStatusListStore::new(&path);
+        assert!(store.load().await.unwrap().lists.is_empty());
+        let manager = StatusListManager::new(
+            store,
+            URI::String("did:example:12345".to_string()),
+            "https://example.com/credentials/status/",
+            "suspension",
+        );
+        let entry = manager.allocate("urn:uuid:1").await.unwrap();
+        let tmp_path = std::env::temp_dir().join(format!(
+            ".ssi-status-lists-{}.json.{}.tmp",
+            std::process::id(),
+            std::process::id()
+        ));
+        assert!(!tmp_path.exists());
+        let store = FileStatusListStore::new(&path);
+        let state = store.load().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            state.lists[0].allocations["urn:uuid:1"],
+            entry.status_list_index.0
+        );
+    }
+
+    /// Loader counting its requests, serving a resource with the given headers.
+    #[derive(Default)]
+    struct CountingLoader {
+        cache_control: Option<String>,
+        etag: Option<String>,
+        loads: std::sync::atomic::AtomicUsize,
+        revalidations: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ResourceLoader for CountingLoader {
+        async fn load(&self, _url: &str) -> Result<LoadedResource, LoadResourceError> {
+            self.loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(LoadedResource {
+                data: b"{}".to_vec(),
+                cache_control: self.cache_control.clone(),
+                etag: self.etag.clone(),
+            })
+        }
+
+        async fn load_if_none_match(
+            &self,
+            url: &str,
+            etag: &str,
+        ) -> Result<Option<LoadedResource>, LoadResourceError> {
+            self.revalidations
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.etag.as_deref() == Some(etag) {
+                return Ok(None);
+            }
+            self.load(url).await.map(Some)
+        }
+    }
+
+    #[async_std::test]
+    async fn caching_resource_loader() {
+        use std::sync::atomic::Ordering;
+        let url = "https://example.com/credentials/status/3";
+        let counts = |loader: &CachingResourceLoader<CountingLoader>| {
+            (
+                loader.loader.loads.load(Ordering::SeqCst),
+                loader.loader.revalidations.load(Ordering::SeqCst),
+            )
+        };
+
+        let loader = CachingResourceLoader::new(CountingLoader {
+            cache_control: Some("public, max-age=60".to_string()),
+            ..Default::default()
+        });
+        loader.load(url).await.unwrap();
+        loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (1, 0));
+        loader.clear();
+        loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (2, 0));
+
+        let loader = CachingResourceLoader::new(CountingLoader {
+            cache_control: Some("max-age=60, no-store".to_string()),
+            etag: Some("\"1\"".to_string()),
+            ..Default::default()
+        });
+        loader.load(url).await.unwrap();
+        loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (2, 0));
+
+        let loader = CachingResourceLoader::new(CountingLoader {
+            cache_control: Some("no-cache".to_string()),
+            etag: Some("\"1\"".to_string()),
+            ..Default::default()
+        });
+        loader.load(url).await.unwrap();
+        let resource = loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (1, 1));
+        assert_eq!(resource.data, b"{}");
+
+        let loader = CachingResourceLoader::new(CountingLoader {
+            cache_control: Some("no-cache, max-age=60".to_string()),
+            etag: Some("\"1\"".to_string()),
+            ..Default::default()
+        });
+        loader.load(url).await.unwrap();
+        loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (1, 1));
+
+        let loader = CachingResourceLoader::new(CountingLoader::default());
+        loader.load(url).await.unwrap();
+        loader.load(url).await.unwrap();
+        assert_eq!(counts(&loader), (2, 0));
+
+        // Huge max-age values are clamped instead of overflowing the expiry date.
+        for max_age in ["10000000000000000", "99999999999999999999999"] {
+            let loader = CachingResourceLoader::new(CountingLoader {
+                cache_control: Some(format!("max-age={}", max_age)),
+                ..Default::default()
+            });
+            loader.load(url).await.unwrap();
+            loader.load(url).await.unwrap();
+            assert_eq!(counts(&loader), (1, 0));
+        }
+    }
+
+    #[async_std::test]
+    async fn file_resource_loader() {
+        let url = "https://example.com/credentials/status/3";
+        let path =
+            std::env::temp_dir().join(format!("ssi-status-list-{}.json", std::process::id()));
+        std::fs::write(&path, b"{}").unwrap();
+        let mut loader = FileResourceLoader::new();
+        loader.insert(url, &path);
+        assert_eq!(loader.load(url).await.unwrap().data, b"{}");
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            loader.load(url).await,
+            Err(LoadResourceError::Read(_))
+        ));
+        assert!(matches!(
+            loader.load("https://example.com/other").await,
+            Err(LoadResourceError::NotFound)
+        ));
+    }
+
+    #[async_std::test]
+    async fn load_status_resource_url_schemes() {
+        let resolver = &crate::did::example::DIDExample;
+        let mut loader = MemoryResourceLoader::new();
+        loader.insert("https://example.com/credentials/status/3", b"{}".to_vec());
+        let load = |url: &'static str| load_status_resource(url, resolver, &loader);
+        assert_eq!(
+            load("data:application/json,%7B%22a%22:1%7D").await.unwrap(),
+            br#"{"a":1}"#
+        );
+        assert_eq!(load("data:;base64,e30=").await.unwrap(), b"{}");
+        assert!(matches!(
+            load("data:,%7").await,
+            Err(LoadResourceError::DataURL(_))
+        ));
+        // DID URL dereferenced to a service endpoint URL
+        assert_eq!(
+            load("did:example:12345?service=status&relativeRef=%2F3")
+                .await
+                .unwrap(),
+            b"{}"
+        );
+        assert!(matches!(
+            load("did:example:12345?service=other&relativeRef=%2F3").await,
+            Err(LoadResourceError::Dereference(_))
+        ));
+        assert!(matches!(
+            load("https://example.com/credentials/status/4").await,
+            Err(LoadResourceError::NotFound)
+        ));
+    }
 }
@@ -0,0 +1,817 @@
+//! [Selective Disclosure for JWTs (SD-JWT)][sd-jwt] and [SD-JWT VC][sd-jwt-vc]
+//!
+//! An issuer [issues](SdJwt::issue) an SD-JWT, concealing the claims it marks as selectively
+//! disclosable behind salted digests (`_sd` arrays and `{"...": <digest>}` array elements). Each
+//! concealed claim is revealed by a [`Disclosure`] appended to the JWT in the `~` serialization.
+//! A holder [presents](SdJwt::present) a subset of the disclosures, optionally
+//! [binding](SdJwt::bind) the presentation to its key with a key binding JWT. A verifier
+//! [verifies](SdJwt::verify) the SD-JWT and gets back the claims reconstructed from the disclosures
+//! presented.
+//!
+//! Claims are designated with [JSON Pointers][json-pointer], e.g. `/address/street_address` or
+//! `/nationalities/1`.
+//!
+//! [sd-jwt]: https://datatracker.ietf.org/doc/draft-ietf-oauth-selective-disclosure-jwt/
+//! [sd-jwt-vc]: https://datatracker.ietf.org/doc/draft-ietf-oauth-sd-jwt-vc/
+//! [json-pointer]: https://www.rfc-editor.org/rfc/rfc6901
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::prelude::*;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::hash::sha256;
+use crate::jwk::{Algorithm, JWK};
+use crate::jws::Header;
+use crate::signer::Signer;
+
+/// JWT `typ` of an SD-JWT VC.
+pub const SD_JWT_VC_TYPE: &str = "vc+sd-jwt";
+
+/// JWT `typ` of a key binding JWT.
+pub const KB_JWT_TYPE: &str = "kb+jwt";
+
+/// Hash algorithm used for disclosure digests.
+pub const SD_ALG: &str = "sha-256";
+
+const SD_CLAIM: &str = "_sd";
+const SD_ALG_CLAIM: &str = "_sd_alg";
+const ARRAY_ELEMENT_DIGEST: &str = "...";
+
+/// Error from issuing, presenting or verifying an SD-JWT.
+#[derive(Error, Debug)]
+pub enum SdJwtError {
+    #[error("Invalid SD-JWT serialization")]
+    InvalidSerialization,
+    #[error("Invalid disclosure: {0}")]
+    InvalidDisclosure(String),
+    #[error("Invalid JSON Pointer: {0}")]
+    InvalidPointer(String),
+    #[error("Claim cannot be selectively disclosable: {0}")]
+    ReservedClaimName(String),
+    #[error("Unsupported digest algorithm: {0}")]
+    UnsupportedDigestAlgorithm(String),
+    #[error("Invalid _sd claim")]
+    InvalidDigests,
+    #[error("Digest referenced more than once: {0}")]
+    DuplicateDigest(String),
+    #[error("Disclosed claim already present: {0}")]
+    ClaimConflict(String),
+    #[error("Disclosure not referenced by the SD-JWT: {0}")]
+    UnreferencedDisclosure(String),
+    #[error("Missing key binding JWT")]
+    MissingKeyBinding,
+    #[error("Missing confirmation key for key binding")]
+    MissingConfirmationKey,
+    #[error("Invalid key binding JWT: {0}")]
+    InvalidKeyBinding(String),
+    #[error("Unexpected JWT type: {0}")]
+    UnexpectedType(String),
+}
+
+/// A salted claim disclosure, encoded as a base64url JSON array `[salt, name, value]`, or
+/// `[salt, value]` for an array element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disclosure {
+    salt: String,
+    name: Option<String>,
+    value: Value,
+    encoded: String,
+}
+
+impl Disclosure {
+    /// Create a disclosure with a random salt. `name` is `None` for an array element.
+    pub fn new(name: Option<String>, value: Value) -> Result<Self, Error> {
+        let salt: [u8; 16] = rand_old::random();
+        let salt = base64::encode_config(salt, base64::URL_SAFE_NO_PAD);
+        Self::with_salt(salt, name, value)
+    }
+
+    /// Create a disclosure with the given salt.
+    pub fn with_salt(salt: String, name: Option<String>, value: Value) -> Result<Self, Error> {
+        let array = match name {
+            Some(ref name) => serde_json::json!([salt, name, value]),
+            None => serde_json::json!([salt, value]),
+        };
+        let encoded =
+            base64::encode_config(serde_json::to_string(&array)?, base64::URL_SAFE_NO_PAD);
+        Ok(Self {
+            salt,
+            name,
+            value,
+            encoded,
+        })
+    }
+
+    pub fn salt(&self) -> &str {
+        &self.salt
+    }
+
+    /// Claim name, or `None` for an array element.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Encoded disclosure, as it appears in the serialized SD-JWT.
+    pub fn encoded(&self) -> &str {
+        &self.encoded
+    }
+
+    /// Base64url-encoded SHA-256 digest of the encoded disclosure.
+    pub fn digest(&self) -> Result<String, Error> {
+        Ok(base64::encode_config(
+            sha256(self.encoded.as_bytes())?,
+            base64::URL_SAFE_NO_PAD,
+        ))
+    }
+}
+
+impl FromStr for Disclosure {
+    type Err = Error;
+    fn from_str(encoded: &str) -> Result<Self, Self::Err> {
+        let invalid = || SdJwtError::InvalidDisclosure(encoded.to_string());
+        let json = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD)?;
+        let array: Vec<Value> = serde_json::from_slice(&json)?;
+        let (salt, name, value) = match <[Value; 3]>::try_from(array) {
+            Ok([Value::String(salt), Value::String(name), value]) => (salt, Some(name), value),
+            Ok(_) => return Err(invalid())?,
+            Err(array) => match <[Value; 2]>::try_from(array) {
+                Ok([Value::String(salt), value]) => (salt, None, value),
+                _ => return Err(invalid())?,
+            },
+        };
+        if let Some(name) = name.as_deref() {
+            if name == SD_CLAIM || name == ARRAY_ELEMENT_DIGEST {
+                return Err(SdJwtError::ReservedClaimName(name.to_string()))?;
+            }
+        }
+        Ok(Self {
+            salt,
+            name,
+            value,
+            encoded: encoded.to_string(),
+        })
+    }
+}
+
+/// Options for [issuing](SdJwt::issue) an SD-JWT.
+#[derive(Debug, Clone, Default)]
+pub struct IssueOptions {
+    /// JWT `typ` header, e.g. [`SD_JWT_VC_TYPE`].
+    pub type_: Option<String>,
+    /// Holder public key, added as the `cnf` claim to allow key binding.
+    pub holder_key: Option<JWK>,
+}
+
+/// Options for [verifying](SdJwt::verify) an SD-JWT.
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    /// Expected JWT `typ` header of the issuer-signed JWT, e.g. [`SD_JWT_VC_TYPE`].
+    pub type_: Option<String>,
+    /// Require a key binding JWT.
+    pub require_key_binding: bool,
+    /// Expected `aud` of the key binding JWT.
+    pub audience: Option<String>,
+    /// Expected `nonce` of the key binding JWT.
+    pub nonce: Option<String>,
+    /// Maximum age of the key binding JWT, in seconds, from its `iat`.
+    pub key_binding_max_age: Option<u64>,
+    /// Clock skew tolerated when checking the `iat` of the key binding JWT, in seconds.
+    pub clock_skew: u64,
+}
+
+/// SD-JWT, with its disclosures and optional key binding JWT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdJwt {
+    /// Issuer-signed JWT.
+    pub jwt: String,
+    pub disclosures: Vec<Disclosure>,
+    pub key_binding_jwt: Option<String>,
+}
+
+impl fmt::Display for SdJwt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}~", self.jwt)?;
+        for disclosure in &self.disclosures {
+            write!(f, "{}~", disclosure.encoded)?;
+        }
+        if let Some(ref kb_jwt) = self.key_binding_jwt {
+            write!(f, "{}", kb_jwt)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SdJwt {
+    type Err = Error;
+    fn from_str(serialized: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = serialized.split('~').collect();
+        if parts.len() < 2 {
+            return Err(SdJwtError::InvalidSerialization)?;
+        }
+        let key_binding_jwt = match parts.pop() {
+            Some("") | None => None,
+            Some(kb_jwt) => Some(kb_jwt.to_string()),
+        };
+        let jwt = parts.remove(0).to_string();
+        let disclosures = parts
+            .into_iter()
+            .map(Disclosure::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            jwt,
+            disclosures,
+            key_binding_jwt,
+        })
+    }
+}
+
+fn split_pointer(pointer: &str) -> Result<(&str, String), Error> {
+    let i = match pointer.rfind('/') {
+        Some(i) if pointer.starts_with('/') => i,
+        _ => return Err(SdJwtError::InvalidPointer(pointer.to_string()))?,
+    };
+    let name = pointer[i + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&pointer[..i], name))
+}
+
+fn push_pointer(pointer: &str, name: &str) -> String {
+    format!("{}/{}", pointer, name.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Conceal the claim at a JSON Pointer, returning its disclosure.
+fn conceal(claims: &mut Value, pointer: &str) -> Result<Disclosure, Error> {
+    let invalid_pointer = || SdJwtError::InvalidPointer(pointer.to_string());
+    let (parent_pointer, name) = split_pointer(pointer)?;
+    match claims.pointer_mut(parent_pointer) {
+        Some(Value::Object(object)) => {
+            if name == SD_CLAIM || name == SD_ALG_CLAIM || name == ARRAY_ELEMENT_DIGEST {
+                return Err(SdJwtError::ReservedClaimName(name).into());
+            }
+            let value = object.remove(&name).ok_or_else(invalid_pointer)?;
+            let disclosure = Disclosure::new(Some(name), value)?;
+            let digests = object
+                .entry(SD_CLAIM)
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(digests) = digests {
+                digests.push(Value::String(disclosure.digest()?));
+                // Sort the digests so their order does not reveal the order of the claims.
+                digests.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            }
+            Ok(disclosure)
+        }
+        Some(Value::Array(array)) => {
+            let element = name
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| array.get_mut(i))
+                .ok_or_else(invalid_pointer)?;
+            let disclosure = Disclosure::new(None, element.take())?;
+            let mut digest = Map::new();
+            digest.insert(
+                ARRAY_ELEMENT_DIGEST.to_string(),
+                Value::String(disclosure.digest()?),
+            );
+            *element = Value::Object(digest);
+            Ok(disclosure)
+        }
+        _ => Err(invalid_pointer())?,
+    }
+}
+
+/// Replaces digests in claims with the disclosed claims, recording the JSON Pointer at which each
+/// disclosure was inserted.
+struct Reconstruction<'a> {
+    disclosures: HashMap<String, usize>,
+    all_disclosures: &'a [Disclosure],
+    pointers: Vec<Option<String>>,
+}
+
+impl<'a> Reconstruction<'a> {
+    fn new(disclosures: &'a [Disclosure]) -> Result<Self, Error> {
+        let mut digests = HashMap::new();
+        for (i, disclosure) in disclosures.iter().enumerate() {
+            let digest = disclosure.digest()?;
+            if digests.insert(digest, i).is_some() {
+                return Err(SdJwtError::DuplicateDigest(disclosure.encoded.clone()))?;
+            }
+        }
+        Ok(Self {
+            disclosures: digests,
+            all_disclosures: disclosures,
+            pointers: vec![None; disclosures.len()],
+        })
+    }
+
+    /// Find the disclosure for a digest, if it was presented, and record its JSON Pointer.
+    fn take(
+        &mut self,
+        digest: &str,
+        pointer: impl FnOnce(&Disclosure) -> String,
+    ) -> Result<Option<&'a Disclosure>, Error> {
+        let i = match self.disclosures.get(digest) {
+            Some(i) => *i,
+            None => return Ok(None),
+        };
+        if self.pointers[i].is_some() {
+            return Err(SdJwtError::DuplicateDigest(digest.to_string()))?;
+        }
+        let disclosure = &self.all_disclosures[i];
+        self.pointers[i] = Some(pointer(disclosure));
+        Ok(Some(disclosure))
+    }
+
+    fn reconstruct(&mut self, value: &mut Value, pointer: &str) -> Result<(), Error> {
+        match value {
+            Value::Object(object) => {
+                if let Some(digests) = object.remove(SD_CLAIM) {
+                    let digests = match digests {
+                        Value::Array(digests) => digests,
+                        _ => return Err(SdJwtError::InvalidDigests)?,
+                    };
+                    for digest in digests {
+                        let digest = digest.as_str().ok_or(SdJwtError::InvalidDigests)?;
+                        let disclosure = match self.take(digest, |disclosure| {
+                            push_pointer(pointer, disclosure.name().unwrap_or_default())
+                        })? {
+                            Some(disclosure) => disclosure,
+                            None => continue,
+                        };
+                        let name = disclosure.name.as_ref().ok_or_else(|| {
+                            SdJwtError::InvalidDisclosure(disclosure.encoded.clone())
+                        })?;
+                        if object.contains_key(name) {
+                            return Err(SdJwtError::ClaimConflict(name.to_string()))?;
+                        }
+                        object.insert(name.to_string(), disclosure.value.clone());
+                    }
+                }
+                for (name, value) in object.iter_mut() {
+                    self.reconstruct(value, &push_pointer(pointer, name))?;
+                }
+            }
+            Value::Array(array) => {
+                let mut elements = Vec::with_capacity(array.len());
+                for element in array.drain(..) {
+                    let digest = match element {
+                        Value::Object(ref object) if object.len() == 1 => {
+                            match object.get(ARRAY_ELEMENT_DIGEST) {
+                                Some(Value::String(digest)) => Some(digest.to_string()),
+                                Some(_) => return Err(SdJwtError::InvalidDigests)?,
+                                None => None,
+                            }
+                        }
+                        _ => None,
+                    };
+                    let digest = match digest {
+                        Some(digest) => digest,
+                        None => {
+                            elements.push(element);
+                            continue;
+                        }
+                    };
+                    let element_pointer = push_pointer(pointer, &elements.len().to_string());
+                    // Elements with undisclosed digests are removed.
+                    if let Some(disclosure) = self.take(&digest, |_| element_pointer)? {
+                        if disclosure.name.is_some() {
+                            return Err(SdJwtError::InvalidDisclosure(disclosure.encoded.clone()))?;
+                        }
+                        elements.push(disclosure.value.clone());
+                    }
+                }
+                *array = elements;
+                for (i, element) in array.iter_mut().enumerate() {
+                    self.reconstruct(element, &push_pointer(pointer, &i.to_string()))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Reconstruct the claims of an SD-JWT payload.
+    fn reconstruct_claims(&mut self, mut claims: Value) -> Result<Map<String, Value>, Error> {
+        let mut claims = match claims.take() {
+            Value::Object(claims) => claims,
+            _ => return Err(Error::ExpectedObject),
+        };
+        match claims.remove(SD_ALG_CLAIM) {
+            None => {}
+            Some(Value::String(alg)) if alg == SD_ALG => {}
+            Some(alg) => return Err(SdJwtError::UnsupportedDigestAlgorithm(alg.to_string()))?,
+        }
+        let mut claims = Value::Object(claims);
+        self.reconstruct(&mut claims, "")?;
+        for (disclosure, pointer) in self.all_disclosures.iter().zip(&self.pointers) {
+            if pointer.is_none() {
+                return Err(SdJwtError::UnreferencedDisclosure(
+                    disclosure.encoded.clone(),
+                ))?;
+            }
+        }
+        match claims {
+            Value::Object(claims) => Ok(claims),
+            _ => Err(Error::ExpectedObject),
+        }
+    }
+}
+
+fn check_validity_period(claims: &Map<String, Value>) -> Result<(), Error> {
+    let now = Utc::now().timestamp() as f64;
+    if let Some(nbf) = claims.get("nbf").and_then(Value::as_f64) {
+        if now < nbf {
+            return Err(Error::CredentialNotYetValid);
+        }
+    }
+    if let Some(exp) = claims.get("exp").and_then(Value::as_f64) {
+        if now >= exp {
+            return Err(Error::ExpiredCredential);
+        }
+    }
+    Ok(())
+}
+
+fn sd_hash(serialized: &str) -> Result<String, Error> {
+    Ok(base64::encode_config(
+        sha256(serialized.as_bytes())?,
+        base64::URL_SAFE_NO_PAD,
+    ))
+}
+
+impl SdJwt {
+    /// Issue an SD-JWT with the claims at the `disclosable` JSON Pointers concealed.
+    ///
+    /// A claim nested in another selectively disclosable claim is concealed first, so its digest
+    /// is part of the enclosing claim's disclosure.
+    pub async fn issue(
+        claims: &Map<String, Value>,
+        disclosable: &[&str],
+        algorithm: Algorithm,
+        signer: &dyn Signer,
+        options: &IssueOptions,
+    ) -> Result<Self, Error> {
+        let mut claims = Value::Object(claims.clone());
+        let mut pointers = disclosable.to_vec();
+        pointers.sort_by_key(|pointer| std::cmp::Reverse(pointer.matches('/').count()));
+        let mut disclosures = pointers
+            .into_iter()
+            .map(|pointer| conceal(&mut claims, pointer))
+            .collect::<Result<Vec<_>, _>>()?;
+        disclosures.reverse();
+        if let Value::Object(ref mut claims) = claims {
+            claims.insert(SD_ALG_CLAIM.to_string(), Value::String(SD_ALG.to_string()));
+            if let Some(ref holder_key) = options.holder_key {
+                let mut cnf = Map::new();
+                cnf.insert(
+                    "jwk".to_string(),
+                    serde_json::to_value(holder_key.to_public())?,
+                );
+                claims.insert("cnf".to_string(), Value::Object(cnf));
+            }
+        }
+        let header = Header {
+            algorithm,
+            key_id: signer.public_key().key_id,
+            type_: options.type_.clone(),
+            ..Default::default()
+        };
+        let payload = serde_json::to_string(&claims)?;
+        let jwt =
+            crate::jws::encode_sign_custom_header_with_signer(&payload, &header, signer).await?;
+        Ok(Self {
+            jwt,
+            disclosures,
+            key_binding_jwt: None,
+        })
+    }
+
+    /// Present the disclosures needed to reveal the claims at the given JSON Pointers, without
+    /// key binding.
+    ///
+    /// Disclosures of claims nested in a revealed claim are presented, as are disclosures of the
+    /// claims enclosing it.
+    pub fn present(&self, reveal: &[&str]) -> Result<Self, Error> {
+        let claims: Value = crate::jwt::decode_unverified(&self.jwt)?;
+        let mut reconstruction = Reconstruction::new(&self.disclosures)?;
+        reconstruction.reconstruct_claims(claims)?;
+        let is_revealed = |pointer: &str| {
+            reveal.iter().any(|revealed| {
+                let (outer, inner) = if revealed.len() <= pointer.len() {
+                    (*revealed, pointer)
+                } else {
+                    (pointer, *revealed)
+                };
+                inner
+                    .strip_prefix(outer)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+        };
+        let disclosures = self
+            .disclosures
+            .iter()
+            .zip(reconstruction.pointers)
+            .filter(|(_, pointer)| pointer.as_deref().is_some_and(is_revealed))
+            .map(|(disclosure, _)| disclosure.clone())
+            .collect();
+        Ok(Self {
+            jwt: self.jwt.clone(),
+            disclosures,
+            key_binding_jwt: None,
+        })
+    }
+
+    /// Bind the presentation to the holder key, with a key binding JWT for the given audience
+    /// and nonce.
+    pub async fn bind(
+        mut self,
+        algorithm: Algorithm,
+        holder: &dyn Signer,
+        audience: &str,
+        nonce: &str,
+    ) -> Result<Self, Error> {
+        self.key_binding_jwt = None;
+        let claims = serde_json::json!({
+            "iat": Utc::now().timestamp(),
+            "aud": audience,
+            "nonce": nonce,
+            "sd_hash": sd_hash(&self.to_string())?,
+        });
+        let header = Header {
+            algorithm,
+            type_: Some(KB_JWT_TYPE.to_string()),
+            ..Default::default()
+        };
+        let payload = serde_json::to_string(&claims)?;
+        self.key_binding_jwt = Some(
+            crate::jws::encode_sign_custom_header_with_signer(&payload, &header, holder).await?,
+        );
+        Ok(self)
+    }
+
+    /// Verify the SD-JWT with the issuer key, and its key binding JWT if present, returning the
+    /// claims with the presented disclosures applied.
+    pub fn verify(
+        &self,
+        issuer_key: &JWK,
+        options: &VerifyOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        let (header, payload) = crate::jws::decode_verify(&self.jwt, issuer_key)?;
+        if let Some(ref type_) = options.type_ {
+            if header.type_.as_ref() != Some(type_) {
+                let actual = header.type_.unwrap_or_default();
+                return Err(SdJwtError::UnexpectedType(actual))?;
+            }
+        }
+        let claims: Value = serde_json::from_slice(&payload)?;
+        let claims = Reconstruction::new(&self.disclosures)?.reconstruct_claims(claims)?;
+        check_validity_period(&claims)?;
+        match self.key_binding_jwt {
+            Some(ref kb_jwt) => self.verify_key_binding(kb_jwt, &claims, options)?,
+            None if options.require_key_binding => Err(SdJwtError::MissingKeyBinding)?,
+            None => {}
+        }
+        Ok(claims)
+    }
+
+    fn verify_key_binding(
+        &self,
+        kb_jwt: &str,
+        claims: &Map<String, Value>,
+        options: &VerifyOptions,
+    ) -> Result<(), Error> {
+        let invalid = |reason: &str| SdJwtError::InvalidKeyBinding(reason.to_string());
+        let holder_key: JWK = claims
+            .get("cnf")
+            .and_then(|cnf| cnf.get("jwk"))
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .ok_or(SdJwtError::MissingConfirmationKey)?;
+        let (header, payload) = crate::jws::decode_verify(kb_jwt, &holder_key)?;
+        if header.type_.as_deref() != Some(KB_JWT_TYPE) {
+            return Err(invalid("typ"))?;
+        }
+        let kb_claims: Map<String, Value> = serde_json::from_slice(&payload)?;
+        let presentation = Self {
+            key_binding_jwt: None,
+            ..self.clone()
+        };
+        if kb_claims.get("sd_hash").and_then(Value::as_str)
+            != Some(sd_hash(&presentation.to_string())?.as_str())
+        {
+            return Err(invalid("sd_hash"))?;
+        }
+        let iat = kb_claims
+            .get("iat")
+            .and_then(Value::as_f64)
+            .ok_or_else(|| invalid("iat"))?;
+        let now = Utc::now().timestamp() as f64;
+        let clock_skew = options.clock_skew as f64;
+        if iat > now + clock_skew {
+            return Err(invalid("iat"))?;
+        }
+        if let Some(max_age) = options.key_binding_max_age {
+            if now - iat > max_age as f64 + clock_skew {
+                return Err(invalid("iat"))?;
+            }
+        }
+        if let Some(ref audience) = options.audience {
+            if kb_claims.get("aud").and_then(Value::as_str) != Some(audience) {
+                return Err(invalid("aud"))?;
+            }
+        }
+        if let Some(ref nonce) = options.nonce {
+            if kb_claims.get("nonce").and_then(Value::as_str) != Some(nonce) {
+                return Err(invalid("nonce"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn disclosure_digest() {
+        // From the SD-JWT specification
+        let disclosure: Disclosure = "WyI2cU1RdlJMNWhhaiIsICJmYW1pbHlfbmFtZSIsICJNw7ZiaXVzIl0"
+            .parse()
+            .unwrap();
+        assert_eq!(disclosure.salt(), "6qMQvRL5haj");
+        assert_eq!(disclosure.name(), Some("family_name"));
+        assert_eq!(disclosure.value(), &json!("Möbius"));
+        assert_eq!(
+            disclosure.digest().unwrap(),
+            "uutlBuYeMDyjLLTpf6Jxi7yNkEF35jdyWMn9U7b_RYY"
+        );
+    }
+
+    #[async_std::test]
+    async fn issue_present_verify() {
+        let issuer_key = JWK::generate_ed25519().unwrap();
+        let holder_key = JWK::generate_ed25519().unwrap();
+        let claims = json!({
+            "iss": "https://example.com/issuer",
+            "vct": "https://credentials.example.com/identity_credential",
+            "given_name": "John",
+            "family_name": "Doe",
+            "address": {
+                "street_address": "123 Main St",
+                "country": "US"
+            },
+            "nationalities": ["US", "DE"]
+        });
+        let options = IssueOptions {
+            type_: Some(SD_JWT_VC_TYPE.to_string()),
+            holder_key: Some(holder_key.to_public()),
+        };
+        let sd_jwt = SdJwt::issue(
+            claims.as_object().unwrap(),
+            &[
+                "/given_name",
+                "/family_name",
+                "/address",
+                "/address/street_address",
+                "/nationalities/1",
+            ],
+            Algorithm::EdDSA,
+            &issuer_key,
+            &options,
+        )
+        .await
+        .unwrap();
+        assert_eq!(sd_jwt.disclosures.len(), 5);
+        let payload: Value = crate::jwt::decode_unverified(&sd_jwt.jwt).unwrap();
+        assert!(payload.get("given_name").is_none());
+        assert!(payload.get("address").is_none());
+        assert_eq!(payload["_sd"].as_array().unwrap().len(), 3);
+
+        // All claims are disclosed to the holder.
+        let sd_jwt: SdJwt = sd_jwt.to_string().parse().unwrap();
+        let issued = sd_jwt
+            .verify(&issuer_key.to_public(), &VerifyOptions::default())
+            .unwrap();
+        for (name, value) in claims.as_object().unwrap() {
+            assert_eq!(issued.get(name), Some(value));
+        }
+
+        let presentation = sd_jwt
+            .present(&["/given_name", "/address/street_address"])
+            .unwrap()
+            .bind(
+                Algorithm::EdDSA,
+                &holder_key,
+                "https://verifier.example",
+                "1234",
+            )
+            .await
+            .unwrap();
+        assert_eq!(presentation.disclosures.len(), 3);
+        let presentation: SdJwt = presentation.to_string().parse().unwrap();
+        let options = VerifyOptions {
+            type_: Some(SD_JWT_VC_TYPE.to_string()),
+            require_key_binding: true,
+            audience: Some("https://verifier.example".to_string()),
+            nonce: Some("1234".to_string()),
+            key_binding_max_age: Some(300),
+            clock_skew: 60,
+        };
+        let disclosed = presentation
+            .verify(&issuer_key.to_public(), &options)
+            .unwrap();
+        assert_eq!(disclosed["given_name"], json!("John"));
+        assert!(disclosed.get("family_name").is_none());
+        assert_eq!(disclosed["address"], claims["address"]);
+        assert_eq!(disclosed["nationalities"], json!(["US"]));
+
+        // Wrong JWT type
+        let wrong_type = VerifyOptions {
+            type_: Some("example+sd-jwt".to_string()),
+            ..options.clone()
+        };
+        match presentation
+            .verify(&issuer_key.to_public(), &wrong_type)
+            .unwrap_err()
+        {
+            Error::SdJwt(SdJwtError::UnexpectedType(type_)) => assert_eq!(type_, SD_JWT_VC_TYPE),
+            err => panic!("unexpected error: {}", err),
+        }
+
+        // Wrong nonce
+        let wrong_nonce = VerifyOptions {
+            nonce: Some("5678".to_string()),
+            ..options.clone()
+        };
+        presentation
+            .verify(&issuer_key.to_public(), &wrong_nonce)
+            .unwrap_err();
+
+        // Key binding does not cover added disclosures.
+        let mut tampered = presentation.clone();
+        tampered.disclosures.push(sd_jwt.disclosures[0].clone());
+        tampered
+            .verify(&issuer_key.to_public(), &options)
+            .unwrap_err();
+
+        // Key binding JWTs issued too long ago, or in the future, are rejected.
+        for iat in [Utc::now().timestamp() - 3600, Utc::now().timestamp() + 3600] {
+            let mut stale = presentation.clone();
+            stale.key_binding_jwt = None;
+            let claims = json!({
+                "iat": iat,
+                "aud": "https://verifier.example",
+                "nonce": "1234",
+                "sd_hash": sd_hash(&stale.to_string()).unwrap(),
+            });
+            let header = Header {
+                algorithm: Algorithm::EdDSA,
+                type_: Some(KB_JWT_TYPE.to_string()),
+                ..Default::default()
+            };
+            stale.key_binding_jwt = Some(
+                crate::jws::encode_sign_custom_header_with_signer(
+                    &claims.to_string(),
+                    &header,
+                    &holder_key,
+                )
+                .await
+                .unwrap(),
+            );
+            match stale.verify(&issuer_key.to_public(), &options).unwrap_err() {
+                Error::SdJwt(SdJwtError::InvalidKeyBinding(reason)) => assert_eq!(reason, "iat"),
+                err => panic!("unexpected error: {}", err),
+            }
+        }
+
+        // Key binding is required.
+        let unbound = sd_jwt.present(&["/given_name"]).unwrap();
+        unbound
+            .verify(&issuer_key.to_public(), &options)
+            .unwrap_err();
+
+        // Disclosures must be referenced by the SD-JWT.
+        let mut tampered = unbound;
+        tampered
+            .disclosures
+            .push(Disclosure::new(Some("age".to_string()), json!(42)).unwrap());
+        match tampered
+            .verify(&issuer_key.to_public(), &VerifyOptions::default())
+            .unwrap_err()
+        {
+            Error::SdJwt(SdJwtError::UnreferencedDisclosure(_)) => {}
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+}
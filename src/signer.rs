@@ -0,0 +1,158 @@
+//! Signing with keys that are not held as a private [`JWK`]
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use crate::error::Error;
+use crate::jwk::{Algorithm, JWK};
+
+/// A key that can sign data without exposing its private key material.
+///
+/// A `Signer` is accepted wherever a private [`JWK`] is, e.g. to create linked data proofs, JWT
+/// credentials and capability delegations. Implement it for keys held in a hardware security
+/// module, a key management service or a remote signing service. A private [`JWK`] is itself a
+/// `Signer`.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Signer: Sync {
+    /// Public key of the signing key, with its key ID and algorithm, if any.
+    fn public_key(&self) -> JWK;
+
+    /// Algorithm to sign with when the proof or token type does not determine one.
+    fn algorithm(&self) -> Option<Algorithm> {
+        self.public_key().get_algorithm()
+    }
+
+    /// Sign data using the given algorithm.
+    ///
+    /// Returns [`Error::UnsupportedAlgorithm`] if the key cannot be used with the algorithm.
+    async fn sign(&self, algorithm: Algorithm, data: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Private key, if held in memory.
+    ///
+    /// Used by proof types that cannot be created by signing bytes with [`sign`](Self::sign).
+    fn private_key(&self) -> Option<&JWK> {
+        None
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for JWK {
+    fn public_key(&self) -> JWK {
+        self.to_public()
+    }
+
+    async fn sign(&self, algorithm: Algorithm, data: &[u8]) -> Result<Vec<u8>, Error> {
+        crate::jws::sign_bytes(algorithm, data, self)
+    }
+
+    fn private_key(&self) -> Option<&JWK> {
+        Some(self)
+    }
+}
+
+/// Software [`Signer`] for a private JWK stored in a file.
+///
+/// The file is read each time data is signed, so the private key is only held in memory while
+/// signing.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileSigner {
+    path: PathBuf,
+    public_key: JWK,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileSigner {
+    /// Open a JSON file containing a private JWK.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let key = read_jwk(&path)?;
+        let public_key = key.to_public();
+        if key.params == public_key.params {
+            return Err(Error::MissingPrivateKey);
+        }
+        Ok(Self { path, public_key })
+    }
+
+    /// Path of the key file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_jwk(path: &Path) -> Result<JWK, Error> {
+    let key_str = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&key_str)?)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Signer for FileSigner {
+    fn public_key(&self) -> JWK {
+        self.public_key.clone()
+    }
+
+    async fn sign(&self, algorithm: Algorithm, data: &[u8]) -> Result<Vec<u8>, Error> {
+        let path = self.path.clone();
+        let key = async_std::task::spawn_blocking(move || read_jwk(&path)).await?;
+        // The key file may have been replaced since it was opened.
+        if !key.equals_public(&self.public_key) {
+            return Err(Error::KeyMismatch);
+        }
+        crate::jws::sign_bytes(algorithm, data, &key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[async_std::test]
+    async fn file_signer() {
+        let signer = FileSigner::open("tests/ed25519-2020-10-18.json").unwrap();
+        let key: JWK =
+            serde_json::from_str(include_str!("../tests/ed25519-2020-10-18.json")).unwrap();
+        assert_eq!(signer.public_key(), key.to_public());
+        assert!(signer.private_key().is_none());
+        let sig = signer.sign(Algorithm::EdDSA, b"hello").await.unwrap();
+        crate::jws::verify_bytes(Algorithm::EdDSA, b"hello", &key, &sig).unwrap();
+
+        let public_key_file = std::env::temp_dir().join(format!(
+            "ssi-file-signer-public-{}.json",
+            std::process::id()
+        ));
+        std::fs::write(
+            &public_key_file,
+            serde_json::to_string(&key.to_public()).unwrap(),
+        )
+        .unwrap();
+        let err = FileSigner::open(&public_key_file).unwrap_err();
+        std::fs::remove_file(&public_key_file).unwrap();
+        assert!(matches!(err, Error::MissingPrivateKey));
+    }
+
+    #[async_std::test]
+    async fn file_signer_key_replaced() {
+        let key_file = std::env::temp_dir().join(format!(
+            "ssi-file-signer-private-{}.json",
+            std::process::id()
+        ));
+        let key = JWK::generate_ed25519().unwrap();
+        std::fs::write(&key_file, serde_json::to_string(&key).unwrap()).unwrap();
+        let signer = FileSigner::open(&key_file).unwrap();
+        signer.sign(Algorithm::EdDSA, b"hello").await.unwrap();
+
+        let other_key = JWK::generate_ed25519().unwrap();
+        std::fs::write(&key_file, serde_json::to_string(&other_key).unwrap()).unwrap();
+        let err = signer.sign(Algorithm::EdDSA, b"hello").await.unwrap_err();
+        assert!(matches!(err, Error::KeyMismatch));
+
+        std::fs::remove_file(&key_file).unwrap();
+        let err = signer.sign(Algorithm::EdDSA, b"hello").await.unwrap_err();
+        assert!(matches!(err, Error::IO(_)));
+    }
+}
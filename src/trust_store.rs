@@ -0,0 +1,268 @@
+//! Trust store for offline verification
+//!
+//! A [`TrustStore`] holds the verification methods trusted by a verifier, either as keys for
+//! verification method IDs or as pinned DID documents. It implements [`DIDResolver`], so it can
+//! be passed as the resolver to verify [credentials](crate::vc::Credential::verify),
+//! [presentations](crate::vc::Presentation::verify) and [capabilities](crate::zcap), without
+//! resolving DIDs over the network. Proofs using verification methods that are not in the store
+//! fail to verify.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+
+use crate::did::{
+    Context, Contexts, Document, VerificationMethod, VerificationMethodMap, DEFAULT_CONTEXT, DIDURL,
+};
+use crate::did_resolve::{
+    DIDResolver, DocumentMetadata, ResolutionInputMetadata, ResolutionMetadata, ERROR_NOT_FOUND,
+    TYPE_DID_LD_JSON,
+};
+use crate::error::Error;
+use crate::jsonld::W3ID_JWS2020_V1_CONTEXT;
+use crate::jwk::JWK;
+
+/// Verification methods and DID documents trusted for offline verification.
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    /// Verification methods by DID, then by ID.
+    verification_methods: HashMap<String, BTreeMap<String, VerificationMethodMap>>,
+    /// Pinned DID documents by DID.
+    documents: HashMap<String, Document>,
+}
+
+impl TrustStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust a public key for a verification method ID (DID URL), for all verification
+    /// relationships. The verification method is of type `JsonWebKey2020`.
+    pub fn insert_key(&mut self, verification_method: &str, jwk: &JWK) -> Result<(), Error> {
+        let did_url = DIDURL::try_from(verification_method.to_string())?;
+        self.insert_verification_method(VerificationMethodMap {
+            id: verification_method.to_string(),
+            type_: "JsonWebKey2020".to_string(),
+            controller: did_url.did,
+            public_key_jwk: Some(jwk.to_public()),
+            ..Default::default()
+        })
+    }
+
+    /// Trust a verification method, for all verification relationships. The verification method
+    /// must be controlled by the DID of its ID.
+    ///
+    /// Verification methods of a DID with a [pinned document](Self::insert_document) are not
+    /// used.
+    pub fn insert_verification_method(&mut self, vm: VerificationMethodMap) -> Result<(), Error> {
+        let did_url = check_verification_method(&vm.id, &vm.controller)?;
+        self.verification_methods
+            .entry(did_url.did)
+            .or_default()
+            .insert(vm.id.clone(), vm);
+        Ok(())
+    }
+
+    /// Pin a DID document, returning the document previously pinned for the same DID, if any.
+    ///
+    /// The verification methods embedded in the document must have IDs of the document's DID,
+    /// and be controlled by it.
+    pub fn insert_document(&mut self, document: Document) -> Result<Option<Document>, Error> {
+        for vm in [
+            &document.verification_method,
+            &document.authentication,
+            &document.assertion_method,
+            &document.key_agreement,
+            &document.capability_invocation,
+            &document.capability_delegation,
+            &document.public_key,
+        ]
+        .iter()
+        .flat_map(|vms| vms.iter().flatten())
+        {
+            if let VerificationMethod::Map(map) = vm {
+                let id = map.get_id(&document.id);
+                let did_url = check_verification_method(&id, &map.controller)?;
+                if did_url.did != document.id {
+                    return Err(Error::DocumentVMMismatch(id, document.id));
+                }
+            }
+        }
+        Ok(self.documents.insert(document.id.clone(), document))
+    }
+
+    /// Get the document of a DID: the pinned document if any, or else a document made of the
+    /// verification methods trusted for the DID.
+    pub fn document(&self, did: &str) -> Option<Document> {
+        if let Some(document) = self.documents.get(did) {
+            return Some(document.clone());
+        }
+        let vms = self.verification_methods.get(did)?;
+        let mut document = Document::new(did);
+        document.context = Contexts::Many(vec![
+            Context::URI(DEFAULT_CONTEXT.to_string()),
+            Context::URI(W3ID_JWS2020_V1_CONTEXT.to_string()),
+        ]);
+        let references: Vec<VerificationMethod> = vms
+            .keys()
+            .filter_map(|id| DIDURL::try_from(id.to_string()).ok())
+            .map(VerificationMethod::DIDURL)
+            .collect();
+        document.verification_method =
+            Some(vms.values().cloned().map(VerificationMethod::Map).collect());
+        document.authentication = Some(references.clone());
+        document.assertion_method = Some(references.clone());
+        document.capability_invocation = Some(references.clone());
+        document.capability_delegation = Some(references);
+        Some(document)
+    }
+}
+
+/// Check that a verification method ID is a DID URL with a fragment, and that the verification
+/// method is controlled by the DID of its ID.
+fn check_verification_method(id: &str, controller: &str) -> Result<DIDURL, Error> {
+    let did_url = DIDURL::try_from(id.to_string())?;
+    if did_url.fragment.is_none() {
+        return Err(Error::DIDURL);
+    }
+    if controller != did_url.did {
+        return Err(Error::ControllerVMMismatch(
+            controller.to_string(),
+            did_url.did,
+        ));
+    }
+    Ok(did_url)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl DIDResolver for TrustStore {
+    /// Resolve a DID to its [document](TrustStore::document) in the trust store, or return a
+    /// [`notFound`](ERROR_NOT_FOUND) error.
+    async fn resolve(
+        &self,
+        did: &str,
+        _input_metadata: &ResolutionInputMetadata,
+    ) -> (
+        ResolutionMetadata,
+        Option<Document>,
+        Option<DocumentMetadata>,
+    ) {
+        match self.document(did) {
+            Some(document) => (
+                ResolutionMetadata {
+                    content_type: Some(TYPE_DID_LD_JSON.to_string()),
+                    ..Default::default()
+                },
+                Some(document),
+                Some(DocumentMetadata::default()),
+            ),
+            None => (ResolutionMetadata::from_error(ERROR_NOT_FOUND), None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::did::example::DIDExample;
+    use crate::jsonld::ContextLoader;
+    use crate::vc::{Credential, LinkedDataProofOptions, URI};
+    use serde_json::json;
+
+    const JWK_JSON: &str = include_str!("../tests/rsa2048-2020-08-25.json");
+
+    #[async_std::test]
+    async fn verify_offline() {
+        let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
+        let mut vc: Credential = serde_json::from_value(json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": "VerifiableCredential",
+            "issuer": "did:example:12345",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:foo"
+            }
+        }))
+        .unwrap();
+        let mut context_loader = ContextLoader::default();
+        let options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:12345#key1".to_string())),
+            ..Default::default()
+        };
+        let proof = vc
+            .generate_proof(&key, &options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+
+        let mut store = TrustStore::new();
+        store.insert_key("did:example:12345#key1", &key).unwrap();
+        let result = vc.verify(None, &store, &mut context_loader).await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let doc = store.document("did:example:12345").unwrap();
+        let vm =
+            doc.select_object(&DIDURL::try_from("did:example:12345#key1".to_string()).unwrap());
+        assert!(vm.is_ok());
+        // Only the public key is kept.
+        let vm = &store.verification_methods["did:example:12345"]["did:example:12345#key1"];
+        assert_eq!(vm.public_key_jwk, Some(key.to_public()));
+
+        // Pinned DID document
+        let mut store = TrustStore::new();
+        let document: Document =
+            serde_json::from_str(include_str!("../tests/did-example-12345.json")).unwrap();
+        store.insert_document(document).unwrap();
+        let result = vc.verify(None, &store, &mut context_loader).await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        // Verification method not in the store
+        let result = vc
+            .verify(None, &TrustStore::new(), &mut context_loader)
+            .await;
+        assert!(!result.errors.is_empty());
+        let mut store = TrustStore::new();
+        store.insert_key("did:example:12345#key2", &key).unwrap();
+        let result = vc.verify(None, &store, &mut context_loader).await;
+        assert!(!result.errors.is_empty());
+
+        assert!(store.insert_key("did:example:12345", &key).is_err());
+
+        // Verification method controlled by another DID
+        let vm = VerificationMethodMap {
+            id: "did:example:12345#key1".to_string(),
+            type_: "JsonWebKey2020".to_string(),
+            controller: "did:example:evil".to_string(),
+            public_key_jwk: Some(key.to_public()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            TrustStore::new().insert_verification_method(vm.clone()),
+            Err(Error::ControllerVMMismatch(_, _))
+        ));
+
+        // Pinned document with verification methods of other DIDs
+        let document: Document =
+            serde_json::from_str(include_str!("../tests/did-example-12345.json")).unwrap();
+        let mut foreign_document = document.clone();
+        foreign_document.assertion_method = Some(vec![VerificationMethod::Map(vm)]);
+        assert!(matches!(
+            TrustStore::new().insert_document(foreign_document),
+            Err(Error::ControllerVMMismatch(_, _))
+        ));
+        let mut foreign_document = document;
+        foreign_document.verification_method =
+            Some(vec![VerificationMethod::Map(VerificationMethodMap {
+                id: "did:example:evil#key1".to_string(),
+                type_: "JsonWebKey2020".to_string(),
+                controller: "did:example:evil".to_string(),
+                public_key_jwk: Some(key.to_public()),
+                ..Default::default()
+            })]);
+        assert!(matches!(
+            TrustStore::new().insert_document(foreign_document),
+            Err(Error::DocumentVMMismatch(_, _))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::Error;
 use crate::jwk::{Algorithm, Base64urlUInt, OctetParams, Params, JWK};
+use crate::signer::Signer;
 use core::convert::TryFrom;
 
 const EDPK_PREFIX: [u8; 4] = [13, 15, 37, 217];
@@ -147,6 +148,23 @@ pub enum SignTezosError {
 pub fn sign_tezos(data: &[u8], algorithm: Algorithm, key: &JWK) -> Result<String, SignTezosError> {
     let sig = crate::jws::sign_bytes(algorithm, data, key)
         .map_err(|e| SignTezosError::Sign(e.to_string()))?;
+    encode_tezos_signature(algorithm, &sig)
+}
+
+/// Sign data for Tezos, with a key that may not be held as a private [`JWK`].
+pub async fn sign_tezos_with_signer(
+    data: &[u8],
+    algorithm: Algorithm,
+    signer: &dyn Signer,
+) -> Result<String, SignTezosError> {
+    let sig = signer
+        .sign(algorithm, data)
+        .await
+        .map_err(|e| SignTezosError::Sign(e.to_string()))?;
+    encode_tezos_signature(algorithm, &sig)
+}
+
+fn encode_tezos_signature(algorithm: Algorithm, sig: &[u8]) -> Result<String, SignTezosError> {
     let mut sig_prefixed = Vec::new();
     const EDSIG_PREFIX: [u8; 5] = [9, 245, 205, 134, 18];
     const SPSIG_PREFIX: [u8; 5] = [13, 115, 101, 19, 63];
@@ -158,7 +176,7 @@ pub fn sign_tezos(data: &[u8], algorithm: Algorithm, key: &JWK) -> Result<String
         alg => return Err(SignTezosError::UnsupportedAlgorithm(alg)),
     };
     sig_prefixed.extend_from_slice(prefix);
-    sig_prefixed.extend_from_slice(&sig);
+    sig_prefixed.extend_from_slice(sig);
     let sig_bs58 = bs58::encode(sig_prefixed).with_check().into_string();
     Ok(sig_bs58)
 }
@@ -314,6 +332,18 @@ mod tests {
         assert_eq!(sig, sig_expected);
     }
 
+    #[async_std::test]
+    async fn edsk_sign_with_signer() {
+        let signer = crate::signer::FileSigner::open("tests/ed25519-2020-10-18.json").unwrap();
+        let tsm = encode_tezos_signed_message("example.org 2021-05-26T18:28:26Z Signed with ssi")
+            .unwrap();
+        let sig = sign_tezos_with_signer(&tsm, Algorithm::EdBlake2b, &signer)
+            .await
+            .unwrap();
+        let sig_expected = "edsigtvvyq6uFWyeoSNZq4Jq2AvsNGZ9hHYDgt4Hzdou4FVkaBLX34tWRyL9MsapFBg3RFXReJ4bNCaAg2F1XWAMgetCLU9AACo";
+        assert_eq!(sig, sig_expected);
+    }
+
     #[test]
     #[cfg(feature = "secp256k1")]
     fn spsk_sign() {
@@ -93,6 +93,14 @@ pub fn hash_first_degree_quads(
 
 /// <https://json-ld.github.io/rdf-dataset-canonicalization/spec/>
 pub fn normalize(input_dataset: &DataSet) -> Result<DataSet, Error> {
+    Ok(normalize_with_issuer(input_dataset)?.0)
+}
+
+/// Normalize a dataset, also returning the canonical issuer, which maps the input blank node
+/// identifiers to their canonical identifiers.
+pub fn normalize_with_issuer(
+    input_dataset: &DataSet,
+) -> Result<(DataSet, IdentifierIssuer), Error> {
     // https://json-ld.github.io/rdf-dataset-canonicalization/spec/#algorithm
     // 1
     let mut normalization_state = NormalizationState {
@@ -221,7 +229,7 @@ pub fn normalize(input_dataset: &DataSet) -> Result<DataSet, Error> {
         normalized_dataset.add_statement(quad_copy);
     }
     // 8
-    Ok(normalized_dataset)
+    Ok((normalized_dataset, normalization_state.canonical_issuer))
 }
 
 /// <https://json-ld.github.io/rdf-dataset-canonicalization/spec/#issue-identifier-algorithm>
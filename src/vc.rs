@@ -5,13 +5,18 @@ use std::str::FromStr;
 use crate::did_resolve::DIDResolver;
 use crate::error::Error;
 use crate::jsonld::{ContextLoader, json_to_dataset};
-use crate::jwk::{JWTKeys, JWK};
+use crate::json_schema::SchemaLoader;
+use crate::jwk::{JWKSet, JWTKeys, JWK};
 use crate::jws::Header;
 use crate::ldp::{
-    now_ms, LinkedDataDocument, LinkedDataProofs, ProofPreparation, VerificationWarnings,
+    now_ms, LinkedDataDocument, LinkedDataProofs, ProofPreparation, ProofSuiteRegistry,
+    VerificationWarnings,
 };
 use crate::one_or_many::OneOrMany;
+use crate::policy::VerificationPolicy;
+use crate::revocation::StatusValue;
 use crate::rdf::DataSet;
+use crate::signer::Signer;
 
 use async_trait::async_trait;
 use chrono::{prelude::*, Duration, LocalResult};
@@ -36,6 +41,9 @@ use serde_json::Value;
 
 pub const DEFAULT_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
 
+/// Base context for the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/#base-context)
+pub const DEFAULT_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+
 // work around https://github.com/w3c/vc-test-suite/issues/103
 pub const ALT_DEFAULT_CONTEXT: &str = "https://w3.org/2018/credentials/v1";
 
@@ -48,11 +56,20 @@ pub struct Credential {
     pub id: Option<URI>,
     #[serde(rename = "type")]
     pub type_: OneOrMany<String>,
+    /// <https://www.w3.org/TR/vc-data-model-2.0/#names-and-descriptions>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Value>,
+    /// <https://www.w3.org/TR/vc-data-model-2.0/#names-and-descriptions>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Value>,
     pub credential_subject: OneOrMany<CredentialSubject>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issuer: Option<Issuer>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issuance_date: Option<VCDateTime>,
+    /// <https://www.w3.org/TR/vc-data-model-2.0/#validity-period>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<VCDateTime>,
     // This field is populated only when using
     // embedded proofs such as LD-PROOF
     //   https://w3c-ccg.github.io/ld-proofs/
@@ -60,8 +77,11 @@ pub struct Credential {
     pub proof: Option<OneOrMany<Proof>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expiration_date: Option<VCDateTime>,
+    /// <https://www.w3.org/TR/vc-data-model-2.0/#validity-period>
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub credential_status: Option<Status>,
+    pub valid_until: Option<VCDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<OneOrMany<Status>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub terms_of_use: Option<Vec<TermsOfUse>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -104,6 +124,16 @@ pub enum Context {
     Object(Map<String, Value>),
 }
 
+/// Version of the VC Data Model, as indicated by the first `@context` value of a credential or
+/// presentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataModelVersion {
+    /// [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/), using [DEFAULT_CONTEXT]
+    V1,
+    /// [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/), using [DEFAULT_CONTEXT_V2]
+    V2,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CredentialSubject {
@@ -171,6 +201,9 @@ pub struct Proof {
     pub context: Value,
     #[serde(rename = "type")]
     pub type_: String,
+    /// Cryptographic suite of a [Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proof
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cryptosuite: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proof_purpose: Option<ProofPurpose>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -247,6 +280,7 @@ pub struct Status {
 pub enum CheckableStatus {
     RevocationList2020Status(crate::revocation::RevocationList2020Status),
     StatusList2021Entry(crate::revocation::StatusList2021Entry),
+    BitstringStatusListEntry(crate::revocation::BitstringStatusListEntry),
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -267,6 +301,14 @@ pub enum URI {
     String(String),
 }
 
+/// [`credentialSchema`](Credential::credential_schema) type of a [JSON Schema
+/// 2018](https://w3c-ccg.github.io/vc-json-schemas/v1/) validator.
+pub const JSON_SCHEMA_VALIDATOR_2018_TYPE: &str = "JsonSchemaValidator2018";
+
+/// [`credentialSchema`](Credential::credential_schema) type of a [JSON
+/// Schema](https://www.w3.org/TR/vc-json-schema/).
+pub const JSON_SCHEMA_TYPE: &str = "JsonSchema";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Schema {
@@ -479,6 +521,10 @@ pub struct LinkedDataProofOptions {
     /// The type of the proof. Default is an appropriate proof type corresponding to the verification method.
     pub type_: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// The cryptosuite of a DataIntegrityProof. Default is an appropriate cryptosuite
+    /// corresponding to the key.
+    pub cryptosuite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     /// The URI of the verificationMethod used for the proof. If omitted a default
     /// assertionMethod will be used.
     pub verification_method: Option<URI>,
@@ -503,6 +549,10 @@ pub struct LinkedDataProofOptions {
     pub eip712_domain: Option<crate::eip712::ProofInfo>,
     #[cfg(not(feature = "keccak-hash"))]
     pub eip712_domain: Option<()>,
+    /// Policy restricting the credentials and presentations accepted when verifying (not
+    /// standard in vc-http-api)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<Box<VerificationPolicy>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -514,6 +564,7 @@ pub enum Check {
     #[serde(rename = "JWS")]
     JWS,
     CredentialStatus,
+    CredentialSchema,
 }
 
 // https://w3c-ccg.github.io/vc-http-api/#/Verifier/verifyCredential
@@ -521,13 +572,170 @@ pub enum Check {
 #[serde(rename_all = "camelCase")]
 /// Object summarizing a verification
 /// Reference: vc-http-api
+///
+/// Warnings and errors are [`VerificationProblem`]s. They are serialized as their messages, for
+/// compatibility with vc-http-api; serialize the problems themselves for their details.
 pub struct VerificationResult {
     /// The checks performed
     pub checks: Vec<Check>,
     /// Warnings
-    pub warnings: Vec<String>,
+    #[serde(with = "problem_messages")]
+    pub warnings: Vec<VerificationProblem>,
     /// Errors
-    pub errors: Vec<String>,
+    #[serde(with = "problem_messages")]
+    pub errors: Vec<VerificationProblem>,
+    /// Statuses read from the [status lists](crate::revocation) of the credential (not standard
+    /// in vc-http-api)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<StatusValue>,
+}
+
+/// Kind of a [`VerificationProblem`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ProblemCode {
+    /// The JWT could not be decoded.
+    #[serde(rename = "invalidJWT")]
+    InvalidJWT,
+    /// The credential or presentation is not valid, independently of its proofs.
+    InvalidDocument,
+    /// The verification methods allowed to sign could not be determined.
+    VerificationMethodResolution,
+    /// No proof or JWS matches the verification options.
+    NoApplicableProof,
+    /// The key to verify a JWS could not be determined.
+    KeyResolution,
+    /// A proof or JWS signature is invalid.
+    InvalidSignature,
+    /// The current time is outside the validity period of the credential.
+    ValidityPeriod,
+    /// The proof purpose is not the one expected.
+    ProofPurpose,
+    /// A capability is not invoked or delegated as it allows.
+    Capability,
+    /// A check requested is not applicable.
+    InapplicableCheck,
+    /// The credential status could not be checked.
+    CredentialStatus,
+    /// The credential is revoked.
+    Revoked,
+    /// The credential is suspended.
+    Suspended,
+    /// The credential does not match its schema, or the schema could not be loaded.
+    CredentialSchema,
+    /// A feature of the credential or proof is not supported, e.g. a `credentialSchema` type.
+    Unsupported,
+    /// A warning from verifying a proof or JWS, e.g. about a deprecated algorithm.
+    ProofWarning,
+    /// The credential, presentation or proof is not accepted by the
+    /// [verification policy](LinkedDataProofOptions::policy).
+    Policy,
+    /// Other problem, e.g. converted from a message.
+    Other,
+}
+
+/// A verification error or warning, with its [code](ProblemCode) and context.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationProblem {
+    pub code: ProblemCode,
+    /// Human-readable message, as serialized in a [`VerificationResult`].
+    pub message: String,
+    /// Check that failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check: Option<Check>,
+    /// Index of the proof the problem is about, among the proofs of the document.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_index: Option<usize>,
+    /// Verification method of the proof or JWS the problem is about.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_method: Option<String>,
+}
+
+impl VerificationProblem {
+    pub fn new(code: ProblemCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            check: None,
+            proof_index: None,
+            verification_method: None,
+        }
+    }
+
+    pub fn with_check(mut self, check: Check) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    pub fn with_proof_index(mut self, proof_index: usize) -> Self {
+        self.proof_index = Some(proof_index);
+        self
+    }
+
+    pub fn with_verification_method(mut self, verification_method: Option<String>) -> Self {
+        self.verification_method = verification_method;
+        self
+    }
+}
+
+impl std::fmt::Display for VerificationProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for VerificationProblem {
+    fn from(message: String) -> Self {
+        Self::new(ProblemCode::Other, message)
+    }
+}
+
+impl From<&str> for VerificationProblem {
+    fn from(message: &str) -> Self {
+        Self::new(ProblemCode::Other, message)
+    }
+}
+
+impl PartialEq<&str> for VerificationProblem {
+    fn eq(&self, message: &&str) -> bool {
+        self.message == *message
+    }
+}
+
+/// Serialize problems as their messages, and deserialize them from messages or problem details.
+mod problem_messages {
+    use super::VerificationProblem;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(problems: &[VerificationProblem], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(problems.iter().map(|problem| &problem.message))
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MessageOrProblem {
+        Message(String),
+        Problem(VerificationProblem),
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<VerificationProblem>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let problems = Vec::<MessageOrProblem>::deserialize(deserializer)?;
+        Ok(problems
+            .into_iter()
+            .map(|problem| match problem {
+                MessageOrProblem::Message(message) => message.into(),
+                MessageOrProblem::Problem(problem) => problem,
+            })
+            .collect())
+    }
 }
 
 impl Default for ProofPurpose {
@@ -547,6 +755,8 @@ impl Default for LinkedDataProofOptions {
             checks: Some(vec![Check::Proof]),
             eip712_domain: None,
             type_: None,
+            cryptosuite: None,
+            policy: None,
         }
     }
 }
@@ -556,11 +766,16 @@ impl VerificationResult {
         Self::default()
     }
 
+    /// Result with an error of code [`ProblemCode::Other`].
     pub fn error(err: &str) -> Self {
+        Self::problem(ProblemCode::Other, err)
+    }
+
+    /// Result with an error.
+    pub fn problem(code: ProblemCode, message: impl Into<String>) -> Self {
         Self {
-            checks: vec![],
-            warnings: vec![],
-            errors: vec![err.to_string()],
+            errors: vec![VerificationProblem::new(code, message)],
+            ..Default::default()
         }
     }
 
@@ -568,10 +783,37 @@ impl VerificationResult {
         self.checks.append(&mut other.checks);
         self.warnings.append(&mut other.warnings);
         self.errors.append(&mut other.errors);
+        self.statuses.append(&mut other.statuses);
     }
 
+    /// Add an error of code [`ProblemCode::Other`].
     pub fn with_error(mut self, error: String) -> Self {
-        self.errors.push(error);
+        self.errors.push(error.into());
+        self
+    }
+
+    pub fn with_problem(mut self, code: ProblemCode, message: impl Into<String>) -> Self {
+        self.errors.push(VerificationProblem::new(code, message));
+        self
+    }
+
+    /// Set the check, proof index and verification method of the warnings and errors that do not
+    /// have them.
+    fn in_context(
+        mut self,
+        check: Check,
+        proof_index: Option<usize>,
+        verification_method: Option<&str>,
+    ) -> Self {
+        for problem in self.warnings.iter_mut().chain(self.errors.iter_mut()) {
+            problem.check.get_or_insert_with(|| check.clone());
+            if problem.proof_index.is_none() {
+                problem.proof_index = proof_index;
+            }
+            if problem.verification_method.is_none() {
+                problem.verification_method = verification_method.map(str::to_string);
+            }
+        }
         self
     }
 }
@@ -580,15 +822,13 @@ impl From<Result<VerificationWarnings, Error>> for VerificationResult {
     fn from(res: Result<VerificationWarnings, Error>) -> Self {
         match res {
             Ok(warnings) => Self {
-                checks: vec![],
-                warnings,
-                errors: vec![],
-            },
-            Err(error) => Self {
-                checks: vec![],
-                warnings: vec![],
-                errors: vec![error.to_string()],
+                warnings: warnings
+                    .into_iter()
+                    .map(|warning| VerificationProblem::new(ProblemCode::ProofWarning, warning))
+                    .collect(),
+                ..Default::default()
             },
+            Err(error) => Self::problem(ProblemCode::InvalidSignature, error.to_string()),
         }
     }
 }
@@ -601,7 +841,10 @@ impl TryFrom<OneOrMany<Context>> for Contexts {
             Some(Context::URI(URI::String(uri))) => uri,
             Some(Context::Object(_)) => return Err(Error::InvalidContext),
         };
-        if first_uri != DEFAULT_CONTEXT && first_uri != ALT_DEFAULT_CONTEXT {
+        if first_uri != DEFAULT_CONTEXT
+            && first_uri != ALT_DEFAULT_CONTEXT
+            && first_uri != DEFAULT_CONTEXT_V2
+        {
             return Err(Error::InvalidContext);
         }
         Ok(match context {
@@ -621,6 +864,20 @@ impl From<Contexts> for OneOrMany<Context> {
 }
 
 impl Contexts {
+    /// Get the VC Data Model version indicated by the first context.
+    pub fn version(&self) -> DataModelVersion {
+        let first = match self {
+            Self::One(context) => Some(context),
+            Self::Many(contexts) => contexts.first(),
+        };
+        match first {
+            Some(Context::URI(URI::String(uri))) if uri == DEFAULT_CONTEXT_V2 => {
+                DataModelVersion::V2
+            }
+            _ => DataModelVersion::V1,
+        }
+    }
+
     /// Check if the contexts contains the given URI.
     pub fn contains_uri(&self, uri: &str) -> bool {
         match self {
@@ -849,6 +1106,12 @@ impl Credential {
         Self::from_jwt_claims(token_data)
     }
 
+    /// Decode a JWT VC signed with a key of a JWK Set, such as one published by the issuer.
+    pub fn from_jwt_jwk_set(jwt: &str, jwk_set: &JWKSet) -> Result<Self, Error> {
+        let token_data: JWTClaims = crate::jwt::decode_verify_jwk_set(jwt, jwk_set)?;
+        Self::from_jwt_claims(token_data)
+    }
+
     pub fn from_jwt_unsigned(jwt: &str) -> Result<Self, Error> {
         let token_data: JWTClaims = crate::jwt::decode_unverified(jwt)?;
         let vc = Self::from_jwt_claims(token_data)?;
@@ -868,12 +1131,17 @@ impl Credential {
             Some(vc) => vc,
             None => return Err(Error::MissingCredential),
         };
+        let version = vc.context.version();
         if let Some(exp) = claims.expiration_time {
             let exp_date_time: LocalResult<DateTime<Utc>> = exp.into();
-            vc.expiration_date = exp_date_time.latest().map(|time| VCDateTime {
+            let exp_date = exp_date_time.latest().map(|time| VCDateTime {
                 date_time: time.into(),
                 use_z: true,
             });
+            match version {
+                DataModelVersion::V1 => vc.expiration_date = exp_date,
+                DataModelVersion::V2 => vc.valid_until = exp_date,
+            }
         }
         if let Some(iss) = claims.issuer {
             if let StringOrURI::URI(issuer_uri) = iss {
@@ -889,10 +1157,14 @@ impl Credential {
         if let Some(nbf) = claims.not_before {
             let nbf_date_time: LocalResult<DateTime<Utc>> = nbf.into();
             if let Some(time) = nbf_date_time.latest() {
-                vc.issuance_date = Some(VCDateTime {
+                let nbf_date = Some(VCDateTime {
                     date_time: time.into(),
                     use_z: true,
                 });
+                match version {
+                    DataModelVersion::V1 => vc.issuance_date = nbf_date,
+                    DataModelVersion::V2 => vc.valid_from = nbf_date,
+                }
             } else {
                 return Err(Error::TimeError);
             }
@@ -930,13 +1202,19 @@ impl Credential {
 
         // Copy fields from vc that are duplicated into the claims.
         let (id, issuer) = (vc.id.clone(), vc.issuer.clone());
+        // The validity period is expressed with different properties depending on the data model
+        // version.
+        let (not_before_date, expiration_date) = match vc.context.version() {
+            DataModelVersion::V1 => (vc.issuance_date.as_ref(), vc.expiration_date.as_ref()),
+            DataModelVersion::V2 => (vc.valid_from.as_ref(), vc.valid_until.as_ref()),
+        };
         // Note that try_into can fail if the date_time overflows the range for NumericDate
         // for expiration_time and not_before.
-        let expiration_time: Option<NumericDate> = match vc.expiration_date.as_ref() {
+        let expiration_time: Option<NumericDate> = match expiration_date {
             Some(date) => Some(date.date_time.try_into()?),
             None => None,
         };
-        let not_before: Option<NumericDate> = match vc.issuance_date.as_ref() {
+        let not_before: Option<NumericDate> = match not_before_date {
             Some(date) => Some(date.date_time.try_into()?),
             None => None,
         };
@@ -980,11 +1258,29 @@ impl Credential {
         jwk: Option<&JWK>,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
+    ) -> Result<String, Error> {
+        self.generate_jwt_with_signer(jwk.map(|jwk| jwk as &dyn Signer), options, resolver)
+            .await
+    }
+
+    /// Encode the Verifiable Credential as JWT, like [`generate_jwt`](Self::generate_jwt), signing
+    /// it with a [`Signer`] if one is passed.
+    pub async fn generate_jwt_with_signer(
+        &self,
+        signer: Option<&dyn Signer>,
+        options: &LinkedDataProofOptions,
+        resolver: &dyn DIDResolver,
     ) -> Result<String, Error> {
         let mut options = options.clone();
-        if let Some(jwk) = jwk {
-            crate::ldp::ensure_or_pick_verification_relationship(&mut options, self, jwk, resolver)
-                .await?;
+        let public_key = signer.map(|signer| signer.public_key());
+        if let Some(ref public_key) = public_key {
+            crate::ldp::ensure_or_pick_verification_relationship(
+                &mut options,
+                self,
+                public_key,
+                resolver,
+            )
+            .await?;
             // If no JWK is passed, there is no verification relationship.
         }
         let LinkedDataProofOptions {
@@ -996,6 +1292,8 @@ impl Credential {
             checks,
             eip712_domain,
             type_,
+            cryptosuite,
+            policy,
         } = options;
         if checks.is_some() {
             return Err(Error::UnencodableOptionClaim("checks".to_string()));
@@ -1009,6 +1307,12 @@ impl Credential {
         if type_.is_some() {
             return Err(Error::UnencodableOptionClaim("type".to_string()));
         }
+        if cryptosuite.is_some() {
+            return Err(Error::UnencodableOptionClaim("cryptosuite".to_string()));
+        }
+        if policy.is_some() {
+            return Err(Error::UnencodableOptionClaim("policy".to_string()));
+        }
         match proof_purpose {
             None => (),
             Some(ProofPurpose::AssertionMethod) => (),
@@ -1022,13 +1326,13 @@ impl Credential {
             },
             ..self.to_jwt_claims()?
         };
-        let algorithm = if let Some(jwk) = jwk {
-            jwk.get_algorithm().ok_or(Error::MissingAlgorithm)?
+        let algorithm = if let Some(signer) = signer {
+            signer.algorithm().ok_or(Error::MissingAlgorithm)?
         } else {
             crate::jwk::Algorithm::None
         };
         // Ensure consistency between key ID and verification method URI.
-        let key_id = match (jwk.and_then(|jwk| jwk.key_id.clone()), verification_method) {
+        let key_id = match (public_key.and_then(|jwk| jwk.key_id), verification_method) {
             (Some(jwk_kid), None) => Some(jwk_kid),
             (None, Some(vm_id)) => Some(vm_id.to_string()),
             (None, None) => None,
@@ -1044,9 +1348,10 @@ impl Credential {
         };
         let header_b64 = base64_encode_json(&header)?;
         let payload_b64 = base64_encode_json(&claims)?;
-        if let Some(jwk) = jwk {
+        if let Some(signer) = signer {
             let signing_input = header_b64 + "." + &payload_b64;
-            let sig_b64 = crate::jws::sign_bytes_b64(algorithm, signing_input.as_bytes(), jwk)?;
+            let sig = signer.sign(algorithm, signing_input.as_bytes()).await?;
+            let sig_b64 = base64::encode_config(sig, base64::URL_SAFE_NO_PAD);
             let jws = signing_input + "." + &sig_b64;
             Ok(jws)
         } else {
@@ -1061,7 +1366,9 @@ impl Credential {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
-        let (_vc, result) = Self::decode_verify_jwt(jwt, options_opt, resolver, context_loader).await;
+        // Boxed to keep the size of the returned future small.
+        let (_vc, result) =
+            Box::pin(Self::decode_verify_jwt(jwt, options_opt, resolver, context_loader)).await;
         result
     }
 
@@ -1071,16 +1378,62 @@ impl Credential {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> (Option<Self>, VerificationResult) {
+        // Boxed to keep the size of the returned future small.
+        Box::pin(Self::decode_verify_jwt_checks(
+            jwt,
+            options_opt,
+            resolver,
+            context_loader,
+            None,
+        ))
+        .await
+    }
+
+    /// Decode and verify a JWT-encoded credential, like
+    /// [`decode_verify_jwt`](Self::decode_verify_jwt), loading the JSON Schemas of its
+    /// `credentialSchema` with `schema_loader` for the
+    /// [`CredentialSchema`](Check::CredentialSchema) check.
+    pub async fn decode_verify_jwt_with_schema_loader(
+        jwt: &str,
+        options_opt: Option<LinkedDataProofOptions>,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        schema_loader: &dyn SchemaLoader,
+    ) -> (Option<Self>, VerificationResult) {
+        Box::pin(Self::decode_verify_jwt_checks(
+            jwt,
+            options_opt,
+            resolver,
+            context_loader,
+            Some(schema_loader),
+        ))
+        .await
+    }
+
+    async fn decode_verify_jwt_checks(
+        jwt: &str,
+        options_opt: Option<LinkedDataProofOptions>,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        schema_loader: Option<&dyn SchemaLoader>,
+    ) -> (Option<Self>, VerificationResult) {
+        let policy = options_opt.as_ref().and_then(|opts| opts.policy.clone());
         let checks = options_opt
             .as_ref()
             .and_then(|opts| opts.checks.clone())
-            .unwrap_or_default();
+            .unwrap_or_default()
+            .into_iter()
+            .chain(policy.iter().flat_map(|policy| policy.required_checks.clone()))
+            .collect::<Vec<Check>>();
         let (header_b64, payload_enc, signature_b64) = match crate::jws::split_jws(jwt) {
             Ok(parts) => parts,
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to split JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to split JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1094,7 +1447,10 @@ impl Credential {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to decode JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to decode JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1103,7 +1459,10 @@ impl Credential {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to decode JWS claims: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to decode JWS claims: {}", err),
+                    ),
                 );
             }
         };
@@ -1112,17 +1471,20 @@ impl Credential {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!(
-                        "Unable to convert JWT claims to VC: {}",
-                        err
-                    )),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to convert JWT claims to VC: {}", err),
+                    ),
                 );
             }
         };
         if let Err(err) = vc.validate_unsigned() {
             return (
                 None,
-                VerificationResult::error(&format!("Invalid VC: {}", err)),
+                VerificationResult::problem(
+                    ProblemCode::InvalidDocument,
+                    format!("Invalid VC: {}", err),
+                ),
             );
         }
         // TODO: error if any unconvertable claims
@@ -1135,14 +1497,23 @@ impl Credential {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to filter proofs: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::VerificationMethodResolution,
+                        format!("Unable to filter proofs: {}", err),
+                    ),
                 );
             }
         };
         let verification_method = match header.key_id {
             Some(kid) => kid,
             None => {
-                return (None, VerificationResult::error("JWT header missing key id"));
+                return (
+                    None,
+                    VerificationResult::problem(
+                        ProblemCode::KeyResolution,
+                        "JWT header missing key id",
+                    ),
+                );
             }
         };
         let key = match crate::ldp::resolve_key(&verification_method, resolver).await {
@@ -1150,7 +1521,10 @@ impl Credential {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to resolve key for JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::KeyResolution,
+                        format!("Unable to resolve key for JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1162,13 +1536,24 @@ impl Credential {
                 &key,
                 &signature,
             ) {
-                Ok(mut warnings) => {
+                Ok(warnings) => {
                     results.checks.push(Check::JWS);
-                    results.warnings.append(&mut warnings);
+                    results.warnings.extend(warnings.into_iter().map(|warning| {
+                        VerificationProblem::new(ProblemCode::ProofWarning, warning)
+                    }));
                 }
-                Err(err) => results
-                    .errors
-                    .push(format!("Unable to filter proofs: {}", err)),
+                Err(err) => results.errors.push(VerificationProblem::new(
+                    ProblemCode::InvalidSignature,
+                    format!("Unable to filter proofs: {}", err),
+                )),
+            }
+            if let Some(ref policy) = policy {
+                results.append(&mut policy.check_algorithm(header.algorithm));
+            }
+            let mut results = results.in_context(Check::JWS, None, Some(&verification_method));
+            if let Some(ref policy) = policy {
+                results.append(&mut policy.check_credential(&vc, Utc::now()));
+                results.append(&mut policy.check_required_checks(&results.checks));
             }
             return (Some(vc), results);
         }
@@ -1176,21 +1561,45 @@ impl Credential {
         if proofs.is_empty() {
             return (
                 None,
-                VerificationResult::error("No applicable JWS or proof"),
+                VerificationResult::problem(
+                    ProblemCode::NoApplicableProof,
+                    "No applicable JWS or proof",
+                ),
             );
         }
         // Try verifying each proof until one succeeds
-        for proof in proofs {
-            let mut result = proof.verify(&vc, resolver, context_loader).await;
+        for (i, proof) in proofs {
+            if let Some(ref policy) = policy {
+                let mut violations = Box::pin(policy.check_proof(proof, resolver))
+                    .await
+                    .in_context(Check::Proof, Some(i), None);
+                if !violations.errors.is_empty() {
+                    results.append(&mut violations);
+                    continue;
+                }
+            }
+            let mut result = proof
+                .verify(&vc, resolver, context_loader)
+                .await
+                .in_context(Check::Proof, Some(i), None);
             results.append(&mut result);
             if results.errors.is_empty() {
                 results.checks.push(Check::Proof);
                 break;
             };
         }
+        if let Some(ref policy) = policy {
+            results.append(&mut policy.check_credential(&vc, Utc::now()));
+        }
         if checks.contains(&Check::CredentialStatus) {
             results.append(&mut vc.check_status(resolver, context_loader).await);
         }
+        if checks.contains(&Check::CredentialSchema) {
+            results.append(&mut vc.check_schema_with_loader(schema_loader).await);
+        }
+        if let Some(ref policy) = policy {
+            results.append(&mut policy.check_required_checks(&results.checks));
+        }
         (Some(vc), results)
     }
 
@@ -1213,8 +1622,22 @@ impl Credential {
                 return Err(Error::EmptyCredentialSubject);
             }
         }
-        if self.issuance_date.is_none() {
-            return Err(Error::MissingIssuanceDate);
+        match self.context.version() {
+            DataModelVersion::V1 => {
+                if self.issuance_date.is_none() {
+                    return Err(Error::MissingIssuanceDate);
+                }
+            }
+            DataModelVersion::V2 => {
+                // https://www.w3.org/TR/vc-data-model-2.0/#validity-period
+                // validFrom and validUntil are optional, but must describe a non-empty period.
+                if let (Some(valid_from), Some(valid_until)) = (&self.valid_from, &self.valid_until)
+                {
+                    if valid_from.date_time > valid_until.date_time {
+                        return Err(Error::InvalidValidityPeriod);
+                    }
+                }
+            }
         }
 
         if self.is_zkp() && self.credential_schema.is_none() {
@@ -1253,12 +1676,34 @@ impl Credential {
         Ok(())
     }
 
+    /// Check that the credential is valid at the given time.
+    ///
+    /// The dates checked depend on the [data model version](Contexts::version):
+    /// `issuanceDate` and `expirationDate` for v1.1, `validFrom` and `validUntil` for v2.0.
+    pub fn check_validity_period(&self, time: DateTime<Utc>) -> Result<(), Error> {
+        let (not_before, not_after) = match self.context.version() {
+            DataModelVersion::V1 => (&self.issuance_date, &self.expiration_date),
+            DataModelVersion::V2 => (&self.valid_from, &self.valid_until),
+        };
+        if let Some(not_before) = not_before {
+            if time < not_before.date_time {
+                return Err(Error::CredentialNotYetValid);
+            }
+        }
+        if let Some(not_after) = not_after {
+            if time >= not_after.date_time {
+                return Err(Error::ExpiredCredential);
+            }
+        }
+        Ok(())
+    }
+
     async fn filter_proofs(
         &self,
         options: Option<LinkedDataProofOptions>,
         jwt_params: Option<(&Header, &JWTClaims)>,
         resolver: &dyn DIDResolver,
-    ) -> Result<(Vec<&Proof>, bool), String> {
+    ) -> Result<(Vec<(usize, &Proof)>, bool), String> {
         // Allow any of issuer's verification methods by default
         let mut options = options.unwrap_or_default();
         let allowed_vms = match options.verification_method.take() {
@@ -1284,7 +1729,8 @@ impl Credential {
             .proof
             .iter()
             .flatten()
-            .filter(|proof| proof.matches(&options, &allowed_vms))
+            .enumerate()
+            .filter(|(_, proof)| proof.matches(&options, &allowed_vms))
             .collect();
         let matched_jwt = match jwt_params {
             Some((header, claims)) => jwt_matches(
@@ -1306,24 +1752,81 @@ impl Credential {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
+        // Boxed to keep the size of the returned future small.
+        Box::pin(self.verify_checks(options, resolver, context_loader, None)).await
+    }
+
+    /// Verify the credential, like [`verify`](Self::verify), loading the JSON Schemas of its
+    /// `credentialSchema` with `schema_loader` for the
+    /// [`CredentialSchema`](Check::CredentialSchema) check.
+    pub async fn verify_with_schema_loader(
+        &self,
+        options: Option<LinkedDataProofOptions>,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        schema_loader: &dyn SchemaLoader,
+    ) -> VerificationResult {
+        Box::pin(self.verify_checks(options, resolver, context_loader, Some(schema_loader))).await
+    }
+
+    async fn verify_checks(
+        &self,
+        options: Option<LinkedDataProofOptions>,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+        schema_loader: Option<&dyn SchemaLoader>,
+    ) -> VerificationResult {
+        let policy = options.as_ref().and_then(|opts| opts.policy.clone());
         let checks = options
             .as_ref()
             .and_then(|opts| opts.checks.clone())
-            .unwrap_or_default();
+            .unwrap_or_default()
+            .into_iter()
+            .chain(policy.iter().flat_map(|policy| policy.required_checks.clone()))
+            .collect::<Vec<Check>>();
         let (proofs, _) = match self.filter_proofs(options, None, resolver).await {
             Ok(proofs) => proofs,
             Err(err) => {
-                return VerificationResult::error(&format!("Unable to filter proofs: {}", err));
+                return VerificationResult::problem(
+                    ProblemCode::VerificationMethodResolution,
+                    format!("Unable to filter proofs: {}", err),
+                );
             }
         };
         if proofs.is_empty() {
-            return VerificationResult::error("No applicable proof");
+            return VerificationResult::problem(
+                ProblemCode::NoApplicableProof,
+                "No applicable proof",
+            );
             // TODO: say why, e.g. expired
         }
         let mut results = VerificationResult::new();
+        // The v1.1 dates are left to the caller (or a policy) as before.
+        if self.context.version() == DataModelVersion::V2 {
+            if let Err(err) = self.check_validity_period(Utc::now()) {
+                results
+                    .errors
+                    .push(VerificationProblem::new(ProblemCode::ValidityPeriod, err.to_string()));
+            }
+        }
+        if let Some(ref policy) = policy {
+            results.append(&mut policy.check_credential(self, Utc::now()));
+        }
         // Try verifying each proof until one succeeds
-        for proof in proofs {
-            let mut result = proof.verify(self, resolver, context_loader).await;
+        for (i, proof) in proofs {
+            if let Some(ref policy) = policy {
+                let mut violations = Box::pin(policy.check_proof(proof, resolver))
+                    .await
+                    .in_context(Check::Proof, Some(i), None);
+                if !violations.errors.is_empty() {
+                    results.append(&mut violations);
+                    continue;
+                }
+            }
+            let mut result = proof
+                .verify(self, resolver, context_loader)
+                .await
+                .in_context(Check::Proof, Some(i), None);
             results.append(&mut result);
             if result.errors.is_empty() {
                 results.checks.push(Check::Proof);
@@ -1333,6 +1836,12 @@ impl Credential {
         if checks.contains(&Check::CredentialStatus) {
             results.append(&mut self.check_status(resolver, context_loader).await);
         }
+        if checks.contains(&Check::CredentialSchema) {
+            results.append(&mut self.check_schema_with_loader(schema_loader).await);
+        }
+        if let Some(ref policy) = policy {
+            results.append(&mut policy.check_required_checks(&results.checks));
+        }
         results
     }
 
@@ -1341,12 +1850,12 @@ impl Credential {
     // https://w3c-ccg.github.io/vc-http-api/#/Issuer/issueCredential
     pub async fn generate_proof(
         &self,
-        jwk: &JWK,
+        signer: &dyn Signer,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> Result<Proof, Error> {
-        LinkedDataProofs::sign(self, options, resolver, context_loader, jwk, None).await
+        LinkedDataProofs::sign(self, options, resolver, context_loader, signer, None).await
     }
 
     /// Prepare to generate a linked data proof. Returns the signing input for the caller to sign
@@ -1376,35 +1885,147 @@ impl Credential {
 
     /// Check the credentials [status](https://www.w3.org/TR/vc-data-model/#status)
     pub async fn check_status(&self, resolver: &dyn DIDResolver, context_loader: &mut ContextLoader) -> VerificationResult {
-        let status = match self.credential_status {
-            Some(ref status) => status,
-            None => return VerificationResult::error("Missing credentialStatus"),
+        self.check_status_problems(resolver, context_loader)
+            .await
+            .in_context(Check::CredentialStatus, None, None)
+    }
+
+    async fn check_status_problems(
+        &self,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> VerificationResult {
+        let statuses = match self.credential_status {
+            Some(ref statuses) => statuses,
+            None => {
+                return VerificationResult::problem(
+                    ProblemCode::CredentialStatus,
+                    "Missing credentialStatus",
+                )
+            }
         };
+        let mut result = VerificationResult::new();
+        for status in statuses {
+            result.append(&mut self.check_status_entry(status, resolver, context_loader).await);
+        }
+        if !result.errors.is_empty() {
+            return result;
+        }
+        result.checks.push(Check::CredentialStatus);
+        result
+    }
+
+    async fn check_status_entry(
+        &self,
+        status: &Status,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> VerificationResult {
         let status_value = match serde_json::to_value(status.clone()) {
             Ok(status) => status,
             Err(e) => {
-                return VerificationResult::error(&format!(
-                    "Unable to convert credentialStatus: {}",
-                    e
-                ))
+                return VerificationResult::problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to convert credentialStatus: {}", e),
+                )
             }
         };
         let checkable_status: CheckableStatus = match serde_json::from_value(status_value) {
             Ok(checkable_status) => checkable_status,
             Err(e) => {
-                return VerificationResult::error(&format!(
-                    "Unable to parse credentialStatus: {}",
-                    e
-                ))
+                return VerificationResult::problem(
+                    ProblemCode::CredentialStatus,
+                    format!("Unable to parse credentialStatus: {}", e),
+                )
             }
         };
-        let mut result = checkable_status.check(self, resolver, context_loader).await;
-        if !result.errors.is_empty() {
-            return result;
+        checkable_status.check(self, resolver, context_loader).await
+    }
+
+    /// Validate the [credential subjects](https://www.w3.org/TR/vc-data-model/#credential-subject)
+    /// against the JSON Schemas of the credential's
+    /// [`credentialSchema`](https://www.w3.org/TR/vc-data-model/#data-schemas) entries.
+    ///
+    /// Entries of type [`JsonSchemaValidator2018`](JSON_SCHEMA_VALIDATOR_2018_TYPE) and
+    /// [`JsonSchema`](JSON_SCHEMA_TYPE) are validated; other entries result in a warning.
+    ///
+    /// Only the `credentialSubject` values are validated, each on its own. The other properties
+    /// of the credential are not checked against the schemas.
+    pub async fn check_schema(&self, loader: &dyn SchemaLoader) -> VerificationResult {
+        self.check_schema_problems(loader)
+            .await
+            .in_context(Check::CredentialSchema, None, None)
+    }
+
+    async fn check_schema_problems(&self, loader: &dyn SchemaLoader) -> VerificationResult {
+        let schemas = match self.credential_schema {
+            Some(ref schemas) => schemas,
+            None => {
+                return VerificationResult::problem(
+                    ProblemCode::CredentialSchema,
+                    "Missing credentialSchema",
+                )
+            }
+        };
+        let subjects = match serde_json::to_value(&self.credential_subject) {
+            Ok(Value::Array(subjects)) => subjects,
+            Ok(subject) => vec![subject],
+            Err(e) => {
+                return VerificationResult::problem(
+                    ProblemCode::CredentialSchema,
+                    format!("Unable to convert credentialSubject: {}", e),
+                )
+            }
+        };
+        let mut result = VerificationResult::new();
+        for schema in schemas {
+            if schema.type_ != JSON_SCHEMA_VALIDATOR_2018_TYPE && schema.type_ != JSON_SCHEMA_TYPE {
+                result.warnings.push(VerificationProblem::new(
+                    ProblemCode::Unsupported,
+                    format!("Unsupported credentialSchema type: {}", schema.type_),
+                ));
+                continue;
+            }
+            let json_schema = match loader.load(schema.id.as_str()).await {
+                Ok(json_schema) => json_schema,
+                Err(e) => {
+                    result.errors.push(VerificationProblem::new(
+                        ProblemCode::CredentialSchema,
+                        format!("Unable to load credentialSchema {}: {}", schema.id, e),
+                    ));
+                    continue;
+                }
+            };
+            for subject in &subjects {
+                if let Err(e) = crate::json_schema::validate(&json_schema, subject) {
+                    result.errors.push(VerificationProblem::new(
+                        ProblemCode::CredentialSchema,
+                        format!(
+                            "credentialSubject does not match credentialSchema {}: {}",
+                            schema.id, e
+                        ),
+                    ));
+                }
+            }
+        }
+        if result.errors.is_empty() {
+            result.checks.push(Check::CredentialSchema);
         }
-        result.checks.push(Check::CredentialStatus);
         result
     }
+
+    async fn check_schema_with_loader(
+        &self,
+        loader: Option<&dyn SchemaLoader>,
+    ) -> VerificationResult {
+        match loader {
+            Some(loader) => self.check_schema(loader).await,
+            None => VerificationResult::problem(
+                ProblemCode::CredentialSchema,
+                "No schema loader to check credentialSchema",
+            ),
+        }
+    }
 }
 
 impl CheckableStatus {
@@ -1417,6 +2038,7 @@ impl CheckableStatus {
         match self {
             Self::RevocationList2020Status(status) => status.check(credential, resolver, context_loader).await,
             Self::StatusList2021Entry(status) => status.check(credential, resolver, context_loader).await,
+            Self::BitstringStatusListEntry(status) => status.check(credential, resolver, context_loader).await,
         }
     }
 }
@@ -1514,11 +2136,29 @@ impl Presentation {
         jwk: Option<&JWK>,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
+    ) -> Result<String, Error> {
+        self.generate_jwt_with_signer(jwk.map(|jwk| jwk as &dyn Signer), options, resolver)
+            .await
+    }
+
+    /// Encode the Verifiable Presentation as JWT, like [`generate_jwt`](Self::generate_jwt), signing
+    /// it with a [`Signer`] if one is passed.
+    pub async fn generate_jwt_with_signer(
+        &self,
+        signer: Option<&dyn Signer>,
+        options: &LinkedDataProofOptions,
+        resolver: &dyn DIDResolver,
     ) -> Result<String, Error> {
         let mut options = options.clone();
-        if let Some(jwk) = jwk {
-            crate::ldp::ensure_or_pick_verification_relationship(&mut options, self, jwk, resolver)
-                .await?;
+        let public_key = signer.map(|signer| signer.public_key());
+        if let Some(ref public_key) = public_key {
+            crate::ldp::ensure_or_pick_verification_relationship(
+                &mut options,
+                self,
+                public_key,
+                resolver,
+            )
+            .await?;
             // If no JWK is passed, there is no verification relationship.
         }
         let LinkedDataProofOptions {
@@ -1530,6 +2170,8 @@ impl Presentation {
             checks,
             eip712_domain,
             type_,
+            cryptosuite,
+            policy,
         } = options;
         if checks.is_some() {
             return Err(Error::UnencodableOptionClaim("checks".to_string()));
@@ -1543,6 +2185,12 @@ impl Presentation {
         if type_.is_some() {
             return Err(Error::UnencodableOptionClaim("type".to_string()));
         }
+        if cryptosuite.is_some() {
+            return Err(Error::UnencodableOptionClaim("cryptosuite".to_string()));
+        }
+        if policy.is_some() {
+            return Err(Error::UnencodableOptionClaim("policy".to_string()));
+        }
         match proof_purpose {
             None => (),
             Some(ProofPurpose::Authentication) => (),
@@ -1556,12 +2204,12 @@ impl Presentation {
             },
             ..self.to_jwt_claims()?
         };
-        let algorithm = if let Some(jwk) = jwk {
-            jwk.get_algorithm().ok_or(Error::MissingAlgorithm)?
+        let algorithm = if let Some(signer) = signer {
+            signer.algorithm().ok_or(Error::MissingAlgorithm)?
         } else {
             crate::jwk::Algorithm::None
         };
-        let key_id = match (jwk.and_then(|jwk| jwk.key_id.clone()), verification_method) {
+        let key_id = match (public_key.and_then(|jwk| jwk.key_id), verification_method) {
             (Some(jwk_kid), None) => Some(jwk_kid),
             (None, Some(vm_id)) => Some(vm_id.to_string()),
             (None, None) => None,
@@ -1577,9 +2225,10 @@ impl Presentation {
         };
         let header_b64 = base64_encode_json(&header)?;
         let payload_b64 = base64_encode_json(&claims)?;
-        if let Some(jwk) = jwk {
+        if let Some(signer) = signer {
             let signing_input = header_b64 + "." + &payload_b64;
-            let sig_b64 = crate::jws::sign_bytes_b64(algorithm, signing_input.as_bytes(), jwk)?;
+            let sig = signer.sign(algorithm, signing_input.as_bytes()).await?;
+            let sig_b64 = base64::encode_config(sig, base64::URL_SAFE_NO_PAD);
             let jws = signing_input + "." + &sig_b64;
             Ok(jws)
         } else {
@@ -1596,26 +2245,43 @@ impl Presentation {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> (Option<Self>, VerificationResult) {
+        let policy = options_opt.as_ref().and_then(|opts| opts.policy.clone());
         let checks = options_opt
             .as_ref()
             .and_then(|opts| opts.checks.clone())
-            .unwrap_or_default();
+            .unwrap_or_default()
+            .into_iter()
+            .chain(policy.iter().flat_map(|policy| policy.required_checks.clone()))
+            .collect::<Vec<Check>>();
         if checks.contains(&Check::CredentialStatus) {
             // TODO: apply check to embedded VCs
             return (
                 None,
-                VerificationResult::error(
+                VerificationResult::problem(
+                    ProblemCode::InapplicableCheck,
                     "credentialStatus check not valid for VerifiablePresentation",
                 ),
             );
         }
+        if checks.contains(&Check::CredentialSchema) {
+            return (
+                None,
+                VerificationResult::problem(
+                    ProblemCode::InapplicableCheck,
+                    "credentialSchema check not valid for VerifiablePresentation",
+                ),
+            );
+        }
         // let mut options = options_opt.unwrap_or_default();
         let (header_b64, payload_enc, signature_b64) = match crate::jws::split_jws(jwt) {
             Ok(parts) => parts,
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to split JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to split JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1629,7 +2295,10 @@ impl Presentation {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to decode JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to decode JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1638,7 +2307,10 @@ impl Presentation {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to decode JWS claims: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to decode JWS claims: {}", err),
+                    ),
                 );
             }
         };
@@ -1647,17 +2319,20 @@ impl Presentation {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!(
-                        "Unable to convert JWT claims to VP: {}",
-                        err
-                    )),
+                    VerificationResult::problem(
+                        ProblemCode::InvalidJWT,
+                        format!("Unable to convert JWT claims to VP: {}", err),
+                    ),
                 );
             }
         };
         if let Err(err) = vp.validate_unsigned() {
             return (
                 None,
-                VerificationResult::error(&format!("Invalid VP: {}", err)),
+                VerificationResult::problem(
+                    ProblemCode::InvalidDocument,
+                    format!("Invalid VP: {}", err),
+                ),
             );
         }
         // TODO: error if any unconvertable claims
@@ -1670,14 +2345,23 @@ impl Presentation {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to filter proofs: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::VerificationMethodResolution,
+                        format!("Unable to filter proofs: {}", err),
+                    ),
                 );
             }
         };
         let verification_method = match header.key_id {
             Some(kid) => kid,
             None => {
-                return (None, VerificationResult::error("JWT header missing key id"));
+                return (
+                    None,
+                    VerificationResult::problem(
+                        ProblemCode::KeyResolution,
+                        "JWT header missing key id",
+                    ),
+                );
             }
         };
         let key = match crate::ldp::resolve_key(&verification_method, resolver).await {
@@ -1685,7 +2369,10 @@ impl Presentation {
             Err(err) => {
                 return (
                     None,
-                    VerificationResult::error(&format!("Unable to resolve key for JWS: {}", err)),
+                    VerificationResult::problem(
+                        ProblemCode::KeyResolution,
+                        format!("Unable to resolve key for JWS: {}", err),
+                    ),
                 );
             }
         };
@@ -1697,32 +2384,63 @@ impl Presentation {
                 &key,
                 &signature,
             ) {
-                Ok(mut warnings) => {
+                Ok(warnings) => {
                     results.checks.push(Check::JWS);
-                    results.warnings.append(&mut warnings);
+                    results.warnings.extend(warnings.into_iter().map(|warning| {
+                        VerificationProblem::new(ProblemCode::ProofWarning, warning)
+                    }));
                 }
-                Err(err) => results
-                    .errors
-                    .push(format!("Unable to filter proofs: {}", err)),
+                Err(err) => results.errors.push(VerificationProblem::new(
+                    ProblemCode::InvalidSignature,
+                    format!("Unable to filter proofs: {}", err),
+                )),
+            }
+            if let Some(ref policy) = policy {
+                results.append(&mut policy.check_algorithm(header.algorithm));
             }
+            let results = results.in_context(Check::JWS, None, Some(&verification_method));
+            let results = vp
+                .check_policy(policy.as_deref(), results, resolver, context_loader)
+                .await;
             return (Some(vp), results);
         }
         // No JWS verified: try to verify a proof.
         if proofs.is_empty() {
             return (
                 None,
-                VerificationResult::error("No applicable JWS or proof"),
+                VerificationResult::problem(
+                    ProblemCode::NoApplicableProof,
+                    "No applicable JWS or proof",
+                ),
             );
         }
         // Try verifying each proof until one succeeds
-        for proof in proofs {
-            let mut result = proof.verify(&vp, resolver, context_loader).await;
+        for (i, proof) in proofs {
+            if let Some(ref policy) = policy {
+                let mut violations = Box::pin(policy.check_proof(proof, resolver))
+                    .await
+                    .in_context(Check::Proof, Some(i), None);
+                if !violations.errors.is_empty() {
+                    results.append(&mut violations);
+                    continue;
+                }
+            }
+            let mut result = proof
+                .verify(&vp, resolver, context_loader)
+                .await
+                .in_context(Check::Proof, Some(i), None);
             if result.errors.is_empty() {
                 result.checks.push(Check::Proof);
+                let result = vp
+                    .check_policy(policy.as_deref(), result, resolver, context_loader)
+                    .await;
                 return (Some(vp), result);
             };
             results.append(&mut result);
         }
+        let results = vp
+            .check_policy(policy.as_deref(), results, resolver, context_loader)
+            .await;
         (Some(vp), results)
     }
 
@@ -1732,7 +2450,9 @@ impl Presentation {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
-        let (_vp, result) = Self::decode_verify_jwt(jwt, options_opt, resolver, context_loader).await;
+        // Boxed to keep the size of the returned future small.
+        let (_vp, result) =
+            Box::pin(Self::decode_verify_jwt(jwt, options_opt, resolver, context_loader)).await;
         result
     }
 
@@ -1767,12 +2487,12 @@ impl Presentation {
 
     pub async fn generate_proof(
         &self,
-        jwk: &JWK,
+        signer: &dyn Signer,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> Result<Proof, Error> {
-        LinkedDataProofs::sign(self, options, resolver, context_loader, jwk, None).await
+        LinkedDataProofs::sign(self, options, resolver, context_loader, signer, None).await
     }
 
     /// Prepare to generate a linked data proof. Returns the signing input for the caller to sign
@@ -1805,7 +2525,7 @@ impl Presentation {
         options: Option<LinkedDataProofOptions>,
         jwt_params: Option<(&Header, &JWTClaims)>,
         resolver: &dyn DIDResolver,
-    ) -> Result<(Vec<&Proof>, bool), String> {
+    ) -> Result<(Vec<(usize, &Proof)>, bool), String> {
         // Allow any of holder's verification methods matching proof purpose by default
         let mut options = options.unwrap_or_else(|| LinkedDataProofOptions {
             proof_purpose: Some(ProofPurpose::Authentication),
@@ -1832,7 +2552,8 @@ impl Presentation {
             .proof
             .iter()
             .flatten()
-            .filter(|proof| {
+            .enumerate()
+            .filter(|(_, proof)| {
                 proof.matches_options(&options)
                     && if let Some(ref allowed_vms) = restrict_allowed_vms {
                         proof.matches_vms(allowed_vms)
@@ -1862,37 +2583,88 @@ impl Presentation {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
+        let policy = options.as_ref().and_then(|opts| opts.policy.clone());
         let checks = options
             .as_ref()
             .and_then(|opts| opts.checks.clone())
-            .unwrap_or_default();
+            .unwrap_or_default()
+            .into_iter()
+            .chain(policy.iter().flat_map(|policy| policy.required_checks.clone()))
+            .collect::<Vec<Check>>();
         if checks.contains(&Check::CredentialStatus) {
             // TODO: apply check to embedded VCs
-            return VerificationResult::error(
+            return VerificationResult::problem(
+                ProblemCode::InapplicableCheck,
                 "credentialStatus check not valid for VerifiablePresentation",
             );
         }
+        if checks.contains(&Check::CredentialSchema) {
+            return VerificationResult::problem(
+                ProblemCode::InapplicableCheck,
+                "credentialSchema check not valid for VerifiablePresentation",
+            );
+        }
         let (proofs, _) = match self.filter_proofs(options, None, resolver).await {
             Ok(proofs) => proofs,
             Err(err) => {
-                return VerificationResult::error(&format!("Unable to filter proofs: {}", err));
+                return VerificationResult::problem(
+                    ProblemCode::VerificationMethodResolution,
+                    format!("Unable to filter proofs: {}", err),
+                );
             }
         };
         if proofs.is_empty() {
-            return VerificationResult::error("No applicable proof");
+            return VerificationResult::problem(
+                ProblemCode::NoApplicableProof,
+                "No applicable proof",
+            );
             // TODO: say why, e.g. expired
         }
         let mut results = VerificationResult::new();
         // Try verifying each proof until one succeeds
-        for proof in proofs {
-            let mut result = proof.verify(self, resolver, context_loader).await;
+        for (i, proof) in proofs {
+            if let Some(ref policy) = policy {
+                let mut violations = Box::pin(policy.check_proof(proof, resolver))
+                    .await
+                    .in_context(Check::Proof, Some(i), None);
+                if !violations.errors.is_empty() {
+                    results.append(&mut violations);
+                    continue;
+                }
+            }
+            let mut result = proof
+                .verify(self, resolver, context_loader)
+                .await
+                .in_context(Check::Proof, Some(i), None);
             if result.errors.is_empty() {
                 result.checks.push(Check::Proof);
-                return result;
+                return self
+                    .check_policy(policy.as_deref(), result, resolver, context_loader)
+                    .await;
             };
             results.append(&mut result);
         }
-        results
+        self.check_policy(policy.as_deref(), results, resolver, context_loader).await
+    }
+
+    /// Apply a verification policy to the presented credentials, verifying them, and to the
+    /// checks that passed.
+    async fn check_policy(
+        &self,
+        policy: Option<&VerificationPolicy>,
+        mut result: VerificationResult,
+        resolver: &dyn DIDResolver,
+        context_loader: &mut ContextLoader,
+    ) -> VerificationResult {
+        if let Some(policy) = policy {
+            // Boxed to keep the size of the returned future small.
+            let now = Utc::now();
+            result.append(
+                &mut Box::pin(policy.check_presentation(self, resolver, context_loader, now)).await,
+            );
+            result.append(&mut policy.check_required_checks(&result.checks));
+        }
+        result
     }
 }
 
@@ -2057,6 +2829,9 @@ impl Proof {
         if let Some(ref type_) = options.type_ {
             assert_local!(&self.type_ == type_);
         }
+        if let Some(ref cryptosuite) = options.cryptosuite {
+            assert_local!(self.cryptosuite.as_ref() == Some(cryptosuite));
+        }
         true
     }
 
@@ -2082,9 +2857,10 @@ impl Proof {
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
-        LinkedDataProofs::verify(self, document, resolver, context_loader)
-            .await
-            .into()
+        VerificationResult::from(
+            LinkedDataProofs::verify(self, document, resolver, context_loader).await,
+        )
+        .in_context(Check::Proof, None, self.verification_method.as_deref())
     }
 }
 
@@ -2213,6 +2989,7 @@ fn verify_proof_consistency(proof: &Proof, dataset: &DataSet) -> Result<(), Erro
         ("Ed25519Signature2020", "https://w3id.org/security#Ed25519Signature2020") => (),
         ("EcdsaSecp256k1Signature2019", "https://w3id.org/security#EcdsaSecp256k1Signature2019") => (),
         ("EcdsaSecp256r1Signature2019", "https://w3id.org/security#EcdsaSecp256r1Signature2019") => (),
+        ("EcdsaSecp384r1Signature2019", "https://w3id.org/security#EcdsaSecp384r1Signature2019") => (),
         ("EcdsaSecp256k1RecoverySignature2020", "https://identity.foundation/EcdsaSecp256k1RecoverySignature2020#EcdsaSecp256k1RecoverySignature2020") => (),
         ("EcdsaSecp256k1RecoverySignature2020", "https://w3id.org/security#EcdsaSecp256k1RecoverySignature2020") => (),
         ("JsonWebSignature2020", "https://w3id.org/security#JsonWebSignature2020") => (),
@@ -2225,10 +3002,20 @@ fn verify_proof_consistency(proof: &Proof, dataset: &DataSet) -> Result<(), Erro
         ("TezosJcsSignature2021", "https://w3id.org/security#TezosJcsSignature2021") => (),
         ("AleoSignature2021", "https://w3id.org/security#AleoSignature2021") => (),
         ("SolanaSignature2021", "https://w3id.org/security#SolanaSignature2021") => (),
+        ("DataIntegrityProof", "https://w3id.org/security#DataIntegrityProof") => (),
+        ("BbsBlsSignature2020", "https://w3id.org/security#BbsBlsSignature2020") => (),
+        ("BbsBlsSignatureProof2020", "https://w3id.org/security#BbsBlsSignatureProof2020") => (),
+        (type_, iri) if ProofSuiteRegistry::read_global().has_type_iri(type_, iri) => (),
         _ => return Err(Error::UnexpectedTriple(type_triple.clone())),
     };
     let proof_id = &type_triple.subject;
 
+    graph_ref.match_typed_string_property(
+        proof_id,
+        "https://w3id.org/security#cryptosuite",
+        "https://w3id.org/security#cryptosuiteString",
+        proof.cryptosuite.as_deref(),
+    )?;
     graph_ref.match_iri_property(
         proof_id,
         "https://w3id.org/security#proofPurpose",
@@ -2355,6 +3142,7 @@ impl FromStr for Check {
             "proof" => Ok(Self::Proof),
             "JWS" => Ok(Self::JWS),
             "credentialStatus" => Ok(Self::CredentialStatus),
+            "credentialSchema" => Ok(Self::CredentialSchema),
             _ => Err(Error::UnsupportedCheck),
         }
     }
@@ -2373,6 +3161,7 @@ impl From<Check> for String {
             Check::Proof => "proof".to_string(),
             Check::JWS => "JWS".to_string(),
             Check::CredentialStatus => "credentialStatus".to_string(),
+            Check::CredentialSchema => "credentialSchema".to_string(),
         }
     }
 }
@@ -2462,6 +3251,10 @@ pub(crate) mod tests {
         "https://example.com/credentials/status/3";
     pub const EXAMPLE_STATUS_LIST_2021: &'static [u8] = include_bytes!("../tests/statusList.json");
 
+    pub const EXAMPLE_BITSTRING_STATUS_LIST_URL: &str = "https://example.com/credentials/status/4";
+    pub const EXAMPLE_BITSTRING_STATUS_LIST: &[u8] =
+        include_bytes!("../tests/bitstringStatusList.json");
+
     const JWK_JSON: &'static str = include_str!("../tests/rsa2048-2020-08-25.json");
 
     #[test]
@@ -2524,6 +3317,92 @@ pub(crate) mod tests {
         println!("{}", serde_json::to_string_pretty(&doc).unwrap());
     }
 
+    #[test]
+    fn credential_v2_validate() {
+        let doc_str = r###"{
+            "@context": [
+              "https://www.w3.org/ns/credentials/v2",
+              "https://www.w3.org/ns/credentials/examples/v2"
+            ],
+            "id": "http://example.org/credentials/3731",
+            "type": ["VerifiableCredential"],
+            "name": "Example Credential",
+            "description": "A credential using the VC Data Model v2.0",
+            "issuer": "did:example:30e07a529f32d234f6181736bd3",
+            "validFrom": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###;
+        let vc = Credential::from_json_unsigned(doc_str).unwrap();
+        assert_eq!(vc.context.version(), DataModelVersion::V2);
+        assert_eq!(vc.name, Some(Value::from("Example Credential")));
+        assert!(vc.description.is_some());
+        assert!(vc.issuance_date.is_none());
+        vc.check_validity_period(Utc::now()).unwrap();
+
+        // validFrom must not be later than validUntil
+        let vc = Credential {
+            valid_until: Some(VCDateTime::from_str("2020-01-01T00:00:00Z").unwrap()),
+            ..vc
+        };
+        assert!(vc.validate_unsigned().is_err());
+
+        // Without validFrom and validUntil, the credential is always valid.
+        let vc = Credential {
+            valid_from: None,
+            valid_until: None,
+            ..vc
+        };
+        vc.validate_unsigned().unwrap();
+        vc.check_validity_period(Utc::now()).unwrap();
+
+        // v1.1 credentials still require issuanceDate, and ignore validFrom/validUntil.
+        let mut vc = vc;
+        vc.context = Contexts::One(Context::URI(URI::String(DEFAULT_CONTEXT.to_string())));
+        assert_eq!(vc.context.version(), DataModelVersion::V1);
+        vc.valid_until = Some(VCDateTime::from(Utc::now() - chrono::Duration::weeks(1)));
+        assert!(vc.validate_unsigned().is_err());
+        vc.issuance_date = Some(VCDateTime::from(Utc::now() - chrono::Duration::weeks(2)));
+        vc.validate_unsigned().unwrap();
+        vc.check_validity_period(Utc::now()).unwrap();
+    }
+
+    #[test]
+    fn credential_v2_jwt_claims() {
+        let doc_str = r###"{
+            "@context": "https://www.w3.org/ns/credentials/v2",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:30e07a529f32d234f6181736bd3",
+            "validFrom": "2020-08-19T21:41:50Z",
+            "validUntil": "2030-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###;
+        let vc = Credential::from_json_unsigned(doc_str).unwrap();
+        let claims = vc.to_jwt_claims().unwrap();
+        let valid_from = VCDateTime::from_str("2020-08-19T21:41:50Z").unwrap();
+        let valid_until = VCDateTime::from_str("2030-08-19T21:41:50Z").unwrap();
+        assert_eq!(
+            claims.not_before,
+            Some(NumericDate::try_from(valid_from.date_time).unwrap())
+        );
+        assert_eq!(
+            claims.expiration_time,
+            Some(NumericDate::try_from(valid_until.date_time).unwrap())
+        );
+        let mut claims_value = serde_json::to_value(&claims).unwrap();
+        claims_value["vc"].as_object_mut().unwrap().remove("validFrom");
+        claims_value["vc"].as_object_mut().unwrap().remove("validUntil");
+        let claims: JWTClaims = serde_json::from_value(claims_value).unwrap();
+        let vc1 = Credential::from_jwt_claims(claims).unwrap();
+        assert!(vc1.issuance_date.is_none());
+        assert!(vc1.expiration_date.is_none());
+        assert_eq!(String::from(vc1.valid_from.unwrap()), String::from(valid_from));
+        assert_eq!(String::from(vc1.valid_until.unwrap()), String::from(valid_until));
+    }
+
     #[test]
     fn test_vc_date_time_roundtrip() {
         let expected_utc_now = chrono::Utc::now();
@@ -2633,6 +3512,53 @@ pub(crate) mod tests {
         assert!(verification_result.errors.len() > 0);
     }
 
+    #[async_std::test]
+    async fn issue_with_file_signer() {
+        let signer = crate::signer::FileSigner::open("tests/rsa2048-2020-08-25.json").unwrap();
+        let mut vc = Credential::from_json_unsigned(
+            r###"{
+                "@context": "https://www.w3.org/2018/credentials/v1",
+                "id": "http://example.org/credentials/3731",
+                "type": ["VerifiableCredential"],
+                "issuer": "did:example:foo",
+                "issuanceDate": "2020-08-19T21:41:50Z",
+                "credentialSubject": {
+                    "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+                }
+            }"###,
+        )
+        .unwrap();
+        let options = LinkedDataProofOptions {
+            checks: None,
+            created: None,
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            ..Default::default()
+        };
+        let mut context_loader = crate::jsonld::ContextLoader::default();
+
+        let jwt = vc
+            .generate_jwt_with_signer(Some(&signer), &options, &DIDExample)
+            .await
+            .unwrap();
+        let (_, verification_result) = Credential::decode_verify_jwt(
+            &jwt,
+            Some(options.clone()),
+            &DIDExample,
+            &mut context_loader,
+        )
+        .await;
+        assert!(verification_result.errors.is_empty());
+
+        let proof = vc
+            .generate_proof(&signer, &options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        let verification_result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+    }
+
     #[async_std::test]
     async fn decode_verify_jwt_single_array_subject() {
         let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
@@ -2723,6 +3649,101 @@ pub(crate) mod tests {
         assert!(verification_result.errors.len() >= 1);
     }
 
+    #[async_std::test]
+    async fn credential_v2_issue_verify() {
+        let vc_str = r###"{
+            "@context": "https://www.w3.org/ns/credentials/v2",
+            "id": "http://example.org/credentials/3731",
+            "type": ["VerifiableCredential"],
+            "name": "Example Credential",
+            "issuer": "did:example:foo",
+            "validFrom": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###;
+        let mut vc: Credential = Credential::from_json_unsigned(vc_str).unwrap();
+        let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            ..Default::default()
+        };
+        let mut context_loader = crate::jsonld::ContextLoader::default();
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        vc.validate().unwrap();
+        let verification_result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        // Expired credential
+        let mut vc = Credential {
+            valid_until: Some(VCDateTime::from(Utc::now() - chrono::Duration::weeks(1))),
+            proof: None,
+            ..vc
+        };
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        let verification_result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.checks.contains(&Check::Proof));
+        assert_eq!(verification_result.errors, vec!["Credential has expired"]);
+
+        // The v1.1 expirationDate is not checked by verify.
+        let mut vc_v1 = Credential::from_json_unsigned(
+            r###"{
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:foo",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "expirationDate": "2021-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###,
+        )
+        .unwrap();
+        let proof = vc_v1
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc_v1.add_proof(proof);
+        assert!(vc_v1.check_validity_period(Utc::now()).is_err());
+        let verification_result = vc_v1.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+
+        // Presentation using the v2.0 context
+        let mut vp = Presentation {
+            context: Contexts::One(Context::URI(URI::String(DEFAULT_CONTEXT_V2.to_string()))),
+            verifiable_credential: Some(OneOrMany::One(CredentialOrJWT::Credential(vc))),
+            holder: Some(URI::String("did:example:foo".to_string())),
+            ..Default::default()
+        };
+        vp.validate_unsigned().unwrap();
+        let vp_issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            proof_purpose: Some(ProofPurpose::Authentication),
+            ..Default::default()
+        };
+        let vp_proof = vp
+            .generate_proof(&key, &vp_issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vp.add_proof(vp_proof);
+        let vp_str = serde_json::to_string(&vp).unwrap();
+        let vp = Presentation::from_json(&vp_str).unwrap();
+        let verification_result = vp.verify(None, &DIDExample, &mut context_loader).await;
+        println!("{:#?}", verification_result);
+        assert!(verification_result.errors.is_empty());
+    }
+
     #[async_std::test]
     async fn credential_issue_verify_bs58() {
         let vc_str = r###"{
@@ -3161,6 +4182,149 @@ _:c14n0 <https://w3id.org/security#verificationMethod> <https://example.org/foo/
         assert_ne!(verification_result.errors.len(), 0);
     }
 
+    #[async_std::test]
+    async fn credential_schema() {
+        use crate::json_schema::{FileSchemaLoader, MemorySchemaLoader};
+        use serde_json::json;
+        const SCHEMA_ID: &str = "https://example.org/schemas/alumni.json";
+        let mut vc: Credential = serde_json::from_value(json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                "https://www.w3.org/2018/credentials/examples/v1"
+            ],
+            "type": ["VerifiableCredential", "AlumniCredential"],
+            "issuer": "did:example:foo",
+            "issuanceDate": "2021-08-25T20:15:45Z",
+            "credentialSubject": {
+                "id": "did:example:bar",
+                "alumniOf": "Example University"
+            },
+            "credentialSchema": {
+                "id": SCHEMA_ID,
+                "type": "JsonSchemaValidator2018"
+            }
+        }))
+        .unwrap();
+        let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            ..Default::default()
+        };
+        let verify_options = LinkedDataProofOptions {
+            checks: Some(vec![Check::Proof, Check::CredentialSchema]),
+            ..Default::default()
+        };
+        let mut file_loader = FileSchemaLoader::new();
+        file_loader.insert(SCHEMA_ID, "tests/schema-alumni.json");
+        let mut context_loader = ContextLoader::default();
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+        let result = vc
+            .verify_with_schema_loader(
+                Some(verify_options.clone()),
+                &DIDExample,
+                &mut context_loader,
+                &file_loader,
+            )
+            .await;
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert!(result.checks.contains(&Check::CredentialSchema));
+
+        // The credential subject must match the schema.
+        let mut memory_loader = MemorySchemaLoader::new();
+        memory_loader.insert(SCHEMA_ID, json!({"type": "object", "required": ["degree"]}));
+        let result = vc
+            .verify_with_schema_loader(
+                Some(verify_options.clone()),
+                &DIDExample,
+                &mut context_loader,
+                &memory_loader,
+            )
+            .await;
+        assert_eq!(result.errors.len(), 1);
+        assert!(!result.checks.contains(&Check::CredentialSchema));
+
+        // The schema must be loaded.
+        let result = vc
+            .verify(Some(verify_options.clone()), &DIDExample, &mut context_loader)
+            .await;
+        assert_eq!(result.errors.len(), 1);
+        let result = vc
+            .verify_with_schema_loader(
+                Some(verify_options),
+                &DIDExample,
+                &mut context_loader,
+                &MemorySchemaLoader::new(),
+            )
+            .await;
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code, ProblemCode::CredentialSchema);
+        assert_eq!(result.errors[0].check, Some(Check::CredentialSchema));
+    }
+
+    #[async_std::test]
+    async fn verification_problems() {
+        use serde_json::json;
+        let mut vc: Credential = Credential::from_json_unsigned(
+            r###"{
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "id": "http://example.org/credentials/3731",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:foo",
+            "issuanceDate": "2020-08-19T21:41:50Z",
+            "credentialSubject": {
+                "id": "did:example:d23dd687a7dc6787646f2eb98d0"
+            }
+        }"###,
+        )
+        .unwrap();
+        let key: JWK = serde_json::from_str(JWK_JSON).unwrap();
+        let issue_options = LinkedDataProofOptions {
+            verification_method: Some(URI::String("did:example:foo#key1".to_string())),
+            ..Default::default()
+        };
+        let mut context_loader = ContextLoader::default();
+        let proof = vc
+            .generate_proof(&key, &issue_options, &DIDExample, &mut context_loader)
+            .await
+            .unwrap();
+        vc.add_proof(proof);
+
+        // A proof of a modified credential is reported with its index and verification method.
+        vc.id = Some(URI::String("http://example.org/credentials/3732".to_string()));
+        let result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        assert_eq!(result.errors.len(), 1);
+        let problem = &result.errors[0];
+        assert_eq!(problem.code, ProblemCode::InvalidSignature);
+        assert_eq!(problem.check, Some(Check::Proof));
+        assert_eq!(problem.proof_index, Some(0));
+        assert_eq!(
+            problem.verification_method.as_deref(),
+            Some("did:example:foo#key1")
+        );
+
+        // Results still serialize errors as messages.
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["errors"], json!([problem.message]));
+        let result: VerificationResult = serde_json::from_value(value).unwrap();
+        assert_eq!(result.errors[0].code, ProblemCode::Other);
+        let result: VerificationResult = serde_json::from_value(json!({
+            "checks": [],
+            "warnings": [],
+            "errors": [{"code": "noApplicableProof", "message": "No applicable proof"}]
+        }))
+        .unwrap();
+        assert_eq!(result.errors[0].code, ProblemCode::NoApplicableProof);
+        assert_eq!(result.errors, vec!["No applicable proof"]);
+
+        vc.proof = None;
+        let result = vc.verify(None, &DIDExample, &mut context_loader).await;
+        assert_eq!(result.errors[0].code, ProblemCode::NoApplicableProof);
+    }
+
     #[tokio::test]
     async fn credential_status_2021() {
         use serde_json::json;
@@ -3222,6 +4386,165 @@ _:c14n0 <https://w3id.org/security#verificationMethod> <https://example.org/foo/
         assert_ne!(vres.errors.len(), 0);
     }
 
+    #[async_std::test]
+    async fn credential_status_loader() {
+        use crate::revocation::MemoryResourceLoader;
+        use serde_json::json;
+        let credential_with_status = |index: &str, url: &str| -> Credential {
+            serde_json::from_value(json!({
+              "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                "https://w3id.org/vc/status-list/2021/v1"
+              ],
+              "id": "https://example.com/credentials/23894672394",
+              "type": ["VerifiableCredential"],
+              "issuer": "did:example:12345",
+              "issuanceDate": "2021-04-05T14:27:42Z",
+              "credentialStatus": {
+                "id": "_:1",
+                "type": "StatusList2021Entry",
+                "statusPurpose": "revocation",
+                "statusListIndex": index,
+                "statusListCredential": url
+              },
+              "credentialSubject": {
+                "id": "did:example:6789",
+                "type": "Person"
+              }
+            }))
+            .unwrap()
+        };
+
+        // The context loader's resource loader is used instead of fetching the list.
+        let vc = credential_with_status("94567", EXAMPLE_STATUS_LIST_2021_URL);
+        let mut context_loader =
+            ContextLoader::default().with_resource_loader(MemoryResourceLoader::new());
+        let vres = vc.check_status(&DIDExample, &mut context_loader).await;
+        assert_eq!(vres.errors.len(), 1);
+        assert!(vres.errors[0].message.contains("Not found"));
+        let mut loader = MemoryResourceLoader::new();
+        loader.insert(
+            EXAMPLE_STATUS_LIST_2021_URL,
+            EXAMPLE_STATUS_LIST_2021.to_vec(),
+        );
+        let mut context_loader = ContextLoader::default().with_resource_loader(loader);
+        let vres = vc.check_status(&DIDExample, &mut context_loader).await;
+        assert!(vres.errors.is_empty(), "{:?}", vres.errors);
+
+        // The list credential can be embedded in a data URL.
+        let data_url = format!(
+            "data:application/json;base64,{}",
+            base64::encode(EXAMPLE_STATUS_LIST_2021)
+        );
+        let mut context_loader = ContextLoader::default();
+        let vc = credential_with_status("94567", &data_url);
+        let vres = vc.check_status(&DIDExample, &mut context_loader).await;
+        assert!(vres.errors.is_empty(), "{:?}", vres.errors);
+        let vc = credential_with_status("1", &data_url);
+        let vres = vc.check_status(&DIDExample, &mut context_loader).await;
+        assert_eq!(vres.errors[0].code, ProblemCode::Revoked);
+    }
+
+    #[async_std::test]
+    async fn credential_status_bitstring() {
+        use crate::revocation::StatusValue;
+        use serde_json::json;
+        // status list credential is generated in examples/issue-bitstring-status-list.rs
+        let credential_with_status = |status: Value| -> Credential {
+            serde_json::from_value(json!({
+              "@context": "https://www.w3.org/ns/credentials/v2",
+              "id": "https://example.com/credentials/23894672394",
+              "type": ["VerifiableCredential"],
+              "issuer": "did:example:12345",
+              "credentialStatus": status,
+              "credentialSubject": {
+                "id": "did:example:6789"
+              }
+            }))
+            .unwrap()
+        };
+        let mut resource_loader = crate::revocation::MemoryResourceLoader::new();
+        resource_loader.insert(
+            EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            EXAMPLE_BITSTRING_STATUS_LIST.to_vec(),
+        );
+        let mut context_loader =
+            crate::jsonld::ContextLoader::default().with_resource_loader(resource_loader);
+
+        let credential = credential_with_status(json!({
+            "id": "https://example.com/credentials/status/4#0",
+            "type": "BitstringStatusListEntry",
+            "statusPurpose": "suspension",
+            "statusListIndex": "0",
+            "statusListCredential": EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            "statusSize": 2
+        }));
+        let vres = credential.check_status(&DIDExample, &mut context_loader).await;
+        assert!(vres.errors.is_empty(), "{:?}", vres.errors);
+        assert!(vres.checks.contains(&Check::CredentialStatus));
+        assert_eq!(vres.statuses[0].value, 0);
+
+        let credential = credential_with_status(json!({
+            "id": "https://example.com/credentials/status/4#1",
+            "type": "BitstringStatusListEntry",
+            "statusPurpose": "suspension",
+            "statusListIndex": "1",
+            "statusListCredential": EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            "statusSize": 2
+        }));
+        let vres = credential.check_status(&DIDExample, &mut context_loader).await;
+        assert_eq!(vres.errors, vec!["Credential is suspended."]);
+        assert_eq!(vres.errors[0].code, ProblemCode::Suspended);
+        assert_eq!(vres.statuses[0].value, 1);
+
+        // Multi-bit status with messages, alongside another status entry.
+        let credential = credential_with_status(json!([{
+            "id": "https://example.com/credentials/status/4#0",
+            "type": "BitstringStatusListEntry",
+            "statusPurpose": "suspension",
+            "statusListIndex": "0",
+            "statusListCredential": EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            "statusSize": 2
+        }, {
+            "id": "https://example.com/credentials/status/4#2",
+            "type": "BitstringStatusListEntry",
+            "statusPurpose": "message",
+            "statusListIndex": "2",
+            "statusListCredential": EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            "statusSize": 2,
+            "statusMessage": [
+                {"status": "0x0", "message": "pending_review"},
+                {"status": "0x1", "message": "accepted"},
+                {"status": "0x2", "message": "rejected"}
+            ]
+        }]));
+        let vres = credential.check_status(&DIDExample, &mut context_loader).await;
+        assert!(vres.errors.is_empty(), "{:?}", vres.errors);
+        assert_eq!(
+            vres.statuses[1],
+            StatusValue {
+                purpose: "message".to_string(),
+                value: 2,
+                message: Some("rejected".to_string()),
+                status_list_credential: EXAMPLE_BITSTRING_STATUS_LIST_URL.to_string(),
+                status_list_index: 2,
+            }
+        );
+
+        // The status purpose must be a purpose of the list.
+        let credential = credential_with_status(json!({
+            "id": "https://example.com/credentials/status/4#1",
+            "type": "BitstringStatusListEntry",
+            "statusPurpose": "revocation",
+            "statusListIndex": "1",
+            "statusListCredential": EXAMPLE_BITSTRING_STATUS_LIST_URL,
+            "statusSize": 2
+        }));
+        let vres = credential.check_status(&DIDExample, &mut context_loader).await;
+        assert_eq!(vres.errors[0].code, ProblemCode::CredentialStatus);
+        assert!(vres.statuses.is_empty());
+    }
+
     #[async_std::test]
     async fn presentation_from_credential_issue_verify() {
         let vc_str = r###"{
@@ -8,7 +8,11 @@ use crate::jwk::JWK;
 use crate::ldp::{LinkedDataDocument, LinkedDataProofs, ProofPreparation};
 use crate::one_or_many::OneOrMany;
 use crate::rdf::DataSet;
-use crate::vc::{Check, LinkedDataProofOptions, Proof, ProofPurpose, VerificationResult, URI};
+use crate::signer::Signer;
+use crate::vc::{
+    Check, LinkedDataProofOptions, ProblemCode, Proof, ProofPurpose, VerificationProblem,
+    VerificationResult, URI,
+};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -82,11 +86,20 @@ where
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
         match &self.proof {
-            None => VerificationResult::error("No applicable proof"),
+            None => {
+                VerificationResult::problem(ProblemCode::NoApplicableProof, "No applicable proof")
+            }
             Some(proof) => {
                 let mut result = proof.verify(self, resolver, context_loader).await;
                 if proof.proof_purpose != Some(ProofPurpose::CapabilityDelegation) {
-                    result.errors.push("Incorrect Proof Purpose".into());
+                    result.errors.push(
+                        VerificationProblem::new(
+                            ProblemCode::ProofPurpose,
+                            "Incorrect Proof Purpose",
+                        )
+                        .with_check(Check::Proof)
+                        .with_verification_method(proof.verification_method.clone()),
+                    );
                 };
                 if result.errors.is_empty() {
                     result.checks.push(Check::Proof);
@@ -101,7 +114,9 @@ where
         S: Serialize + Send + Sync + Clone,
     {
         match &invocation.proof {
-            None => VerificationResult::error("No applicable proof"),
+            None => {
+                VerificationResult::problem(ProblemCode::NoApplicableProof, "No applicable proof")
+            }
             Some(proof) => {
                 let mut result = VerificationResult::new();
                 match (
@@ -119,25 +134,38 @@ where
                     (Some(ref id), URI::String(ref t_id)) => {
                         // ensure proof target cap ID and given
                         if id != t_id {
-                            result
-                                .errors
-                                .push("Target Capability IDs dont match".into())
+                            result = result.with_problem(
+                                ProblemCode::Capability,
+                                "Target Capability IDs dont match",
+                            );
                         };
                     }
-                    _ => result
-                        .errors
-                        .push("Missing proof target capability ID".into()),
+                    _ => {
+                        result = result.with_problem(
+                            ProblemCode::Capability,
+                            "Missing proof target capability ID",
+                        )
+                    }
                 };
                 match (&self.invoker, &proof.verification_method) {
                     // Ensure the proof's verification method is authorized as an invoker. TODO: also allow target_capability's capabilityDelegation verification methods.
                     (Some(URI::String(ref invoker)), Some(ref delegatee)) => {
                         if invoker != delegatee {
-                            result.errors.push("Incorrect Invoker".into());
+                            result.errors.push(
+                                VerificationProblem::new(
+                                    ProblemCode::Capability,
+                                    "Incorrect Invoker",
+                                )
+                                .with_verification_method(Some(delegatee.to_string())),
+                            );
                         }
                     }
-                    (_, None) => result
-                        .errors
-                        .push("Missing Proof Verification Method".into()),
+                    (_, None) => {
+                        result = result.with_problem(
+                            ProblemCode::Capability,
+                            "Missing Proof Verification Method",
+                        )
+                    }
                     _ => {}
                 };
                 result
@@ -148,7 +176,7 @@ where
     // https://w3c-ccg.github.io/ld-proofs/
     pub async fn generate_proof(
         &self,
-        jwk: &JWK,
+        signer: &dyn Signer,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
@@ -159,7 +187,7 @@ where
             "capabilityChain".into(),
             serde_json::to_value(capability_chain)?,
         );
-        LinkedDataProofs::sign(self, options, resolver, context_loader, jwk, Some(ps)).await
+        LinkedDataProofs::sign(self, options, resolver, context_loader, signer, Some(ps)).await
     }
 
     /// Prepare to generate a linked data proof. Returns the signing input for the caller to sign
@@ -284,11 +312,20 @@ where
         context_loader: &mut ContextLoader,
     ) -> VerificationResult {
         match &self.proof {
-            None => VerificationResult::error("No applicable proof"),
+            None => {
+                VerificationResult::problem(ProblemCode::NoApplicableProof, "No applicable proof")
+            }
             Some(proof) => {
                 let mut result = proof.verify(self, resolver, context_loader).await;
                 if proof.proof_purpose != Some(ProofPurpose::CapabilityInvocation) {
-                    result.errors.push("Incorrect Proof Purpose".into());
+                    result.errors.push(
+                        VerificationProblem::new(
+                            ProblemCode::ProofPurpose,
+                            "Incorrect Proof Purpose",
+                        )
+                        .with_check(Check::Proof)
+                        .with_verification_method(proof.verification_method.clone()),
+                    );
                 };
                 if result.errors.is_empty() {
                     result.checks.push(Check::Proof);
@@ -301,7 +338,7 @@ where
     // https://w3c-ccg.github.io/ld-proofs/
     pub async fn generate_proof(
         &self,
-        jwk: &JWK,
+        signer: &dyn Signer,
         options: &LinkedDataProofOptions,
         resolver: &dyn DIDResolver,
         context_loader: &mut ContextLoader,
@@ -309,7 +346,7 @@ where
     ) -> Result<Proof, Error> {
         let mut ps = Map::<String, Value>::new();
         ps.insert("capability".into(), serde_json::to_value(target)?);
-        LinkedDataProofs::sign(self, options, resolver, context_loader, jwk, Some(ps)).await
+        LinkedDataProofs::sign(self, options, resolver, context_loader, signer, Some(ps)).await
     }
 
     /// Prepare to generate a linked data proof. Returns the signing input for the caller to sign